@@ -0,0 +1,176 @@
+// Dump the contents of FMOD Studio banks: events, parameters, snapshots, buses, VCAs and strings
+// Run with: ./run_demos.sh bank_inspect [--json] [--no-strings] <bank|dir|glob>...
+// Examples:
+//   ./run_demos.sh bank_inspect                                  # SDK example banks
+//   ./run_demos.sh bank_inspect path/to/Build/Desktop            # every bank in a directory
+//   ./run_demos.sh bank_inspect "build/*.bank" --json > banks.json
+
+use libfmod::OutputType;
+use libfmod_demos::{
+    FmodSdk, StudioSession,
+    inspect::{self, EventInfo, Inspection, ParameterInfo},
+};
+use std::env;
+
+fn print_usage(program: &str) {
+    println!("Usage: {} [options] [bank|directory|glob]...", program);
+    println!();
+    println!("Options:");
+    println!("  --json          Print JSON instead of text (stable order, for diffing)");
+    println!("  --no-strings    Leave out string-table entries");
+    println!();
+    println!("Without arguments the SDK example banks are inspected.");
+}
+
+fn format_length(length_ms: i32) -> String {
+    if length_ms <= 0 {
+        "no timeline".to_string()
+    } else {
+        format!("{:.2}s", length_ms as f32 / 1000.0)
+    }
+}
+
+fn print_parameter(parameter: &ParameterInfo, indent: &str) {
+    let flags = if parameter.flags.is_empty() {
+        "-".to_string()
+    } else {
+        parameter.flags.join(",")
+    };
+    println!(
+        "{}{:<24} [{} .. {}] default {}  {}  flags: {}",
+        indent, parameter.name, parameter.minimum, parameter.maximum, parameter.default, parameter.kind, flags
+    );
+    if !parameter.labels.is_empty() {
+        println!("{}  labels: {}", indent, parameter.labels.join(", "));
+    }
+}
+
+fn print_event(event: &EventInfo) {
+    println!("    {}  {}", event.path, event.id);
+
+    let mut traits = vec![format_length(event.length_ms)];
+    if event.is_oneshot {
+        traits.push("one-shot".to_string());
+    }
+    if event.is_3d {
+        traits.push(format!("3D ({} - {}m)", event.min_distance, event.max_distance));
+    }
+    if event.is_stream {
+        traits.push("streaming".to_string());
+    }
+    if event.has_sustain_point {
+        traits.push("sustain point".to_string());
+    }
+    println!("      {}", traits.join(", "));
+
+    for parameter in &event.parameters {
+        print_parameter(parameter, "      ");
+    }
+}
+
+fn print_text(inspection: &Inspection) {
+    for bank in &inspection.banks {
+        match &bank.path {
+            Some(path) => println!("\n📦 {} ({})", bank.file.display(), path),
+            None => println!("\n📦 {}", bank.file.display()),
+        }
+
+        if !bank.events.is_empty() {
+            println!("  Events ({})", bank.events.len());
+            bank.events.iter().for_each(print_event);
+        }
+        if !bank.snapshots.is_empty() {
+            println!("  Snapshots ({})", bank.snapshots.len());
+            bank.snapshots.iter().for_each(print_event);
+        }
+        if !bank.buses.is_empty() {
+            println!("  Buses ({})", bank.buses.len());
+            for bus in &bank.buses {
+                println!("    {}", bus);
+            }
+        }
+        if !bank.vcas.is_empty() {
+            println!("  VCAs ({})", bank.vcas.len());
+            for vca in &bank.vcas {
+                println!("    {}", vca);
+            }
+        }
+        if !bank.strings.is_empty() {
+            println!("  Strings ({})", bank.strings.len());
+            for entry in &bank.strings {
+                println!("    {}  {}", entry.id, entry.path);
+            }
+        }
+    }
+
+    if !inspection.global_parameters.is_empty() {
+        println!("\n🌍 Global parameters ({})", inspection.global_parameters.len());
+        for parameter in &inspection.global_parameters {
+            print_parameter(parameter, "    ");
+        }
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().collect();
+
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        print_usage(&args[0]);
+        return Ok(());
+    }
+
+    let mut json = false;
+    let mut strings = true;
+    let mut inputs = Vec::new();
+    for arg in args.iter().skip(1) {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--no-strings" => strings = false,
+            flag if flag.starts_with("--") => {
+                eprintln!("❌ unknown option {}\n", flag);
+                print_usage(&args[0]);
+                std::process::exit(2);
+            }
+            input => inputs.push(input.to_string()),
+        }
+    }
+    if inputs.is_empty() {
+        let sdk = FmodSdk::locate()?;
+        inputs.push(sdk.example_banks_dir().to_string_lossy().into_owned());
+    }
+
+    let files = inspect::find_banks(&inputs)?;
+    if files.is_empty() {
+        return Err(format!("no .bank files found in {}", inputs.join(", ")).into());
+    }
+
+    // Nothing is played, so skip the sound card entirely
+    let mut session = StudioSession::builder().output(OutputType::NosoundNrt).build()?;
+
+    // One bad bank (or a second localized variant) shouldn't hide the rest
+    let (files, failed) = session.load_banks(files);
+    for error in &failed {
+        eprintln!("⚠️  {} (skipped)", error);
+    }
+    if files.is_empty() {
+        return Err("no bank could be loaded".into());
+    }
+
+    let mut inspection = inspect::inspect(session.studio(), session.banks(), &files)?;
+    if !strings {
+        for bank in &mut inspection.banks {
+            bank.strings.clear();
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&inspection)?);
+    } else {
+        println!("\n🔎 FMOD Bank Inspector ({} banks)", inspection.banks.len());
+        print_text(&inspection);
+        println!();
+    }
+
+    session.close()?;
+    Ok(())
+}
@@ -0,0 +1,108 @@
+// Golden-audio regression test: render scenarios offline and compare them to reference WAVs
+// Run with: ./run_demos.sh golden_audio_test [--bless] [--allow-missing] [--golden-dir DIR] [--out-dir DIR] [scenario...]
+// Examples:
+//   ./run_demos.sh golden_audio_test                 # compare every scenario
+//   ./run_demos.sh golden_audio_test explosion       # compare one scenario
+//   ./run_demos.sh golden_audio_test --bless         # store current renders as the new references
+//
+// A scenario without a reference fails the run unless --allow-missing is given.
+
+use libfmod_demos::golden::{self, GOLDEN_DIR, Outcome};
+use std::{env, path::PathBuf, process};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().collect();
+
+    let mut bless = false;
+    let mut allow_missing = false;
+    let mut golden_dir = PathBuf::from(GOLDEN_DIR);
+    let mut out_dir = PathBuf::from("target/golden");
+    let mut names = Vec::new();
+
+    let mut iter = args.iter().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--bless" => bless = true,
+            "--allow-missing" => allow_missing = true,
+            "--golden-dir" => golden_dir = iter.next().ok_or("--golden-dir needs a value")?.into(),
+            "--out-dir" => out_dir = iter.next().ok_or("--out-dir needs a value")?.into(),
+            "--list" => {
+                for scenario in golden::builtin_scenarios() {
+                    println!("{:<26} {}", scenario.name, scenario.description);
+                }
+                return Ok(());
+            }
+            name => names.push(name.to_string()),
+        }
+    }
+
+    let scenarios = if names.is_empty() {
+        golden::builtin_scenarios()
+    } else {
+        names
+            .iter()
+            .map(|name| golden::find_scenario(name).ok_or(format!("unknown scenario '{}' (see --list)", name)))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    println!("\n🎧 Golden Audio Regression Test\n");
+    println!("References: {}", golden_dir.display());
+    println!("Renders:    {}\n", out_dir.display());
+
+    let mut failed = Vec::new();
+    let mut skipped = Vec::new();
+    for scenario in &scenarios {
+        println!("▶ {} - {}", scenario.name, scenario.description);
+
+        let outcome = match golden::run_scenario(scenario, &out_dir, &golden_dir, bless) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                println!("    ✗ {}\n", e);
+                failed.push(scenario.name);
+                continue;
+            }
+        };
+
+        let reference = scenario.reference_path(&golden_dir);
+        match &outcome {
+            Outcome::Compared(comparison) => {
+                println!("    render:    {}", comparison.actual);
+                println!("    reference: {}", comparison.reference);
+                for check in &comparison.checks {
+                    println!("    {}", check);
+                }
+            }
+            Outcome::MissingReference(stats) => {
+                println!("    render:    {}", stats);
+                if allow_missing {
+                    println!("    - skipped: no reference at {}", reference.display());
+                } else {
+                    println!("    ✗ no reference at {} (run with --bless to create it, or --allow-missing to skip)",
+                        reference.display());
+                }
+            }
+            Outcome::Blessed(stats) => {
+                println!("    render:    {}", stats);
+                println!("    ✓ stored as {}", reference.display());
+            }
+        }
+        if outcome.failed(allow_missing) {
+            failed.push(scenario.name);
+        } else if outcome.skipped(allow_missing) {
+            skipped.push(scenario.name);
+        }
+        println!();
+    }
+
+    if failed.is_empty() {
+        let passed = scenarios.len() - skipped.len();
+        println!("✅ {}/{} scenarios passed", passed, scenarios.len());
+        if !skipped.is_empty() {
+            println!("⏭️  {} skipped without a reference: {}", skipped.len(), skipped.join(", "));
+        }
+        Ok(())
+    } else {
+        println!("❌ {}/{} scenarios failed: {}", failed.len(), scenarios.len(), failed.join(", "));
+        process::exit(1);
+    }
+}
@@ -3,16 +3,30 @@
 // Examples:
 //   ./run_fmod.sh harness_demo explosion
 //   ./run_fmod.sh harness_demo spatial
+//   ./run_fmod.sh harness_demo paths
 //   ./run_fmod.sh harness_demo parameters
 //   ./run_fmod.sh harness_demo footsteps
+//   ./run_fmod.sh harness_demo snapshots
 //   ./run_fmod.sh harness_demo all (default)
-
-use libfmod::{Studio, StudioInit, Init, LoadBank, StopMode, Vector, Attributes3d, SpeakerMode};
-use libfmod_demos;
-use std::{thread, time::Duration, env};
+//   ./run_fmod.sh harness_demo all --output nosound   (no sound card needed)
+//   ./run_fmod.sh harness_demo scenes/drive_by.yaml   (any scene file, see scenes/README.md)
+
+use libfmod::{EventInstance, StopMode, Vector, Attributes3d, SpeakerMode};
+use libfmod_demos::{
+    Curve, Interpolation, Looping, Meter, MotionPath, OutputOptions, ParameterTarget, Scene, Snapshot, StudioSession, UpdateLoop,
+    run_scene, set_parameter,
+    analysis::to_db,
+    scene::SceneFormat,
+    spatial::{self, VelocityTracker},
+};
+use std::{
+    io::{self, Write},
+    path::Path,
+    time::Duration,
+};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = env::args().collect();
+    let (output, args) = OutputOptions::from_process_args()?;
     let demo_name = if args.len() > 1 {
         args[1].as_str()
     } else {
@@ -27,17 +41,30 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("Running demo: {}\n", demo_name);
     }
 
-    // Initialize Studio with explicit stereo output configuration
-    let studio = Studio::create()?;
-
-    // Get the core system BEFORE initialization to configure audio output
-    let core = studio.get_core_system()?;
-
-    // Set speaker mode to stereo for proper 3D panning (must be done before init)
-    core.set_software_format(Some(48000), Some(SpeakerMode::Stereo), Some(0))?;
+    // A scene file instead of a built-in demo
+    let scene_path = Path::new(demo_name);
+    if SceneFormat::from_path(scene_path).is_some() {
+        let scene = Scene::load(scene_path)?;
+        println!(">>> SCENE: {}", if scene.name.is_empty() { demo_name } else { &scene.name });
+        let base_dir = scene_path.parent().unwrap_or(Path::new("."));
+        run_scene(&scene, base_dir, &output, |cue| {
+            println!("  [{:6.2}s] {}", cue.at.as_secs_f32(), cue.message);
+        })?;
+        println!("OK - Scene complete\n");
+        return Ok(());
+    }
 
-    // Now initialize the studio system
-    studio.initialize(1024, StudioInit::NORMAL, Init::NORMAL, None)?;
+    // Initialize Studio with explicit stereo output configuration
+    // (speaker mode must be set before init for proper 3D panning)
+    let session = StudioSession::builder()
+        .sample_rate(48000)
+        .speaker_mode(SpeakerMode::Stereo)
+        .output_options(&output)
+        .example_banks(&["Master.bank", "Master.strings.bank", "SFX.bank", "Vehicles.bank"])
+        .build()?;
+    let studio = session.studio();
+    let core = session.core()?;
+    let mut clock = UpdateLoop::for_output(studio, &output)?;
 
     // Get and display output info
     let output_type = core.get_output()?;
@@ -57,13 +84,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("  Rolloff Scale: {}", rolloff);
     println!();
 
-    // Load banks
-    let bank_dir = libfmod_demos::get_example_banks_dir()?;
-    let master = studio.load_bank_file(&format!("{}/Master.bank", bank_dir), LoadBank::NORMAL)?;
-    let strings = studio.load_bank_file(&format!("{}/Master.strings.bank", bank_dir), LoadBank::NORMAL)?;
-    let sfx = studio.load_bank_file(&format!("{}/SFX.bank", bank_dir), LoadBank::NORMAL)?;
-    let vehicles = studio.load_bank_file(&format!("{}/Vehicles.bank", bank_dir), LoadBank::NORMAL)?;
-
     println!("OK - Banks loaded: Master, SFX, Vehicles\n");
 
     // Demo 1: Event Playback
@@ -78,17 +98,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     explosion.start()?;
 
     // Let explosion play fully (3 seconds)
-    for _ in 0..60 {
-        studio.update()?;
-        thread::sleep(Duration::from_millis(50));
-    }
+    clock.run_for(Duration::from_secs(3))?;
     explosion.release()?;
     println!("OK - Explosion complete\n");
     }
 
     // Pause between demos
     if demo_name == "all" {
-        thread::sleep(Duration::from_secs(1));
+        clock.run_for(Duration::from_secs(1))?;
     }
 
     // Demo 2: 3D Spatial Audio
@@ -153,15 +170,27 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Vehicle drives along the road at Z=0, from X=+10 to X=-10");
     println!("Note: In FMOD, positive X = left speaker, negative X = right speaker\n");
 
-    for i in 0..100 {
-        let progress = i as f32 / 100.0;
-        // Reverse the movement to match audio: start at +10 (left), go to -10 (right)
-        let x = 10.0 - (progress * 20.0);  // +10 to -10
-        let z = 0.0;  // Keep on same plane as listener
+    // Reverse the movement to match audio: start at +10 (left), go to -10 (right),
+    // on the same plane as the listener
+    let road = MotionPath::linear(Vector { x: 10.0, y: 0.0, z: 0.0 }, Vector { x: -10.0, y: 0.0, z: 0.0 })
+        .speed(2.0)
+        .looping(Looping::Once);
+    let drive_time = road.duration().unwrap_or_default();
+    let drive_start = clock.time();
+    // Velocity comes from the actual movement per tick (2 units/s), for correct doppler
+    let mut tracker = VelocityTracker::new();
+    tracker.update(&road.position_at(Duration::ZERO), Duration::ZERO);
+    let mut last_rpm = drive_start;
+    let mut last_draw = drive_start;
+    clock.run_for_with(drive_time, |tick| {
+        let elapsed = tick.time - drive_start;
+        let progress = (elapsed.as_secs_f32() / drive_time.as_secs_f32()).min(1.0);
+        let position = road.position_at(elapsed);
+        let x = position.x;
 
         let attributes = Attributes3d {
-            position: Vector { x, y: 0.0, z },
-            velocity: Vector { x: -0.2, y: 0.0, z: 0.0 },  // Negative velocity
+            velocity: tracker.update(&position, tick.delta),
+            position,
             forward: Vector { x: 1.0, y: 0.0, z: 0.0 },
             up: Vector { x: 0.0, y: 1.0, z: 0.0 },
         };
@@ -169,12 +198,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         vehicle.set_3d_attributes(attributes)?;
 
         // Keep RPM at constant 2000
-        if i % 20 == 0 {
+        if tick.time - last_rpm >= Duration::from_secs(2) {
             vehicle.set_parameter_by_name("RPM", 2000.0, false)?;
+            last_rpm = tick.time;
         }
 
         // Visual position indicator - now matches audio direction
-        if i % 2 == 0 {
+        if tick.time - last_draw >= Duration::from_millis(200) {
+            last_draw = tick.time;
             // Visual goes left to right as audio does
             let visual_pos = (progress * 40.0) as usize;
             let line = format!("{:>width$}▶", "", width = visual_pos.min(40));
@@ -184,31 +215,87 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             print!("\r  [{:<40}] X:{:5.1} Distance:{:4.1}m ({})", line, x, distance,
                    if x > 5.0 { "Left" } else if x < -5.0 { "Right" } else { "Center" });
-            use std::io::{self, Write};
             io::stdout().flush()?;
         }
 
-        // Update both studio and core systems for 3D processing
-        studio.update()?;
-        core.update()?;
-        thread::sleep(Duration::from_millis(100));
-    }
+        // studio.update() (run by the clock) also updates the core system
+        Ok::<_, Box<dyn std::error::Error>>(())
+    })?;
 
     println!("\nStopping vehicle...");
     vehicle.stop(StopMode::AllowFadeout)?;
 
     // Let fadeout complete (2 seconds)
-    for _ in 0..40 {
-        studio.update()?;
-        thread::sleep(Duration::from_millis(50));
-    }
+    clock.run_for(Duration::from_secs(2))?;
     vehicle.release()?;
     println!("OK - 3D movement complete\n");
     }
 
     // Pause between demos
     if demo_name == "all" {
-        thread::sleep(Duration::from_secs(1));
+        clock.run_for(Duration::from_secs(1))?;
+    }
+
+    // Demo 2b: Motion paths
+    if demo_name == "all" || demo_name == "paths" {
+    println!(">>> DEMO 2b: Motion Paths");
+    println!("-------------------------");
+
+    // Listener at the origin facing -Z
+    let listener = spatial::Listener::new(spatial::ZERO);
+    spatial::apply_listeners(studio, std::slice::from_ref(&listener))?;
+
+    let center = spatial::ZERO;
+    let paths = [
+        MotionPath::orbit(center.clone(), 8.0).speed(5.0),
+        MotionPath::figure_eight(center.clone(), 10.0).speed(6.0),
+        MotionPath::spline(vec![
+            spatial::vector(-15.0, 0.0, -10.0),
+            spatial::vector(-5.0, 0.0, -2.0),
+            spatial::vector(5.0, 0.0, -12.0),
+            spatial::vector(15.0, 0.0, -3.0),
+        ])
+        .speed(6.0)
+        .looping(Looping::PingPong),
+        MotionPath::fly_over(center.clone(), 6.0, 25.0).speed(12.0).looping(Looping::Once),
+    ];
+    let leg_time = Duration::from_secs(6);
+
+    let vehicle_desc = studio.get_event("event:/Vehicles/Ride-on Mower")?;
+    for path in paths {
+        println!("  {} ({:.0}m at {}m/s, {})", path.shape().name(), path.length(),
+                 path.units_per_second(), path.looping_mode());
+
+        let vehicle = vehicle_desc.create_instance()?;
+        vehicle.set_parameter_by_name("RPM", 2000.0, false)?;
+        let mut emitter = spatial::Emitter::new(path.position_at(Duration::ZERO));
+        vehicle.set_3d_attributes(emitter.attributes())?;
+        vehicle.start()?;
+
+        let mut tracker = VelocityTracker::new();
+        tracker.update(&emitter.position, Duration::ZERO);
+        let start = clock.time();
+        let duration = match path.looping_mode() {
+            Looping::Once => path.duration().unwrap_or(leg_time),
+            Looping::Loop | Looping::PingPong => leg_time,
+        };
+        clock.run_for_with(duration, |tick| {
+            emitter.position = path.position_at(tick.time - start);
+            emitter.velocity = tracker.update(&emitter.position, tick.delta);
+            vehicle.set_3d_attributes(emitter.attributes())?;
+            Ok::<_, libfmod::Error>(())
+        })?;
+
+        vehicle.stop(StopMode::AllowFadeout)?;
+        vehicle.release()?;
+        clock.run_for(Duration::from_millis(500))?;
+    }
+    println!("OK - Motion paths complete\n");
+    }
+
+    // Pause between demos
+    if demo_name == "all" {
+        clock.run_for(Duration::from_secs(1))?;
     }
 
     // Demo 3: Parameter Control
@@ -220,53 +307,38 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let vehicle2 = vehicle_desc.create_instance()?;
 
 
-    // Start with RPM at 0 (idle)
-    vehicle2.set_parameter_by_name("RPM", 0.0, false)?;
     vehicle2.start()?;
 
-    println!("Adjusting vehicle RPM:");
-    println!("(Each RPM level will play for 2 seconds)");
-
     // RPM ranges from 0 to 2000 based on parameter description
-    for rpm_level in [0.0, 250.0, 500.0, 750.0, 1000.0, 1250.0, 1500.0, 1750.0, 2000.0, 1000.0, 0.0] {
-        print!("\n  RPM: {:4.0} ", rpm_level);
-
-        // Visual RPM meter (scaled to 0-2000 range)
-        let bar_length = (rpm_level / 100.0) as usize;
-        for i in 0..20 {
-            if i < bar_length {
-                print!("█");
-            } else {
-                print!("░");
-            }
-        }
-        use std::io::{self, Write};
-        io::stdout().flush()?;
-
-        vehicle2.set_parameter_by_name("RPM", rpm_level, false)?;
-
-
-        // Hold each RPM level for 2 seconds
-        for _ in 0..40 {
-            studio.update()?;
-            thread::sleep(Duration::from_millis(50));
-        }
-    }
+    println!("Ramping vehicle RPM:");
+    println!("(Eased up to 2000 over 8s, held, then back down to idle)");
+    let ramp = Curve::keyframes(
+        &[
+            (Duration::ZERO, 0.0),
+            (Duration::from_secs(8), 2000.0),
+            (Duration::from_secs(10), 2000.0),
+            (Duration::from_secs(14), 0.0),
+        ],
+        Interpolation::Ease,
+    );
+    clock.automate(ParameterTarget::Instance(vehicle2), "RPM", ramp)?;
+    show_rpm(&mut clock, &vehicle2, Duration::from_secs(14))?;
+
+    println!("\nWobbling RPM with an LFO (1000 +/- 600, 0.5 Hz):");
+    clock.automate(ParameterTarget::Instance(vehicle2), "RPM", Curve::lfo(1000.0, 600.0, 0.5))?;
+    show_rpm(&mut clock, &vehicle2, Duration::from_secs(6))?;
     println!();
 
     println!("\nStopping engine...");
     vehicle2.stop(StopMode::AllowFadeout)?;
-    for _ in 0..40 {
-        studio.update()?;
-        thread::sleep(Duration::from_millis(50));
-    }
+    clock.run_for(Duration::from_secs(2))?;
     vehicle2.release()?;
     println!("OK - Parameter control complete\n");
     }
 
     // Pause between demos
     if demo_name == "all" {
-        thread::sleep(Duration::from_secs(1));
+        clock.run_for(Duration::from_secs(1))?;
     }
 
     // Demo 4: Multiple Instances
@@ -290,19 +362,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         };
 
         print!(".");
-        use std::io::{self, Write};
         io::stdout().flush()?;
 
         let footstep = footstep_desc.create_instance()?;
-        footstep.set_parameter_by_name("Surface", surface, false).ok();
+        set_parameter(&footstep, "Surface", surface)?;
         footstep.start()?;
         footstep.release()?;
 
         // Half second between steps
-        for _ in 0..10 {
-            studio.update()?;
-            thread::sleep(Duration::from_millis(50));
-        }
+        clock.run_for(Duration::from_millis(500))?;
 
         if step % 5 == 0 {
             println!("  ({})", surface_name);
@@ -317,9 +385,46 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\nOK - Multiple instances complete\n");
     }
 
+    // Pause between demos
+    if demo_name == "all" {
+        clock.run_for(Duration::from_secs(1))?;
+    }
+
+    // Demo 5: Snapshots
+    if demo_name == "all" || demo_name == "snapshots" {
+    println!(">>> DEMO 5: Snapshot Ducking");
+    println!("----------------------------");
+
+    let meter = Meter::master(&core)?;
+    let ambience = studio.get_event("event:/Ambience/Country")?.create_instance()?;
+    let mut pause = Snapshot::find(studio, "snapshot:/IngamePause")?;
+
+    println!("Country ambience at full level:");
+    ambience.start()?;
+    show_ducking(&mut clock, &meter, &mut pause, Duration::from_secs(3), None)?;
+
+    println!("\nStarting {} and raising its intensity 0 -> 100:", pause.info.path);
+    pause.set_intensity(0.0)?;
+    pause.start()?;
+    show_ducking(&mut clock, &meter, &mut pause, Duration::from_secs(4), Some((0.0, 100.0)))?;
+
+    println!("\nHolding the duck:");
+    show_ducking(&mut clock, &meter, &mut pause, Duration::from_secs(2), None)?;
+
+    println!("\nLowering the intensity back to 0:");
+    show_ducking(&mut clock, &meter, &mut pause, Duration::from_secs(3), Some((100.0, 0.0)))?;
+
+    println!("\nStopping the snapshot and the ambience...");
+    pause.stop(StopMode::AllowFadeout)?;
+    ambience.stop(StopMode::AllowFadeout)?;
+    clock.run_for(Duration::from_secs(2))?;
+    ambience.release()?;
+    println!("OK - Snapshot complete\n");
+    }
+
     // Final pause
     if demo_name == "all" {
-        thread::sleep(Duration::from_secs(1));
+        clock.run_for(Duration::from_secs(1))?;
     }
 
     // Summary
@@ -332,6 +437,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("   * 3D spatial positioning (WASD + QE)                    ");
     println!("   * Parameter adjustment (+/-)                            ");
     println!("   * Multiple event instances                              ");
+    println!("   * Snapshots with intensity control                      ");
     println!("   * Visual feedback and status display                    ");
     println!("                                                            ");
     println!(" To use the full interactive version, run:                 ");
@@ -341,11 +447,58 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Cleanup
-    vehicles.unload()?;
-    sfx.unload()?;
-    strings.unload()?;
-    master.unload()?;
-    studio.release()?;
+    session.close()?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Run the clock for `duration`, redrawing the instance's RPM as a bar every 250ms
+fn show_rpm(clock: &mut UpdateLoop, instance: &EventInstance, duration: Duration) -> Result<(), libfmod::Error> {
+    let mut next_draw = clock.time();
+    clock.run_for_with(duration, |tick| {
+        if tick.time < next_draw {
+            return Ok(());
+        }
+        next_draw = tick.time + Duration::from_millis(250);
+
+        // RPM ranges from 0 to 2000, 100 per cell
+        let (rpm, _) = instance.get_parameter_by_name("RPM")?;
+        let filled = ((rpm / 100.0).round().max(0.0) as usize).min(20);
+        print!("\r  RPM: {:4.0} {}{}", rpm, "█".repeat(filled), "░".repeat(20 - filled));
+        let _ = io::stdout().flush();
+        Ok(())
+    })
+}
+
+/// Run the clock for `duration`, drawing the snapshot intensity and the master level
+/// every 250ms; `ramp` moves the intensity linearly from one value to another
+fn show_ducking(
+    clock: &mut UpdateLoop,
+    meter: &Meter,
+    snapshot: &mut Snapshot,
+    duration: Duration,
+    ramp: Option<(f32, f32)>,
+) -> Result<(), libfmod::Error> {
+    let start = clock.time();
+    let mut next_draw = start;
+    clock.run_for_with(duration, |tick| {
+        if let Some((from, to)) = ramp {
+            let t = ((tick.time - start).as_secs_f32() / duration.as_secs_f32()).min(1.0);
+            snapshot.set_intensity(from + (to - from) * t)?;
+        }
+        if tick.time < next_draw {
+            return Ok(());
+        }
+        next_draw = tick.time + Duration::from_millis(250);
+
+        // Master peak from -60 dB to 0 dB, 3 dB per cell
+        let db = to_db(meter.read()?.max_peak());
+        let filled = (((db + 60.0) / 3.0).round().max(0.0) as usize).min(20);
+        let intensity = if snapshot.is_active() { format!("{:5.1}", snapshot.intensity) } else { "  off".to_string() };
+        print!("\r  Intensity: {}  Master: {:6.1} dB {}{}", intensity, db, "█".repeat(filled), "░".repeat(20 - filled));
+        let _ = io::stdout().flush();
+        Ok(())
+    })?;
+    println!();
+    Ok(())
+}
@@ -1,5 +1,6 @@
 // Interactive FMOD Studio Test Harness - Real-time control for all features
-// Run with: ./run_fmod.sh interactive_harness
+// Run with: ./run_fmod.sh interactive_harness [bank|directory|glob]...
+// Without arguments the SDK example banks are loaded; every event in the loaded banks is listed.
 
 use crossterm::{
     event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
@@ -8,79 +9,297 @@ use crossterm::{
     cursor,
     style::{Color, Print, ResetColor, SetForegroundColor, Attribute, SetAttribute},
 };
-use libfmod::{Studio, StudioInit, Init, LoadBank, StopMode, EventDescription, EventInstance, Bank, Vector, Attributes3d};
-use libfmod_demos;
+use libfmod::{Init, StopMode, EventDescription, EventInstance, PlaybackState, Vector};
+use libfmod_demos::{
+    Blip, EventInfo, EventTree, Follower, GlobalParameter, InstanceRegistry, Levels, Looping, Meter, Mixer, MotionPath, OutputOptions,
+    ParameterValue, PlaybackInfo, Radar, Shape, Snapshot, Spectrum, StudioSession, TreeRow, analysis::to_db, edit_global_parameter,
+    inspect,
+    radar::Mark,
+    spatial::{self, Emitter, Listener, VelocityTracker},
+};
 use std::{
-    collections::HashMap,
+    collections::BTreeMap,
     io::{self, Write},
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
+// Rows of the event browser shown at once
+const TREE_HEIGHT: usize = 14;
+
+// Up to this many events the browser starts fully expanded
+const EXPAND_ALL_LIMIT: usize = 40;
+
+// Split-screen testing needs at most four players
+const MAX_LISTENERS: usize = 4;
+
+// Degrees per yaw key press (Shift for fine control)
+const YAW_STEP: f32 = 15.0;
+
+// Key presses move things in 1m jumps; average velocity over this long
+// so doppler follows the overall motion instead of spiking per press
+const VELOCITY_SMOOTHING: Duration = Duration::from_millis(250);
+
+// Speed of a newly attached motion path and the range [J]/[K] step through
+const PATH_SPEED: f32 = 4.0;
+const PATH_SPEED_RANGE: std::ops::RangeInclusive<f32> = 0.5..=40.0;
+
+// Where new instances appear until the spawn point is moved
+const DEFAULT_SPAWN: Vector = Vector { x: 0.0, y: 0.0, z: -5.0 };
+
+// Random spawns land this far (in metres) from the selected listener
+const SPAWN_RADIUS: std::ops::Range<f32> = 2.0..20.0;
+
+// Radar size in cells and where it is drawn, to the right of the panels
+const RADAR_WIDTH: usize = 41;
+const RADAR_HEIGHT: usize = 21;
+const RADAR_COLUMN: u16 = 76;
+const RADAR_ROW: u16 = 4;
+
+// Mixer volume step per [+]/[-] press, and the fader's top end (+6 dB)
+const VOLUME_STEP: f32 = 0.05;
+const MAX_VOLUME: f32 = 2.0;
+
+// Snapshot intensity step per [+]/[-] press
+const INTENSITY_STEP: f32 = 10.0;
+
+// Level meters span this many dB below full scale
+const METER_FLOOR_DB: f32 = -60.0;
+
+// Spectrum view size: log-spaced bands across, rows of bar height, and the
+// level at the bottom row (FFT magnitudes sit well below the peak level)
+const SPECTRUM_BANDS: usize = 64;
+const SPECTRUM_HEIGHT: usize = 8;
+const SPECTRUM_FLOOR_DB: f32 = -80.0;
+
+const DEFAULT_BANKS: [&str; 5] = [
+    "Master.bank",
+    "Master.strings.bank",
+    "SFX.bank",
+    "Music.bank",
+    "Vehicles.bank",
+];
+
+// An event discovered in the loaded banks
+struct HarnessEvent {
+    description: EventDescription,
+    info: EventInfo,
+    // Value of each entry in `info.parameters`, applied to new instances
+    values: Vec<f32>,
+}
+
+impl HarnessEvent {
+    fn new(description: EventDescription, info: EventInfo) -> Self {
+        let values = info.parameters.iter().map(|p| p.default).collect();
+        HarnessEvent { description, info, values }
+    }
+
+    // Indices of the parameters game code may set
+    fn settable_parameters(&self) -> Vec<usize> {
+        (0..self.info.parameters.len())
+            .filter(|i| self.info.parameters[*i].is_settable())
+            .collect()
+    }
+}
+
+// A looping/continuous instance the harness keeps track of
+struct ActiveInstance {
+    // Index into `HarnessState::events`
+    event: usize,
+    instance: EventInstance,
+    emitter: Emitter,
+    tracker: VelocityTracker,
+    // Motion path the instance travels along, if any
+    follower: Option<Follower>,
+    // Live state, timeline and mix settings, read every frame
+    playback: Option<PlaybackInfo>,
+}
+
+// Which panel the arrow keys drive
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Focus {
+    Events,
+    Parameters,
+    Globals,
+    Snapshots,
+}
+
+// What WASD/QE and the yaw keys move
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mover {
+    Source,
+    Listener,
+}
+
+// What the parameter panel edits
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Target {
+    // Values used for the next instance of an event (also covers one-shots)
+    Defaults(usize),
+    // A playing instance, by instance id
+    Instance { id: usize, event: usize },
+}
+
+impl Target {
+    fn event(&self) -> usize {
+        match self {
+            Target::Defaults(event) | Target::Instance { event, .. } => *event,
+        }
+    }
+}
+
 // Harness state
 struct HarnessState {
-    studio: Studio,
-    banks: Vec<Bank>,
-    event_descriptions: Vec<(String, EventDescription)>,
-    active_instances: HashMap<usize, EventInstance>,
+    session: StudioSession,
+    events: Vec<HarnessEvent>,
+    // Keyed by instance id, so iteration follows creation order
+    active_instances: BTreeMap<usize, ActiveInstance>,
+    next_instance_id: usize,
+    // Every instance created, one-shots included, until FMOD destroys it
+    registry: InstanceRegistry,
+    // Leak report shown after [C], until the next check
+    leak_report: Option<Vec<String>>,
+    tree: EventTree,
+    searching: bool,
     selected_event: usize,
+    selected_target: usize,
     selected_parameter: usize,
+    focus: Focus,
+
+    // Every bus and VCA; the mixer screen replaces the panels while shown
+    mixer: Mixer,
+    selected_strip: usize,
+    // Why the last mixer key did nothing (e.g. muting a VCA)
+    mixer_message: Option<String>,
+
+    // Per-channel output levels of the master channel group; buses locked in
+    // the mixer are metered beside it
+    master_meter: Meter,
+    master_levels: Option<Levels>,
+    // FFT on the master channel group while the spectrum view is shown
+    spectrum: Option<Spectrum>,
+    spectrum_bands: Vec<f32>,
+
+    // Every snapshot in the loaded banks; started ones hold an instance
+    snapshots: Vec<Snapshot>,
+    selected_snapshot: usize,
+
+    // Global parameters, values refreshed every frame
+    globals: Vec<GlobalParameter>,
+    selected_global: usize,
+    // Text typed after [G]: "name-or-id=value-or-label"
+    global_input: Option<String>,
+    // Result of the last typed edit, or why it failed
+    global_message: Option<String>,
 
     // 3D position
-    listener_pos: Vector,
-    source_pos: Vector,
+    listeners: Vec<Listener>,
+    // One per listener, deriving its velocity from frame-to-frame movement
+    listener_trackers: Vec<VelocityTracker>,
+    selected_listener: usize,
+    mover: Mover,
+    // Id of the playing 3D instance WASD/QE moves; None moves the spawn point
+    selected_instance: Option<usize>,
+    spawn_pos: Vector,
+    // Text typed after [T], spawning at that coordinate on Enter
+    coordinate_input: Option<String>,
+    rng: u64,
 
     // Display state
     show_help: bool,
+    show_radar: bool,
+    show_mixer: bool,
+    last_frame: Instant,
     last_update: Instant,
     frame_count: u32,
     fps: f32,
 }
 
 impl HarnessState {
-    fn new() -> Result<Self, Box<dyn std::error::Error>> {
-        // Initialize Studio
-        let studio = Studio::create()?;
-        studio.initialize(1024, StudioInit::NORMAL, Init::NORMAL, None)?;
-
-        // Load all banks
-        let bank_dir = libfmod_demos::get_example_banks_dir()?;
-        let mut banks = Vec::new();
-
-        banks.push(studio.load_bank_file(&format!("{}/Master.bank", bank_dir), LoadBank::NORMAL)?);
-        banks.push(studio.load_bank_file(&format!("{}/Master.strings.bank", bank_dir), LoadBank::NORMAL)?);
-        banks.push(studio.load_bank_file(&format!("{}/SFX.bank", bank_dir), LoadBank::NORMAL)?);
-        banks.push(studio.load_bank_file(&format!("{}/Music.bank", bank_dir), LoadBank::NORMAL)?);
-        banks.push(studio.load_bank_file(&format!("{}/Vehicles.bank", bank_dir), LoadBank::NORMAL)?);
-
-        // Get all events
-        let mut event_descriptions = Vec::new();
-
-        // Pre-defined events we know exist
-        let event_paths = vec![
-            "event:/Ambience/Country",
-            "event:/Character/Player Footsteps",
-            "event:/Weapons/Explosion",
-            "event:/UI/Cancel",
-            "event:/Vehicles/Ride-on Mower",
-            "event:/Music/Level 01",
-        ];
-
-        for path in event_paths {
-            if let Ok(desc) = studio.get_event(path) {
-                event_descriptions.push((path.to_string(), desc));
+    fn new(output: &OutputOptions, bank_args: &[String]) -> Result<Self, Box<dyn std::error::Error>> {
+        // Initialize Studio and load the requested banks (or the SDK examples)
+        // Profiling gives the mixer per-bus CPU times
+        let mut builder = StudioSession::builder().output_options(output).core_flags(Init::PROFILE_ENABLE);
+        if bank_args.is_empty() {
+            builder = builder.example_banks(&DEFAULT_BANKS);
+        }
+        let mut session = builder.build()?;
+        if !bank_args.is_empty() {
+            // Report banks that fail (e.g. a second localized variant) and carry on
+            let (loaded, failed) = session.load_banks(inspect::find_banks(bank_args)?);
+            for error in &failed {
+                eprintln!("⚠️  {} (skipped)", error);
+            }
+            if loaded.is_empty() {
+                return Err("no bank could be loaded".into());
             }
         }
 
+        // Every event in every loaded bank, with its parameter descriptions
+        let events: Vec<HarnessEvent> = inspect::list_events(session.studio())?
+            .into_iter()
+            .map(|(description, info)| HarnessEvent::new(description, info))
+            .collect();
+        if events.is_empty() {
+            return Err("the loaded banks contain no events".into());
+        }
+
+        let mut tree = EventTree::new(events.iter().map(|e| e.info.path.clone()).collect());
+        if events.len() <= EXPAND_ALL_LIMIT {
+            tree.expand_all();
+        }
+        tree.select_event(0);
+
+        let globals = GlobalParameter::list(session.studio())?;
+        let mixer = Mixer::load(session.studio())?;
+        let snapshots = Snapshot::list(session.studio())?;
+        let master_meter = Meter::master(&session.core()?)?;
+
+        let listeners = vec![Listener::new(spatial::ZERO)];
+        spatial::apply_listeners(session.studio(), &listeners)?;
+
         Ok(HarnessState {
-            studio,
-            banks,
-            event_descriptions,
-            active_instances: HashMap::new(),
+            session,
+            events,
+            active_instances: BTreeMap::new(),
+            next_instance_id: 0,
+            registry: InstanceRegistry::new(),
+            leak_report: None,
+            tree,
+            searching: false,
             selected_event: 0,
+            selected_target: 0,
             selected_parameter: 0,
-            listener_pos: Vector { x: 0.0, y: 0.0, z: 0.0 },
-            source_pos: Vector { x: 0.0, y: 0.0, z: -5.0 },
+            focus: Focus::Events,
+            mixer,
+            selected_strip: 0,
+            mixer_message: None,
+            master_meter,
+            master_levels: None,
+            spectrum: None,
+            spectrum_bands: Vec::new(),
+            snapshots,
+            selected_snapshot: 0,
+            globals,
+            selected_global: 0,
+            global_input: None,
+            global_message: None,
+            listeners,
+            listener_trackers: vec![VelocityTracker::new().smoothing(VELOCITY_SMOOTHING)],
+            selected_listener: 0,
+            mover: Mover::Source,
+            selected_instance: None,
+            spawn_pos: DEFAULT_SPAWN,
+            coordinate_input: None,
+            // Seed for the random spawn positions; any non-zero value will do
+            rng: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or(1)
+                | 1,
             show_help: false,
+            show_radar: true,
+            show_mixer: false,
+            last_frame: Instant::now(),
             last_update: Instant::now(),
             frame_count: 0,
             fps: 0.0,
@@ -88,7 +307,27 @@ impl HarnessState {
     }
 
     fn update(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        self.studio.update()?;
+        let now = Instant::now();
+        self.update_motion(now - self.last_frame)?;
+        self.last_frame = now;
+
+        self.session.studio().update()?;
+        self.refresh_playback()?;
+        self.registry.update();
+        self.mixer.refresh()?;
+        self.master_levels = self.master_meter.read().ok();
+        // The FFT has no data until it has processed a window
+        if let Some(spectrum) = &self.spectrum
+            && let Ok(bands) = spectrum.bands(SPECTRUM_BANDS)
+        {
+            self.spectrum_bands = bands;
+        }
+        for snapshot in &mut self.snapshots {
+            snapshot.refresh()?;
+        }
+        for global in &mut self.globals {
+            global.refresh(self.session.studio())?;
+        }
 
         // Calculate FPS
         self.frame_count += 1;
@@ -102,92 +341,946 @@ impl HarnessState {
         Ok(())
     }
 
+    // Move instances along their paths, then derive velocities (for doppler)
+    // from how far everything moved since the last frame
+    fn update_motion(&mut self, elapsed: Duration) -> Result<(), Box<dyn std::error::Error>> {
+        for active in self.active_instances.values_mut() {
+            if !self.events[active.event].info.is_3d {
+                continue;
+            }
+            if let Some(follower) = &mut active.follower {
+                active.emitter.position = follower.advance(elapsed);
+                if follower.is_finished() {
+                    active.follower = None;
+                }
+            }
+            active.emitter.velocity = active.tracker.update(&active.emitter.position, elapsed);
+            active.instance.set_3d_attributes(active.emitter.attributes())?;
+        }
+        for (listener, tracker) in self.listeners.iter_mut().zip(&mut self.listener_trackers) {
+            listener.velocity = tracker.update(&listener.position, elapsed);
+        }
+        self.apply_listeners()
+    }
+
+    // Read every instance's playback state; drop the ones that have stopped
+    fn refresh_playback(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let mut stopped = Vec::new();
+        for (id, active) in self.active_instances.iter_mut() {
+            // A new instance reads as stopped until the update that starts it
+            let was_running = active.playback.is_some_and(|p| p.state != PlaybackState::Stopped);
+            active.playback = PlaybackInfo::read(&active.instance).ok();
+            if was_running && active.playback.is_some_and(|p| p.state == PlaybackState::Stopped) {
+                stopped.push(*id);
+            }
+        }
+        for id in stopped {
+            if let Some(active) = self.active_instances.remove(&id) {
+                self.registry.release(&active.instance)?;
+            }
+        }
+        self.clamp_selection();
+        Ok(())
+    }
+
     fn play_event(&mut self, index: usize) -> Result<(), Box<dyn std::error::Error>> {
-        if index >= self.event_descriptions.len() {
+        self.play_event_at(index, self.spawn_pos.clone(), "browser")
+    }
+
+    // `owner` is what started the instance, for the registry
+    fn play_event_at(&mut self, index: usize, position: Vector, owner: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if index >= self.events.len() {
             return Ok(());
         }
-
-        let (path, desc) = &self.event_descriptions[index];
-        let instance = desc.create_instance()?;
+        let event = &self.events[index];
+        let instance = self.registry.create(&event.description, owner)?;
 
         // Set 3D attributes if applicable
-        let attributes = Attributes3d {
-            position: self.source_pos.clone(),
-            velocity: Vector { x: 0.0, y: 0.0, z: 0.0 },
-            forward: Vector { x: 0.0, y: 0.0, z: 1.0 },
-            up: Vector { x: 0.0, y: 1.0, z: 0.0 },
-        };
-        instance.set_3d_attributes(attributes).ok();
-
-        // Set parameters based on event type
-        let is_one_shot = path.contains("Footstep") || path.contains("Explosion");
+        let emitter = Emitter::new(position);
+        if event.info.is_3d {
+            instance.set_3d_attributes(emitter.attributes())?;
+        }
 
-        if path.contains("Vehicle") || path.contains("Ride-on Mower") {
-            // Vehicles need RPM to make sound
-            instance.set_parameter_by_name("RPM", 1500.0, false)?;
-        } else if path.contains("Footstep") {
-            // Set surface parameter for footsteps
-            instance.set_parameter_by_name("Surface", 1.0, false).ok();
+        // Apply the current value of every parameter the event exposes
+        for i in event.settable_parameters() {
+            instance.set_parameter_by_name(&event.info.parameters[i].name, event.values[i], false)?;
         }
 
         instance.start()?;
 
         // For one-shot events, release immediately after starting
         // They will play once and clean up automatically
-        if is_one_shot {
-            instance.release()?;
+        if event.info.is_oneshot {
+            self.registry.release(&instance)?;
         } else {
-            // For looping/continuous events, track them
-            self.active_instances.insert(index, instance);
+            // For looping/continuous events, track them; new 3D ones take WASD
+            let tracker = VelocityTracker::new().smoothing(VELOCITY_SMOOTHING);
+            let id = self.next_instance_id;
+            self.next_instance_id += 1;
+            let is_3d = event.info.is_3d;
+            self.active_instances.insert(id, ActiveInstance { event: index, instance, emitter, tracker, follower: None, playback: None });
+            if is_3d {
+                self.selected_instance = Some(id);
+            }
         }
 
         Ok(())
     }
 
+    // Ids of the tracked instances of event `index`, oldest first
+    fn instances_of(&self, index: usize) -> Vec<usize> {
+        self.active_instances
+            .iter()
+            .filter(|(_, active)| active.event == index)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    // Stop every tracked instance of event `index`
     fn stop_event(&mut self, index: usize) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(instance) = self.active_instances.remove(&index) {
-            instance.stop(StopMode::AllowFadeout)?;
-            instance.release()?;
+        for id in self.instances_of(index) {
+            if let Some(active) = self.active_instances.remove(&id) {
+                self.registry.stop_and_release(&active.instance, StopMode::AllowFadeout)?;
+            }
         }
+        self.clamp_selection();
         Ok(())
     }
 
+    fn toggle_event(&mut self, index: usize) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.instances_of(index).is_empty() {
+            self.stop_event(index)
+        } else {
+            self.play_event(index)
+        }
+    }
+
     fn stop_all_events(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        for (_idx, instance) in self.active_instances.drain() {
-            instance.stop(StopMode::AllowFadeout)?;
-            instance.release()?;
+        for (_id, active) in std::mem::take(&mut self.active_instances) {
+            self.registry.stop_and_release(&active.instance, StopMode::AllowFadeout)?;
         }
+        self.clamp_selection();
         Ok(())
     }
 
+    fn select_event(&mut self, index: usize) {
+        self.selected_event = index.min(self.events.len() - 1);
+        self.selected_target = 0;
+        self.selected_parameter = 0;
+    }
+
+    // Follow the browser: a selected event row becomes the selected event
+    fn sync_selection(&mut self) {
+        if let Some(index) = self.tree.selected_event()
+            && index != self.selected_event
+        {
+            self.select_event(index);
+        }
+    }
+
+    fn move_tree(&mut self, offset: isize) {
+        self.tree.move_selection(offset);
+        self.sync_selection();
+    }
+
+    fn search_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => {
+                self.searching = false;
+                self.tree.set_query("");
+            }
+            KeyCode::Enter => self.searching = false,
+            KeyCode::Backspace => {
+                let mut query = self.tree.query().to_string();
+                query.pop();
+                self.tree.set_query(query);
+            }
+            KeyCode::Up => self.tree.move_selection(-1),
+            KeyCode::Down => self.tree.move_selection(1),
+            KeyCode::Char(c) => {
+                let query = format!("{}{}", self.tree.query(), c);
+                self.tree.set_query(query);
+            }
+            _ => {}
+        }
+        self.sync_selection();
+    }
+
+    // Enter in the browser: open/close a folder or play/stop an event
+    fn activate_selected(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.focus == Focus::Snapshots {
+            if let Some(snapshot) = self.snapshots.get_mut(self.selected_snapshot) {
+                snapshot.toggle()?;
+            }
+            return Ok(());
+        }
+        match self.tree.selected_row() {
+            Some(TreeRow::Event { index, .. }) => self.toggle_event(index),
+            Some(TreeRow::Folder { .. }) => {
+                self.tree.toggle();
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    // The selected event's defaults first, then every playing instance
+    fn targets(&self) -> Vec<Target> {
+        let playing = self
+            .active_instances
+            .iter()
+            .map(|(id, active)| Target::Instance { id: *id, event: active.event });
+        std::iter::once(Target::Defaults(self.selected_event))
+            .chain(playing)
+            .collect()
+    }
+
+    fn target(&self) -> Target {
+        self.targets()
+            .get(self.selected_target)
+            .copied()
+            .unwrap_or(Target::Defaults(self.selected_event))
+    }
+
+    fn select_target(&mut self, offset: isize) {
+        let count = self.targets().len() as isize;
+        self.selected_target = (self.selected_target as isize + offset).rem_euclid(count) as usize;
+        self.selected_parameter = 0;
+    }
+
+    fn select_parameter(&mut self, offset: isize) {
+        let count = self.events[self.target().event()].settable_parameters().len() as isize;
+        if count > 0 {
+            self.selected_parameter = (self.selected_parameter as isize + offset).rem_euclid(count) as usize;
+        }
+    }
+
+    // Keep the selections valid after instances come and go
+    fn clamp_selection(&mut self) {
+        if self.selected_target >= self.targets().len() {
+            self.selected_target = 0;
+            self.selected_parameter = 0;
+        }
+        if let Some(id) = self.selected_instance
+            && !self.active_instances.contains_key(&id)
+        {
+            self.selected_instance = self.spatial_instances().last().copied();
+        }
+    }
+
+    // Current value of parameter `index` on a target
+    fn parameter_value(&self, target: Target, index: usize) -> f32 {
+        let event = &self.events[target.event()];
+        if let Target::Instance { id, .. } = target
+            && let Some(active) = self.active_instances.get(&id)
+            && let Ok((value, _final_value)) = active.instance.get_parameter_by_name(&event.info.parameters[index].name)
+        {
+            return value;
+        }
+        event.values[index]
+    }
+
+    // Ids of the playing 3D instances, oldest first
+    fn spatial_instances(&self) -> Vec<usize> {
+        self.active_instances
+            .iter()
+            .filter(|(_, active)| self.events[active.event].info.is_3d)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    // [I]: step through the playing 3D instances, then the spawn point
+    fn cycle_instance(&mut self) {
+        let playing = self.spatial_instances();
+        let next = match self.selected_instance.and_then(|i| playing.iter().position(|p| *p == i)) {
+            Some(position) => playing.get(position + 1).copied(),
+            None => playing.first().copied(),
+        };
+        self.selected_instance = next;
+        self.mover = Mover::Source;
+    }
+
+    // Move the selected instance, or the spawn point when none is selected
     fn move_source(&mut self, dx: f32, dy: f32, dz: f32) -> Result<(), Box<dyn std::error::Error>> {
-        self.source_pos.x += dx;
-        self.source_pos.y += dy;
-        self.source_pos.z += dz;
-
-        // Update all active instances
-        for (_idx, instance) in &self.active_instances {
-            let attributes = Attributes3d {
-                position: self.source_pos.clone(),
-                velocity: Vector { x: 0.0, y: 0.0, z: 0.0 },
-                forward: Vector { x: 0.0, y: 0.0, z: 1.0 },
-                up: Vector { x: 0.0, y: 1.0, z: 0.0 },
+        let Some(active) = self.selected_instance.and_then(|i| self.active_instances.get_mut(&i)) else {
+            self.spawn_pos.x += dx;
+            self.spawn_pos.y += dy;
+            self.spawn_pos.z += dz;
+            return Ok(());
+        };
+        // Taking manual control leaves the path
+        active.follower = None;
+        active.emitter.position.x += dx;
+        active.emitter.position.y += dy;
+        active.emitter.position.z += dz;
+        active.instance.set_3d_attributes(active.emitter.attributes())?;
+        Ok(())
+    }
+
+    fn turn_source(&mut self, degrees: f32) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(active) = self.selected_instance.and_then(|i| self.active_instances.get_mut(&i)) {
+            active.emitter.turn(degrees);
+            active.instance.set_3d_attributes(active.emitter.attributes())?;
+        }
+        Ok(())
+    }
+
+    fn reset_source(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        match self.selected_instance.and_then(|i| self.active_instances.get_mut(&i)) {
+            Some(active) => {
+                active.emitter = Emitter::new(DEFAULT_SPAWN);
+                active.tracker.reset();
+                active.follower = None;
+                active.instance.set_3d_attributes(active.emitter.attributes())?;
+            }
+            None => self.spawn_pos = DEFAULT_SPAWN,
+        }
+        Ok(())
+    }
+
+    // Start a new instance of the selected event at `position`
+    fn spawn_at(&mut self, position: Vector) -> Result<(), Box<dyn std::error::Error>> {
+        self.play_event_at(self.selected_event, position, "spawn")?;
+        self.mover = Mover::Source;
+        Ok(())
+    }
+
+    // [O]: put the selected instance on the next kind of path, built around
+    // the selected listener and starting from where the instance is
+    fn cycle_path(&mut self) {
+        let Some(id) = self.selected_instance else {
+            return;
+        };
+        let center = self.listeners[self.selected_listener].position.clone();
+        let (current, speed, looping) = match &self.active_instances[&id].follower {
+            Some(follower) => (
+                Some(follower.path.shape().clone()),
+                follower.path.units_per_second(),
+                follower.path.looping_mode(),
+            ),
+            None => (None, PATH_SPEED, Looping::Loop),
+        };
+        let position = self.active_instances[&id].emitter.position.clone();
+        let radius = spatial::distance(&position, &center).max(2.0);
+
+        let shape = match current {
+            None => Some(Shape::Orbit { center, radius }),
+            Some(Shape::Orbit { .. }) => Some(Shape::FigureEight { center, radius }),
+            Some(Shape::FigureEight { .. }) => {
+                // Wander through a few random points around the listener
+                let mut waypoints = vec![position.clone()];
+                for _ in 0..4 {
+                    let angle = self.random() * std::f32::consts::TAU;
+                    let distance = SPAWN_RADIUS.start + self.random() * (SPAWN_RADIUS.end - SPAWN_RADIUS.start);
+                    waypoints.push(Vector {
+                        x: center.x + distance * angle.cos(),
+                        y: position.y,
+                        z: center.z + distance * angle.sin(),
+                    });
+                }
+                Some(Shape::Spline { waypoints })
+            }
+            Some(Shape::Spline { .. }) => Some(Shape::FlyOver { center, height: 5.0, distance: radius.max(10.0) }),
+            Some(Shape::FlyOver { .. }) => {
+                // Straight through the listener to the opposite side
+                let to = Vector {
+                    x: 2.0 * center.x - position.x,
+                    y: position.y,
+                    z: 2.0 * center.z - position.z,
+                };
+                Some(Shape::Linear { from: position, to })
+            }
+            Some(Shape::Linear { .. }) => None,
+        };
+
+        let active = self.active_instances.get_mut(&id).expect("selected instance is active");
+        active.follower = shape.map(|shape| Follower::new(MotionPath::new(shape).speed(speed).looping(looping)));
+        active.tracker.reset();
+        self.mover = Mover::Source;
+    }
+
+    // [J]/[K]: scale the selected instance's path speed, keeping its place along the path
+    fn adjust_path_speed(&mut self, factor: f32) {
+        if let Some(follower) = self.selected_follower() {
+            let old = follower.path.units_per_second();
+            let new = (old * factor).clamp(*PATH_SPEED_RANGE.start(), *PATH_SPEED_RANGE.end());
+            follower.path = follower.path.clone().speed(new);
+            follower.time = follower.time.mul_f32(old / new);
+        }
+    }
+
+    // [U]: once, loop, ping-pong
+    fn cycle_looping(&mut self) {
+        if let Some(follower) = self.selected_follower() {
+            let next = match follower.path.looping_mode() {
+                Looping::Once => Looping::Loop,
+                Looping::Loop => Looping::PingPong,
+                Looping::PingPong => Looping::Once,
             };
-            instance.set_3d_attributes(attributes).ok();
+            follower.path = follower.path.clone().looping(next);
+        }
+    }
+
+    fn selected_follower(&mut self) -> Option<&mut Follower> {
+        let id = self.selected_instance?;
+        self.active_instances.get_mut(&id)?.follower.as_mut()
+    }
+
+    // [P]: somewhere around the selected listener, at ear height
+    fn spawn_random(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let angle = self.random() * std::f32::consts::TAU;
+        let distance = SPAWN_RADIUS.start + self.random() * (SPAWN_RADIUS.end - SPAWN_RADIUS.start);
+        let center = &self.listeners[self.selected_listener].position;
+        let position = Vector {
+            x: center.x + distance * angle.cos(),
+            y: center.y,
+            z: center.z + distance * angle.sin(),
+        };
+        self.spawn_at(position)
+    }
+
+    // xorshift64, good enough to scatter test sources; 0.0 to 1.0
+    fn random(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    fn coordinate_key(&mut self, code: KeyCode) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(input) = self.coordinate_input.as_mut() else {
+            return Ok(());
+        };
+        match code {
+            KeyCode::Esc => self.coordinate_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() || matches!(c, '-' | '.' | ',' | ' ') => input.push(c),
+            KeyCode::Enter => {
+                // Keep the prompt open on a typo so it can be corrected
+                if let Some(position) = parse_coordinate(input) {
+                    self.coordinate_input = None;
+                    self.spawn_at(position)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn move_listener(&mut self, dx: f32, dy: f32, dz: f32) -> Result<(), Box<dyn std::error::Error>> {
+        let listener = &mut self.listeners[self.selected_listener];
+        listener.position.x += dx;
+        listener.position.y += dy;
+        listener.position.z += dz;
+        self.apply_listeners()
+    }
+
+    // WASD/QE: move whichever of source and listener is selected
+    fn nudge(&mut self, dx: f32, dy: f32, dz: f32) -> Result<(), Box<dyn std::error::Error>> {
+        match self.mover {
+            Mover::Source => self.move_source(dx, dy, dz),
+            Mover::Listener => self.move_listener(dx, dy, dz),
+        }
+    }
+
+    fn turn_listener(&mut self, degrees: f32) -> Result<(), Box<dyn std::error::Error>> {
+        self.listeners[self.selected_listener].turn(degrees);
+        self.apply_listeners()
+    }
+
+    // [ and ]: turn whichever of source and listener is selected
+    fn turn(&mut self, degrees: f32) -> Result<(), Box<dyn std::error::Error>> {
+        match self.mover {
+            Mover::Source => self.turn_source(degrees),
+            Mover::Listener => self.turn_listener(degrees),
+        }
+    }
+
+    fn adjust_listener_weight(&mut self, delta: f32) -> Result<(), Box<dyn std::error::Error>> {
+        let listener = &mut self.listeners[self.selected_listener];
+        listener.weight = (listener.weight + delta).clamp(0.0, 1.0);
+        self.apply_listeners()
+    }
+
+    // New listeners start beside the selected one so they are easy to tell apart
+    fn add_listener(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.listeners.len() >= MAX_LISTENERS {
+            return Ok(());
+        }
+        let mut listener = self.listeners[self.selected_listener].clone();
+        listener.position.x += 5.0;
+        listener.velocity = spatial::ZERO;
+        self.listeners.push(listener);
+        self.listener_trackers.push(VelocityTracker::new().smoothing(VELOCITY_SMOOTHING));
+        self.selected_listener = self.listeners.len() - 1;
+        self.mover = Mover::Listener;
+        self.apply_listeners()
+    }
+
+    fn remove_listener(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.listeners.len() <= 1 {
+            return Ok(());
         }
+        self.listeners.remove(self.selected_listener);
+        self.listener_trackers.remove(self.selected_listener);
+        self.selected_listener = self.selected_listener.min(self.listeners.len() - 1);
+        self.apply_listeners()
+    }
+
+    fn apply_listeners(&self) -> Result<(), Box<dyn std::error::Error>> {
+        spatial::apply_listeners(self.session.studio(), &self.listeners)?;
+        Ok(())
+    }
+
+    // Playing 3D instances as radar blips, lettered oldest first
+    fn blips(&self) -> Vec<(usize, Blip)> {
+        self.spatial_instances()
+            .into_iter()
+            .zip(source_glyphs())
+            .map(|(id, glyph)| {
+                let active = &self.active_instances[&id];
+                let info = &self.events[active.event].info;
+                let blip = Blip {
+                    position: active.emitter.position.clone(),
+                    min_distance: info.min_distance,
+                    max_distance: info.max_distance,
+                    glyph,
+                };
+                (id, blip)
+            })
+            .collect()
+    }
+
+    // Step the selected parameter of the selected target up or down
+    fn adjust_parameter(&mut self, direction: f32) -> Result<(), Box<dyn std::error::Error>> {
+        let target = self.target();
+        let Some(&index) = self.events[target.event()]
+            .settable_parameters()
+            .get(self.selected_parameter)
+        else {
+            return Ok(());
+        };
+        let current = self.parameter_value(target, index);
 
+        let event = &mut self.events[target.event()];
+        let parameter = &event.info.parameters[index];
+        let value = (current + direction * parameter.step()).clamp(parameter.minimum, parameter.maximum);
+
+        // New instances of the event start from the last value set
+        event.values[index] = value;
+        if let Target::Instance { id, .. } = target
+            && let Some(active) = self.active_instances.get(&id)
+        {
+            active.instance.set_parameter_by_name(&parameter.name, value, false)?;
+        }
         Ok(())
     }
 
-    fn adjust_parameter(&mut self, delta: f32) -> Result<(), Box<dyn std::error::Error>> {
-        // Adjust parameter on all active instances
-        for (_idx, instance) in &self.active_instances {
-            // Common parameter names
-            instance.set_parameter_by_name("RPM", 2000.0 + delta * 1000.0, false).ok();
-            instance.set_parameter_by_name("Surface", delta, false).ok();
+    // [C]: list instances never released or stuck sustaining
+    fn check_leaks(&mut self) {
+        self.registry.update();
+        let leaks = self.registry.leaks();
+        self.leak_report = Some(if leaks.is_empty() {
+            vec![format!("no leaks among {} live instances", self.registry.instances().len())]
+        } else {
+            leaks.iter().map(|leak| leak.to_string()).collect()
+        });
+    }
+
+    fn select_global(&mut self, offset: isize) {
+        let count = self.globals.len() as isize;
+        if count > 0 {
+            self.selected_global = (self.selected_global as isize + offset).rem_euclid(count) as usize;
         }
+    }
+
+    // Step the selected global parameter up or down
+    fn adjust_global(&mut self, direction: f32) -> Result<(), Box<dyn std::error::Error>> {
+        let studio = *self.session.studio();
+        let Some(global) = self.globals.get_mut(self.selected_global) else {
+            return Ok(());
+        };
+        if !global.info.is_settable() {
+            return Ok(());
+        }
+        let value = (global.value + direction * global.info.step()).clamp(global.info.minimum, global.info.maximum);
+        global.set(&studio, &ParameterValue::Number(value))?;
         Ok(())
     }
+
+    // [F]: add or remove the FFT on the master channel group
+    fn toggle_spectrum(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        match self.spectrum.take() {
+            Some(spectrum) => spectrum.remove()?,
+            None => self.spectrum = Some(Spectrum::master(&self.session.core()?)?),
+        }
+        self.spectrum_bands.clear();
+        Ok(())
+    }
+
+    fn select_strip(&mut self, offset: isize) {
+        let count = self.mixer.len() as isize;
+        if count > 0 {
+            self.selected_strip = (self.selected_strip as isize + offset).rem_euclid(count) as usize;
+        }
+    }
+
+    // Mixer screen keys; returns false for keys it leaves to the main screen
+    fn mixer_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> Result<bool, Box<dyn std::error::Error>> {
+        let bus_count = self.mixer.buses.len();
+        let index = self.selected_strip;
+        self.mixer_message = None;
+        match code {
+            KeyCode::Esc | KeyCode::Char('x') | KeyCode::Char('X') => self.show_mixer = false,
+            KeyCode::Up => self.select_strip(-1),
+            KeyCode::Down => self.select_strip(1),
+            KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Char('-') | KeyCode::Char('_') => {
+                let step = if matches!(code, KeyCode::Char('+') | KeyCode::Char('=')) { VOLUME_STEP } else { -VOLUME_STEP };
+                if let Some(strip) = self.mixer.buses.get_mut(index) {
+                    strip.set_volume((strip.volume + step).min(MAX_VOLUME))?;
+                } else if let Some(strip) = self.mixer.vcas.get_mut(index - bus_count) {
+                    strip.set_volume((strip.volume + step).min(MAX_VOLUME))?;
+                }
+            }
+            KeyCode::Enter | KeyCode::Char('m') | KeyCode::Char('M') | KeyCode::Char('p') | KeyCode::Char('P') | KeyCode::Char(' ') => {
+                let Some(strip) = self.mixer.buses.get_mut(index) else {
+                    self.mixer_message = Some("VCAs only have a volume".to_string());
+                    return Ok(true);
+                };
+                match code {
+                    KeyCode::Enter => strip.set_locked(!strip.locked)?,
+                    KeyCode::Char('m') | KeyCode::Char('M') => strip.toggle_mute()?,
+                    KeyCode::Char('p') | KeyCode::Char('P') => strip.toggle_paused()?,
+                    _ => {
+                        let mode = if modifiers.contains(KeyModifiers::SHIFT) { StopMode::Immediate } else { StopMode::AllowFadeout };
+                        strip.stop_all_events(mode)?;
+                        self.mixer_message = Some(format!("stopped all events on {} ({:?})", strip.path, mode));
+                    }
+                }
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn select_snapshot(&mut self, offset: isize) {
+        let count = self.snapshots.len() as isize;
+        if count > 0 {
+            self.selected_snapshot = (self.selected_snapshot as isize + offset).rem_euclid(count) as usize;
+        }
+    }
+
+    // Step the selected snapshot's intensity (applied at once if it is active)
+    fn adjust_intensity(&mut self, direction: f32) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(snapshot) = self.snapshots.get_mut(self.selected_snapshot) {
+            snapshot.set_intensity(snapshot.intensity + direction * INTENSITY_STEP)?;
+        }
+        Ok(())
+    }
+
+    fn global_key(&mut self, code: KeyCode) {
+        let Some(input) = self.global_input.as_mut() else {
+            return;
+        };
+        match code {
+            KeyCode::Esc => self.global_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Enter => {
+                let text = std::mem::take(input);
+                self.global_input = None;
+                self.global_message = Some(self.edit_global(&text));
+            }
+            _ => {}
+        }
+    }
+
+    // Apply "name-or-id=value-or-label" and describe the outcome
+    fn edit_global(&mut self, text: &str) -> String {
+        let Some((key, value)) = text.split_once('=') else {
+            return format!("expected name=value or id=label, got \"{}\"", text);
+        };
+        match edit_global_parameter(self.session.studio(), key.trim(), value.trim()) {
+            Ok(edited) => {
+                let message = format!("{} = {}", edited.info.name, edited.value);
+                if let Some(index) = self.globals.iter().position(|g| g.info.name == edited.info.name) {
+                    self.selected_global = index;
+                    self.globals[index] = edited;
+                }
+                message
+            }
+            Err(e) => e.to_string(),
+        }
+    }
+}
+
+// "x,y,z" or "x z" (y = 0), separated by commas and/or spaces
+fn parse_coordinate(text: &str) -> Option<Vector> {
+    let values = text
+        .split([',', ' '])
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse::<f32>().ok())
+        .collect::<Option<Vec<f32>>>()?;
+    match values[..] {
+        [x, z] => Some(Vector { x, y: 0.0, z }),
+        [x, y, z] => Some(Vector { x, y, z }),
+        _ => None,
+    }
+}
+
+// `[#####-----]` showing where `fraction` (0-1) lies
+fn value_bar(fraction: f32, width: usize) -> String {
+    let filled = (fraction.clamp(0.0, 1.0) * width as f32).round() as usize;
+    format!("[{}{}]", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+// "Playing 1.2/5.0s [███░░] vol 1.00 pitch 1.00", plus "PAUSED"
+fn playback_summary(playback: &PlaybackInfo) -> String {
+    let position = playback.position.as_secs_f32();
+    let timeline = match (playback.length, playback.progress()) {
+        (Some(length), Some(progress)) => {
+            format!("{:.1}/{:.1}s {}", position, length.as_secs_f32(), value_bar(progress, 10))
+        }
+        _ => format!("{:.1}s", position),
+    };
+    format!("{:?} {} vol {:.2} pitch {:.2}{}",
+        playback.state,
+        timeline,
+        playback.volume,
+        playback.pitch,
+        if playback.paused { " PAUSED" } else { "" })
+}
+
+fn state_color(state: PlaybackState) -> Color {
+    match state {
+        PlaybackState::Starting => Color::Cyan,
+        PlaybackState::Playing => Color::Green,
+        PlaybackState::Sustaining => Color::Magenta,
+        PlaybackState::Stopping => Color::Yellow,
+        PlaybackState::Stopped => Color::DarkGrey,
+    }
+}
+
+// Meter bar for a linear level, with `METER_FLOOR_DB` at the left end
+fn level_bar(level: f32, width: usize) -> String {
+    value_bar((to_db(level) - METER_FLOOR_DB) / -METER_FLOOR_DB, width)
+}
+
+// Every bus and VCA with its fader, state, CPU time and output level
+fn draw_mixer(stdout: &mut io::Stdout, state: &HarnessState) -> io::Result<()> {
+    let mixer = &state.mixer;
+    let cpu = match &mixer.cpu {
+        Some(cpu) => format!("DSP {:.1}%  update {:.1}%", cpu.dsp, cpu.update),
+        None => "CPU n/a".to_string(),
+    };
+    execute!(stdout,
+        SetForegroundColor(Color::Yellow),
+        Print(format!("\r\n* Mixer: {} buses, {} VCAs", mixer.buses.len(), mixer.vcas.len())),
+        SetForegroundColor(Color::DarkGrey),
+        Print(format!("  {}\r\n", cpu)),
+        ResetColor
+    )?;
+    if mixer.is_empty() {
+        execute!(stdout, Print("  (no buses or VCAs in the loaded banks)\r\n"))?;
+    }
+    for (n, strip) in mixer.buses.iter().enumerate() {
+        let is_selected = n == state.selected_strip;
+        let flags = match (strip.mute, strip.paused) {
+            (true, true) => "MUTE PAUSED",
+            (true, false) => "MUTE",
+            (false, true) => "PAUSED",
+            (false, false) => "",
+        };
+        let cpu = match strip.cpu {
+            Some((exclusive, inclusive)) => format!("{:>5}/{:<5}us", exclusive, inclusive),
+            None => format!("{:^13}", "-"),
+        };
+        let level = match &strip.levels {
+            Some(levels) => format!("{} {:6.1} dB", level_bar(levels.max_peak(), 16), levels.max_peak_db()),
+            None => "(idle)".to_string(),
+        };
+        execute!(stdout,
+            SetForegroundColor(if is_selected { Color::Green } else { Color::White }),
+            Print(if is_selected { "> " } else { "  " }),
+            Print(if strip.locked { "◆ " } else { "  " }),
+            Print(format!("{:<26} {} {:.2} (final {:.2})  ",
+                strip.path,
+                value_bar(strip.volume / MAX_VOLUME, 10),
+                strip.volume,
+                strip.final_volume)),
+            SetForegroundColor(Color::Red),
+            Print(format!("{:<12}", flags)),
+            SetForegroundColor(if is_selected { Color::Green } else { Color::White }),
+            Print(format!("{}  {}\r\n", cpu, level)),
+            ResetColor
+        )?;
+    }
+    for (n, strip) in mixer.vcas.iter().enumerate() {
+        let is_selected = mixer.buses.len() + n == state.selected_strip;
+        execute!(stdout,
+            SetForegroundColor(if is_selected { Color::Green } else { Color::Cyan }),
+            Print(if is_selected { "> " } else { "  " }),
+            Print(format!("  {:<26} {} {:.2} (final {:.2})\r\n",
+                strip.path,
+                value_bar(strip.volume / MAX_VOLUME, 10),
+                strip.volume,
+                strip.final_volume)),
+            ResetColor
+        )?;
+    }
+    if let Some(message) = &state.mixer_message {
+        execute!(stdout, SetForegroundColor(Color::DarkGrey), Print(format!("  {}\r\n", message)), ResetColor)?;
+    }
+    execute!(stdout,
+        Print("\r\n"),
+        SetForegroundColor(Color::DarkGrey),
+        Print("[Up/Down] Select  [+/-] Volume  [M] Mute  [P] Pause  [Space] Stop bus events (Shift: immediate)\r\n"),
+        Print("[Enter] Lock bus (◆: metered on the main screen, even while idle)  [X/Esc] Close\r\n"),
+        ResetColor
+    )?;
+    Ok(())
+}
+
+// "L"/"R" for stereo, channel numbers otherwise
+fn channel_name(channel: usize, channels: usize) -> String {
+    match (channels, channel) {
+        (1, _) => "M".to_string(),
+        (2, 0) => "L".to_string(),
+        (2, _) => "R".to_string(),
+        _ => (channel + 1).to_string(),
+    }
+}
+
+// Peak and RMS bars for every channel of one meter
+fn draw_levels(stdout: &mut io::Stdout, name: &str, levels: Option<&Levels>) -> io::Result<()> {
+    let Some(levels) = levels.filter(|levels| !levels.peak.is_empty()) else {
+        return execute!(stdout, SetForegroundColor(Color::DarkGrey), Print(format!("  {:<20} (idle)\r\n", name)), ResetColor);
+    };
+    for (channel, (peak, rms)) in levels.peak.iter().zip(&levels.rms).enumerate() {
+        let peak_db = to_db(*peak);
+        let color = if peak_db > -1.0 {
+            Color::Red
+        } else if peak_db > -12.0 {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        execute!(stdout,
+            Print(format!("  {:<20} {:>2} ", if channel == 0 { name } else { "" }, channel_name(channel, levels.peak.len()))),
+            SetForegroundColor(color),
+            Print(format!("peak {} {:6.1} dB", level_bar(*peak, 20), peak_db)),
+            SetForegroundColor(Color::Cyan),
+            Print(format!("  rms {} {:6.1} dB\r\n", level_bar(*rms, 20), to_db(*rms))),
+            ResetColor
+        )?;
+    }
+    Ok(())
+}
+
+// Bar graph of the master spectrum, low frequencies on the left
+fn draw_spectrum(stdout: &mut io::Stdout, bands: &[f32], max_frequency: f32) -> io::Result<()> {
+    const EIGHTHS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    if bands.is_empty() {
+        return execute!(stdout, SetForegroundColor(Color::DarkGrey), Print("  (waiting for FFT data)\r\n"), ResetColor);
+    }
+    let heights: Vec<f32> = bands
+        .iter()
+        .map(|level| ((to_db(*level) - SPECTRUM_FLOOR_DB) / -SPECTRUM_FLOOR_DB).clamp(0.0, 1.0) * SPECTRUM_HEIGHT as f32)
+        .collect();
+    for row in (0..SPECTRUM_HEIGHT).rev() {
+        let line: String = heights
+            .iter()
+            .map(|height| EIGHTHS[((height - row as f32).clamp(0.0, 1.0) * 8.0).round() as usize])
+            .collect();
+        let color = if row >= SPECTRUM_HEIGHT * 3 / 4 { Color::Yellow } else { Color::Green };
+        execute!(stdout, Print("  |"), SetForegroundColor(color), Print(line), ResetColor, Print("|\r\n"))?;
+    }
+    let top = format!("{:.0} kHz", max_frequency / 1000.0);
+    execute!(stdout,
+        SetForegroundColor(Color::DarkGrey),
+        Print(format!("   20 Hz{:>width$}\r\n", top, width = bands.len().saturating_sub(5))),
+        ResetColor
+    )?;
+    Ok(())
+}
+
+fn mark_color(mark: Mark) -> Color {
+    match mark {
+        Mark::Empty | Mark::Grid => Color::DarkGrey,
+        Mark::MaxDistance => Color::DarkBlue,
+        Mark::MinDistance => Color::Blue,
+        Mark::Facing | Mark::SelectedListener => Color::Yellow,
+        Mark::OffRadar => Color::DarkGreen,
+        Mark::Source => Color::Green,
+        Mark::Listener => Color::White,
+    }
+}
+
+// Top-down map centred on the selected listener, with a legend below
+fn draw_radar(stdout: &mut io::Stdout, state: &HarnessState) -> io::Result<()> {
+    let radar = Radar::new(RADAR_WIDTH, RADAR_HEIGHT);
+    let center = &state.listeners[state.selected_listener].position;
+    let blips = state.blips();
+    let plotted: Vec<Blip> = blips.iter().map(|(_, blip)| blip.clone()).collect();
+    let cells = radar.render(center, &state.listeners, state.selected_listener, &plotted);
+
+    execute!(stdout,
+        cursor::MoveTo(RADAR_COLUMN, RADAR_ROW),
+        SetForegroundColor(Color::Yellow),
+        Print(format!("* Radar (listener {}, {}m per column)", state.selected_listener + 1, radar.scale)),
+        ResetColor
+    )?;
+    let border = format!("+{}+", "-".repeat(RADAR_WIDTH));
+    execute!(stdout, cursor::MoveTo(RADAR_COLUMN, RADAR_ROW + 1), Print(&border))?;
+    for (y, row) in cells.iter().enumerate() {
+        execute!(stdout, cursor::MoveTo(RADAR_COLUMN, RADAR_ROW + 2 + y as u16), Print("|"))?;
+        for cell in row {
+            execute!(stdout, SetForegroundColor(mark_color(cell.mark)), Print(cell.glyph))?;
+        }
+        execute!(stdout, ResetColor, Print("|"))?;
+    }
+    let bottom = RADAR_ROW + 2 + RADAR_HEIGHT as u16;
+    execute!(stdout, cursor::MoveTo(RADAR_COLUMN, bottom), Print(&border))?;
+
+    // Legend: distance to the nearest listener against the event's range
+    for (n, (id, blip)) in blips.iter().enumerate() {
+        let line = bottom + 1 + n as u16;
+        let info = &state.events[state.active_instances[id].event].info;
+        let name = info.path.rsplit('/').next().unwrap_or(&info.path);
+        let distance = state
+            .listeners
+            .iter()
+            .map(|listener| spatial::distance(&listener.position, &blip.position))
+            .fold(f32::INFINITY, f32::min);
+        let (status, color) = if distance > blip.max_distance {
+            ("beyond max", Color::Red)
+        } else if distance < blip.min_distance {
+            ("within min", Color::Green)
+        } else {
+            ("attenuating", Color::Yellow)
+        };
+        execute!(stdout,
+            cursor::MoveTo(RADAR_COLUMN, line),
+            SetForegroundColor(Color::Green),
+            Print(format!("{} ", blip.glyph)),
+            ResetColor,
+            Print(format!("{:<20} {:6.1}m ({}-{}m) ", name, distance, blip.min_distance, blip.max_distance)),
+            SetForegroundColor(color),
+            Print(status),
+            ResetColor
+        )?;
+    }
+    Ok(())
+}
+
+// Sources are lettered a-z on the radar and in the position list; any beyond
+// that share a generic mark rather than being left off
+fn source_glyphs() -> impl Iterator<Item = char> {
+    ('a'..='z').chain(std::iter::repeat('*'))
 }
 
 fn clear_screen() {
@@ -195,7 +1288,7 @@ fn clear_screen() {
     io::stdout().flush().unwrap();
 }
 
-fn draw_ui(state: &HarnessState) -> io::Result<()> {
+fn draw_ui(state: &mut HarnessState) -> io::Result<()> {
     let mut stdout = io::stdout();
 
     // Clear and reset cursor
@@ -212,59 +1305,366 @@ fn draw_ui(state: &HarnessState) -> io::Result<()> {
         SetForegroundColor(Color::Cyan),
         Print(" | Events: "),
         ResetColor,
-        Print(format!("{}/{}", state.active_instances.len(), state.event_descriptions.len())),
+        Print(format!("{}/{}", state.active_instances.len(), state.events.len())),
         SetForegroundColor(Color::Cyan),
         Print("       |\r\n"),
         Print("========================================================================\r\n"),
         ResetColor
     )?;
 
-    // Event list
+    // The mixer takes the whole screen below the header
+    if state.show_mixer {
+        draw_mixer(&mut stdout, state)?;
+        stdout.flush()?;
+        return Ok(());
+    }
+
+    // Event browser
+    let query = state.tree.query().to_string();
+    let title = if state.searching {
+        format!("\r\n> Events  /{}_\r\n", query)
+    } else if !query.is_empty() {
+        format!("\r\n> Events  (search: {}, Esc clears)\r\n", query)
+    } else {
+        "\r\n> Events:\r\n".to_string()
+    };
     execute!(stdout,
-        SetForegroundColor(Color::Yellow),
-        Print("\r\n> Available Events:\r\n"),
+        SetForegroundColor(if state.focus == Focus::Events { Color::Green } else { Color::Yellow }),
+        Print(title),
         ResetColor
     )?;
 
-    for (i, (path, _desc)) in state.event_descriptions.iter().enumerate() {
-        let is_active = state.active_instances.contains_key(&i);
-        let is_selected = i == state.selected_event;
+    let rows = state.tree.rows();
+    let window = state.tree.window(TREE_HEIGHT);
+    if rows.is_empty() {
+        execute!(stdout, SetForegroundColor(Color::DarkGrey), Print("  (no matching events)\r\n"), ResetColor)?;
+    }
+    if window.start > 0 {
+        execute!(stdout, SetForegroundColor(Color::DarkGrey), Print(format!("  ... {} more above\r\n", window.start)), ResetColor)?;
+    }
+    for position in window.clone() {
+        let row = &rows[position];
+        let is_selected = position == state.tree.selected();
 
         if is_selected {
-            execute!(stdout, SetForegroundColor(Color::Green), Print("> "))?;
+            let marker = if state.focus == Focus::Events { Color::Green } else { Color::DarkGrey };
+            execute!(stdout, SetForegroundColor(marker), Print("> "))?;
         } else {
             execute!(stdout, Print("  "))?;
         }
+        execute!(stdout, Print("  ".repeat(row.depth())))?;
 
+        match row {
+            TreeRow::Folder { name, expanded, events, .. } => {
+                execute!(stdout,
+                    SetForegroundColor(Color::Cyan),
+                    Print(format!("{} {}/ ", if *expanded { "▾" } else { "▸" }, name)),
+                    SetForegroundColor(Color::DarkGrey),
+                    Print(format!("({})", events)),
+                    ResetColor
+                )?;
+            }
+            TreeRow::Event { index, name, .. } => {
+                let event = &state.events[*index];
+                let instances = state.instances_of(*index);
+                let is_active = !instances.is_empty();
+                execute!(stdout,
+                    SetForegroundColor(if is_active { Color::Green } else { Color::White }),
+                    Print(name),
+                    ResetColor
+                )?;
+                if event.info.is_oneshot {
+                    execute!(stdout, SetForegroundColor(Color::DarkGrey), Print(" (one-shot)"), ResetColor)?;
+                }
+                // The newest instance's state, and how many are playing
+                if let Some(active) = instances.last().map(|id| &state.active_instances[id]) {
+                    let (mut text, color) = match &active.playback {
+                        Some(playback) => (playback_summary(playback), state_color(playback.state)),
+                        None => ("Starting".to_string(), Color::Cyan),
+                    };
+                    if instances.len() > 1 {
+                        text = format!("{} x{}", text, instances.len());
+                    }
+                    execute!(stdout, SetForegroundColor(color), Print(format!(" [{}]", text)), ResetColor)?;
+                }
+            }
+        }
+
+        execute!(stdout, Print("\r\n"))?;
+    }
+    if window.end < rows.len() {
+        execute!(stdout, SetForegroundColor(Color::DarkGrey), Print(format!("  ... {} more below\r\n", rows.len() - window.end)), ResetColor)?;
+    }
+
+    // Parameter panel for the selected target
+    let targets = state.targets();
+    let target = state.target();
+    let event = &state.events[target.event()];
+    let settable = event.settable_parameters();
+    let panel_focused = state.focus == Focus::Parameters;
+    let target_name = match target {
+        Target::Defaults(_) => format!("{} (next instance)", event.info.path),
+        Target::Instance { id, .. } => {
+            let playing = state.active_instances.get(&id).and_then(|active| active.playback);
+            format!("{} #{} [{}]", event.info.path, id, playing.map_or("Starting".to_string(), |p| format!("{:?}", p.state)))
+        }
+    };
+    execute!(stdout,
+        Print("\r\n"),
+        SetForegroundColor(if panel_focused { Color::Green } else { Color::Yellow }),
+        Print(format!("* Parameters {}/{}: ", state.selected_target + 1, targets.len())),
+        ResetColor,
+        Print(target_name),
+        SetForegroundColor(Color::DarkGrey),
+        Print("  [Left/Right] target\r\n"),
+        ResetColor
+    )?;
+    if settable.is_empty() {
+        execute!(stdout, Print("  (no settable parameters)\r\n"))?;
+    }
+    for (n, index) in settable.iter().enumerate() {
+        let parameter = &event.info.parameters[*index];
+        let value = state.parameter_value(target, *index);
+        let is_selected = n == state.selected_parameter;
+        let shown = match parameter.label(value) {
+            Some(label) => format!("{} ({})", value, label),
+            None => format!("{:.2}", value),
+        };
         execute!(stdout,
-            SetForegroundColor(if is_active { Color::Green } else { Color::White }),
-            Print(format!("[{}] ", i + 1)),
-            Print(path),
+            SetForegroundColor(if is_selected && panel_focused { Color::Green } else { Color::White }),
+            Print(if is_selected { "> " } else { "  " }),
+            Print(format!("{:<20} {:>6} {} {:<6}  {}  step {}\r\n",
+                parameter.name,
+                parameter.minimum,
+                value_bar(parameter.fraction(value), 20),
+                parameter.maximum,
+                shown,
+                parameter.step())),
             ResetColor
         )?;
+        if is_selected && !parameter.labels.is_empty() {
+            execute!(stdout,
+                SetForegroundColor(Color::DarkGrey),
+                Print(format!("    labels: {}\r\n", parameter.labels.join(", "))),
+                ResetColor
+            )?;
+        }
+    }
 
-        if is_active {
+    // Global parameters with the value set and the value in use
+    let globals_focused = state.focus == Focus::Globals;
+    execute!(stdout,
+        Print("\r\n"),
+        SetForegroundColor(if globals_focused { Color::Green } else { Color::Yellow }),
+        Print("* Global Parameters"),
+        SetForegroundColor(Color::DarkGrey),
+        Print("  [G] set by name or ID\r\n"),
+        ResetColor
+    )?;
+    if state.globals.is_empty() {
+        execute!(stdout, Print("  (no global parameters in the loaded banks)\r\n"))?;
+    }
+    for (n, global) in state.globals.iter().enumerate() {
+        let is_selected = n == state.selected_global;
+        let label = global.label().map(|label| format!(" ({})", label)).unwrap_or_default();
+        let color = if is_selected && globals_focused {
+            Color::Green
+        } else if global.info.is_settable() {
+            Color::White
+        } else {
+            Color::DarkGrey
+        };
+        execute!(stdout,
+            SetForegroundColor(color),
+            Print(if is_selected { "> " } else { "  " }),
+            Print(format!("{:<20} {} {:>6} {} {:<6}  {:.2}{}  final {:.2}\r\n",
+                global.info.name,
+                global.id_string(),
+                global.info.minimum,
+                value_bar(global.info.fraction(global.value), 20),
+                global.info.maximum,
+                global.value,
+                label,
+                global.final_value)),
+            ResetColor
+        )?;
+        if is_selected && globals_focused && !global.info.labels.is_empty() {
             execute!(stdout,
-                SetForegroundColor(Color::Green),
-                Print(" [PLAYING]"),
+                SetForegroundColor(Color::DarkGrey),
+                Print(format!("    labels: {}\r\n", global.info.labels.join(", "))),
                 ResetColor
             )?;
         }
+    }
+    if let Some(input) = &state.global_input {
+        execute!(stdout,
+            SetForegroundColor(Color::Green),
+            Print(format!("  Set global (name or ID = value or label): {}_  (Enter sets, Esc cancels)\r\n", input)),
+            ResetColor
+        )?;
+    } else if let Some(message) = &state.global_message {
+        execute!(stdout, SetForegroundColor(Color::DarkGrey), Print(format!("  {}\r\n", message)), ResetColor)?;
+    }
 
+    // Snapshots: started ones blend their mix changes in by their intensity
+    let snapshots_focused = state.focus == Focus::Snapshots;
+    let active = state.snapshots.iter().filter(|snapshot| snapshot.is_active()).count();
+    execute!(stdout,
+        Print("\r\n"),
+        SetForegroundColor(if snapshots_focused { Color::Green } else { Color::Yellow }),
+        Print(format!("* Snapshots: {} active", active)),
+        SetForegroundColor(Color::DarkGrey),
+        Print("  [Enter] start/stop  [+/-] intensity\r\n"),
+        ResetColor
+    )?;
+    if state.snapshots.is_empty() {
+        execute!(stdout, Print("  (no snapshots in the loaded banks)\r\n"))?;
+    }
+    for (n, snapshot) in state.snapshots.iter().enumerate() {
+        let is_selected = n == state.selected_snapshot;
+        let color = if is_selected && snapshots_focused {
+            Color::Green
+        } else if snapshot.is_active() {
+            Color::White
+        } else {
+            Color::DarkGrey
+        };
+        execute!(stdout,
+            SetForegroundColor(color),
+            Print(if is_selected { "> " } else { "  " }),
+            Print(format!("{:<32} {} {:5.1}  ",
+                snapshot.info.path,
+                value_bar(snapshot.intensity / 100.0, 20),
+                snapshot.intensity)),
+            ResetColor
+        )?;
+        match snapshot.state {
+            Some(playback) => execute!(stdout, SetForegroundColor(state_color(playback)), Print(format!("[{:?}]", playback)), ResetColor)?,
+            None => execute!(stdout, SetForegroundColor(Color::DarkGrey), Print("off"), ResetColor)?,
+        }
         execute!(stdout, Print("\r\n"))?;
     }
 
-    // 3D Position display
+    // Live instances per event, from the registry
+    let counts = state.registry.counts();
+    execute!(stdout,
+        Print("\r\n"),
+        SetForegroundColor(Color::Yellow),
+        Print(format!("* Instances: {} live", state.registry.instances().len())),
+        SetForegroundColor(Color::DarkGrey),
+        Print("  [C] check for leaks\r\n"),
+        ResetColor
+    )?;
+    for (path, count) in &counts {
+        let mut states = Vec::new();
+        for (n, name) in [
+            (count.starting, "starting"),
+            (count.playing, "playing"),
+            (count.sustaining, "sustaining"),
+            (count.stopping, "stopping"),
+            (count.stopped, "stopped"),
+        ] {
+            if n > 0 {
+                states.push(format!("{} {}", n, name));
+            }
+        }
+        let released = if count.released > 0 { format!(" ({} released)", count.released) } else { String::new() };
+        execute!(stdout, Print(format!("  {:<32} {}{}\r\n", path, states.join(", "), released)))?;
+    }
+    if let Some(report) = &state.leak_report {
+        let clean = state.registry.leaks().is_empty();
+        execute!(stdout, SetForegroundColor(if clean { Color::Green } else { Color::Red }))?;
+        for line in report {
+            execute!(stdout, Print(format!("  {}\r\n", line)))?;
+        }
+        execute!(stdout, ResetColor)?;
+    }
+
+    // Output levels: the master mix, then every bus locked in the mixer
+    execute!(stdout,
+        Print("\r\n"),
+        SetForegroundColor(Color::Yellow),
+        Print("* Levels"),
+        SetForegroundColor(Color::DarkGrey),
+        Print(format!("  ({:.0} dB to 0 dB)  [F] spectrum  [X] lock buses in the mixer to meter them\r\n", METER_FLOOR_DB)),
+        ResetColor
+    )?;
+    draw_levels(&mut stdout, "master", state.master_levels.as_ref())?;
+    for strip in state.mixer.buses.iter().filter(|strip| strip.locked) {
+        draw_levels(&mut stdout, &strip.path, strip.levels.as_ref())?;
+    }
+    if let Some(spectrum) = &state.spectrum {
+        execute!(stdout,
+            Print("\r\n"),
+            SetForegroundColor(Color::Yellow),
+            Print(format!("* Spectrum (master, {:.0} dB to 0 dB)\r\n", SPECTRUM_FLOOR_DB)),
+            ResetColor
+        )?;
+        draw_spectrum(&mut stdout, &state.spectrum_bands, spectrum.max_frequency())?;
+    }
+
+    // 3D Position display; the highlighted line is what WASD/QE moves
+    let moving_color = |moving: bool| if moving { Color::Green } else { Color::White };
+    let moving_source = state.mover == Mover::Source;
     execute!(stdout,
         Print("\r\n"),
         SetForegroundColor(Color::Yellow),
         Print("* 3D Position:\r\n"),
-        ResetColor,
-        Print(format!("  Source: X:{:5.1} Y:{:5.1} Z:{:5.1}\r\n",
-            state.source_pos.x, state.source_pos.y, state.source_pos.z)),
-        Print(format!("  Listener: X:{:5.1} Y:{:5.1} Z:{:5.1}\r\n",
-            state.listener_pos.x, state.listener_pos.y, state.listener_pos.z))
+        SetForegroundColor(moving_color(moving_source && state.selected_instance.is_none())),
+        Print(format!("{} Spawn point: X:{:5.1} Y:{:5.1} Z:{:5.1}\r\n",
+            if state.selected_instance.is_none() { ">" } else { " " },
+            state.spawn_pos.x, state.spawn_pos.y, state.spawn_pos.z)),
+        ResetColor
     )?;
+    for (id, glyph) in state.spatial_instances().into_iter().zip(source_glyphs()) {
+        let active = &state.active_instances[&id];
+        let emitter = &active.emitter;
+        let path = match &active.follower {
+            Some(follower) => format!("  {} {}m/s {}",
+                follower.path.shape().name(),
+                follower.path.units_per_second(),
+                follower.path.looping_mode()),
+            None => String::new(),
+        };
+        let is_selected = state.selected_instance == Some(id);
+        let info = &state.events[active.event].info;
+        execute!(stdout,
+            SetForegroundColor(moving_color(moving_source && is_selected)),
+            Print(format!("{} {} {:<18} X:{:5.1} Y:{:5.1} Z:{:5.1}  Yaw:{:4.0}°  {:4.1}m/s{}\r\n",
+                if is_selected { ">" } else { " " },
+                glyph,
+                info.path.rsplit('/').next().unwrap_or(&info.path),
+                emitter.position.x, emitter.position.y, emitter.position.z,
+                emitter.yaw,
+                spatial::distance(&emitter.velocity, &spatial::ZERO),
+                path)),
+            ResetColor
+        )?;
+    }
+    for (i, listener) in state.listeners.iter().enumerate() {
+        let is_selected = i == state.selected_listener;
+        execute!(stdout,
+            SetForegroundColor(moving_color(is_selected && state.mover == Mover::Listener)),
+            Print(format!("{} Listener {}:  X:{:5.1} Y:{:5.1} Z:{:5.1}  Yaw:{:4.0}°  {:4.1}m/s  Weight:{:.2}\r\n",
+                if is_selected { ">" } else { " " },
+                i + 1,
+                listener.position.x, listener.position.y, listener.position.z,
+                listener.yaw,
+                spatial::distance(&listener.velocity, &spatial::ZERO),
+                listener.weight)),
+            ResetColor
+        )?;
+    }
+
+    if let Some(input) = &state.coordinate_input {
+        execute!(stdout,
+            Print("\r\n"),
+            SetForegroundColor(Color::Green),
+            Print(format!("Spawn {} at x,y,z: {}_  (Enter spawns, Esc cancels)\r\n",
+                state.events[state.selected_event].info.path, input)),
+            ResetColor
+        )?;
+    }
 
     // Controls
     if state.show_help {
@@ -275,12 +1675,34 @@ fn draw_ui(state: &HarnessState) -> io::Result<()> {
             Print("Controls:\r\n"),
             ResetColor,
             SetForegroundColor(Color::White),
-            Print("  [1-6]     Play/Stop event\r\n"),
+            Print("  [Tab]     Switch between event list, parameter, global and snapshot panels\r\n"),
+            Print("  [Up/Down] Select event / parameter (PgUp/PgDn scroll)\r\n"),
+            Print("  [Left/Right] Collapse/expand folder\r\n"),
+            Print("  [Enter]   Play/Stop selected event, open/close folder\r\n"),
+            Print("  [/]       Search events (Enter keeps, Esc clears)\r\n"),
             Print("  [Space]   Stop all events\r\n"),
-            Print("  [WASD]    Move source (X/Z)\r\n"),
-            Print("  [Q/E]     Move source (Up/Down)\r\n"),
-            Print("  [+/-]     Adjust parameters\r\n"),
+            Print("  [WASD]    Move selected instance, spawn point or listener (X/Z)\r\n"),
+            Print("  [Q/E]     Move selected instance, spawn point or listener (Up/Down)\r\n"),
+            Print("  [I]       Select next playing 3D instance / spawn point\r\n"),
+            Print("  [P]       Spawn selected event at a random spot near the listener\r\n"),
+            Print("  [T]       Spawn selected event at a typed x,y,z\r\n"),
+            Print("  [O]       Next motion path for selected instance (orbit, figure-eight,\r\n"),
+            Print("            spline, fly-over, line through listener, none)\r\n"),
+            Print("  [J/K]     Path slower/faster   [U] Path looping (once/loop/ping-pong)\r\n"),
+            Print("  [L]       Toggle moving source / listener\r\n"),
+            Print("  [ [ / ] ] Turn selected instance or listener left/right\r\n"),
+            Print("  [1-4]     Select listener\r\n"),
+            Print("  [N/Del]   Add / remove listener (up to 4)\r\n"),
+            Print("  [,/.]     Listener weight down/up\r\n"),
+            Print("  [Left/Right] Pick instance in the parameter panel\r\n"),
+            Print("  [+/-]     Step selected parameter (global or snapshot intensity in those panels)\r\n"),
+            Print("  [Enter]   Start/stop the selected snapshot, in the snapshot panel\r\n"),
+            Print("  [G]       Set a global by name or ID to a value or label (Weather=Rainy)\r\n"),
             Print("  [R]       Reset position\r\n"),
+            Print("  [M]       Toggle radar\r\n"),
+            Print("  [X]       Mixer: bus/VCA volume, mute, pause, stop, CPU and levels\r\n"),
+            Print("  [F]       Toggle the master spectrum view\r\n"),
+            Print("  [C]       Check for leaked or stuck-sustaining instances\r\n"),
             Print("  [H]       Toggle help\r\n"),
             Print("  [Esc]     Exit\r\n"),
             ResetColor
@@ -289,21 +1711,34 @@ fn draw_ui(state: &HarnessState) -> io::Result<()> {
         execute!(stdout,
             Print("\r\n"),
             SetForegroundColor(Color::DarkGrey),
-            Print("[H] Help  [1-6] Play  [WASD] Move  [Space] Stop All  [Esc] Exit\r\n"),
+            Print("[H] Help  [/] Search  [Tab] Panel  [Enter] Play  [+/-] Adjust  [WASD] Move  [L] Listener  [X] Mixer  [Space] Stop All  [Esc] Exit\r\n"),
             ResetColor
         )?;
     }
 
+    // Drawn last, beside the panels, so nothing prints over it
+    if state.show_radar {
+        draw_radar(&mut stdout, state)?;
+    }
+
     stdout.flush()?;
     Ok(())
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let (output, args) = OutputOptions::from_process_args()?;
+
     // Setup terminal
     enable_raw_mode()?;
     clear_screen();
 
-    let mut state = HarnessState::new()?;
+    let mut state = match HarnessState::new(&output, &args[1..]) {
+        Ok(state) => state,
+        Err(e) => {
+            disable_raw_mode()?;
+            return Err(e);
+        }
+    };
 
     println!("Initializing FMOD Studio Interactive Harness...");
     std::thread::sleep(Duration::from_millis(500));
@@ -314,126 +1749,230 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         state.update()?;
 
         // Draw UI
-        draw_ui(&state)?;
+        draw_ui(&mut state)?;
 
         // Handle input (non-blocking)
-        if event::poll(Duration::from_millis(16))? {
-            if let Event::Key(KeyEvent { code, modifiers, .. }) = event::read()? {
-                match code {
-                    // Exit
-                    KeyCode::Esc => break,
+        if event::poll(Duration::from_millis(16))?
+            && let Event::Key(KeyEvent { code, modifiers, .. }) = event::read()?
+        {
+            // While typing a coordinate, keys edit it
+            if state.coordinate_input.is_some() {
+                state.coordinate_key(code)?;
+                continue;
+            }
 
-                    // Help
-                    KeyCode::Char('h') | KeyCode::Char('H') => {
-                        state.show_help = !state.show_help;
-                    }
+            // While typing a global parameter edit, keys edit it
+            if state.global_input.is_some() {
+                state.global_key(code);
+                continue;
+            }
 
-                    // Play events
-                    KeyCode::Char('1') => {
-                        if state.active_instances.contains_key(&0) {
-                            state.stop_event(0)?;
-                        } else {
-                            state.play_event(0)?;
-                        }
-                    }
-                    KeyCode::Char('2') if state.event_descriptions.len() > 1 => {
-                        if state.active_instances.contains_key(&1) {
-                            state.stop_event(1)?;
-                        } else {
-                            state.play_event(1)?;
-                        }
-                    }
-                    KeyCode::Char('3') if state.event_descriptions.len() > 2 => {
-                        if state.active_instances.contains_key(&2) {
-                            state.stop_event(2)?;
-                        } else {
-                            state.play_event(2)?;
-                        }
-                    }
-                    KeyCode::Char('4') if state.event_descriptions.len() > 3 => {
-                        if state.active_instances.contains_key(&3) {
-                            state.stop_event(3)?;
-                        } else {
-                            state.play_event(3)?;
-                        }
-                    }
-                    KeyCode::Char('5') if state.event_descriptions.len() > 4 => {
-                        if state.active_instances.contains_key(&4) {
-                            state.stop_event(4)?;
-                        } else {
-                            state.play_event(4)?;
-                        }
-                    }
-                    KeyCode::Char('6') if state.event_descriptions.len() > 5 => {
-                        if state.active_instances.contains_key(&5) {
-                            state.stop_event(5)?;
-                        } else {
-                            state.play_event(5)?;
-                        }
-                    }
+            // The mixer screen handles its own keys and passes the rest on
+            if state.show_mixer && state.mixer_key(code, modifiers)? {
+                continue;
+            }
 
-                    // Stop all
-                    KeyCode::Char(' ') => {
-                        state.stop_all_events()?;
-                    }
+            // While searching, keys edit the search text
+            if state.searching {
+                state.search_key(code);
+                continue;
+            }
 
-                    // 3D movement
-                    KeyCode::Char('w') | KeyCode::Char('W') => {
-                        let delta = if modifiers.contains(KeyModifiers::SHIFT) { 0.1 } else { 1.0 };
-                        state.move_source(0.0, 0.0, -delta)?;
-                    }
-                    KeyCode::Char('s') | KeyCode::Char('S') => {
-                        let delta = if modifiers.contains(KeyModifiers::SHIFT) { 0.1 } else { 1.0 };
-                        state.move_source(0.0, 0.0, delta)?;
-                    }
-                    KeyCode::Char('a') | KeyCode::Char('A') => {
-                        let delta = if modifiers.contains(KeyModifiers::SHIFT) { 0.1 } else { 1.0 };
-                        state.move_source(-delta, 0.0, 0.0)?;
-                    }
-                    KeyCode::Char('d') | KeyCode::Char('D') => {
-                        let delta = if modifiers.contains(KeyModifiers::SHIFT) { 0.1 } else { 1.0 };
-                        state.move_source(delta, 0.0, 0.0)?;
-                    }
-                    KeyCode::Char('q') | KeyCode::Char('Q') => {
-                        let delta = if modifiers.contains(KeyModifiers::SHIFT) { 0.1 } else { 1.0 };
-                        state.move_source(0.0, delta, 0.0)?;
+            match code {
+                // Clear the search first, exit otherwise
+                KeyCode::Esc if !state.tree.query().is_empty() => {
+                    state.tree.set_query("");
+                    state.sync_selection();
+                }
+                KeyCode::Esc => break,
+
+                // Help
+                KeyCode::Char('h') | KeyCode::Char('H') => {
+                    state.show_help = !state.show_help;
+                }
+
+                // Focus and selection
+                KeyCode::Tab => {
+                    state.focus = match state.focus {
+                        Focus::Events => Focus::Parameters,
+                        Focus::Parameters => Focus::Globals,
+                        Focus::Globals => Focus::Snapshots,
+                        Focus::Snapshots => Focus::Events,
+                    };
+                }
+                KeyCode::Up => match state.focus {
+                    Focus::Events => state.move_tree(-1),
+                    Focus::Parameters => state.select_parameter(-1),
+                    Focus::Globals => state.select_global(-1),
+                    Focus::Snapshots => state.select_snapshot(-1),
+                },
+                KeyCode::Down => match state.focus {
+                    Focus::Events => state.move_tree(1),
+                    Focus::Parameters => state.select_parameter(1),
+                    Focus::Globals => state.select_global(1),
+                    Focus::Snapshots => state.select_snapshot(1),
+                },
+                KeyCode::PageUp => state.move_tree(-(TREE_HEIGHT as isize)),
+                KeyCode::PageDown => state.move_tree(TREE_HEIGHT as isize),
+                KeyCode::Left => match state.focus {
+                    Focus::Events => {
+                        state.tree.collapse();
+                        state.sync_selection();
                     }
-                    KeyCode::Char('e') | KeyCode::Char('E') => {
-                        let delta = if modifiers.contains(KeyModifiers::SHIFT) { 0.1 } else { 1.0 };
-                        state.move_source(0.0, -delta, 0.0)?;
+                    Focus::Parameters => state.select_target(-1),
+                    Focus::Globals | Focus::Snapshots => {}
+                },
+                KeyCode::Right => match state.focus {
+                    Focus::Events => {
+                        state.tree.expand();
                     }
+                    Focus::Parameters => state.select_target(1),
+                    Focus::Globals | Focus::Snapshots => {}
+                },
+                KeyCode::Enter => state.activate_selected()?,
 
-                    // Reset position
-                    KeyCode::Char('r') | KeyCode::Char('R') => {
-                        state.source_pos = Vector { x: 0.0, y: 0.0, z: -5.0 };
-                        state.move_source(0.0, 0.0, 0.0)?;
-                    }
+                // Incremental search over event paths
+                KeyCode::Char('/') => {
+                    state.searching = true;
+                    state.focus = Focus::Events;
+                }
+
+                // Stop all
+                KeyCode::Char(' ') => {
+                    state.stop_all_events()?;
+                }
 
-                    // Parameter adjustment
-                    KeyCode::Char('+') | KeyCode::Char('=') => {
-                        state.adjust_parameter(0.1)?;
+                // 3D movement
+                KeyCode::Char('w') | KeyCode::Char('W') => {
+                    let delta = if modifiers.contains(KeyModifiers::SHIFT) { 0.1 } else { 1.0 };
+                    state.nudge(0.0, 0.0, -delta)?;
+                }
+                KeyCode::Char('s') | KeyCode::Char('S') => {
+                    let delta = if modifiers.contains(KeyModifiers::SHIFT) { 0.1 } else { 1.0 };
+                    state.nudge(0.0, 0.0, delta)?;
+                }
+                KeyCode::Char('a') | KeyCode::Char('A') => {
+                    let delta = if modifiers.contains(KeyModifiers::SHIFT) { 0.1 } else { 1.0 };
+                    state.nudge(-delta, 0.0, 0.0)?;
+                }
+                KeyCode::Char('d') | KeyCode::Char('D') => {
+                    let delta = if modifiers.contains(KeyModifiers::SHIFT) { 0.1 } else { 1.0 };
+                    state.nudge(delta, 0.0, 0.0)?;
+                }
+                KeyCode::Char('q') | KeyCode::Char('Q') => {
+                    let delta = if modifiers.contains(KeyModifiers::SHIFT) { 0.1 } else { 1.0 };
+                    state.nudge(0.0, delta, 0.0)?;
+                }
+                KeyCode::Char('e') | KeyCode::Char('E') => {
+                    let delta = if modifiers.contains(KeyModifiers::SHIFT) { 0.1 } else { 1.0 };
+                    state.nudge(0.0, -delta, 0.0)?;
+                }
+
+                // Reset position of whatever is being moved
+                KeyCode::Char('r') | KeyCode::Char('R') => match state.mover {
+                    Mover::Source => state.reset_source()?,
+                    Mover::Listener => {
+                        state.listeners[state.selected_listener] = Listener::new(spatial::ZERO);
+                        state.listener_trackers[state.selected_listener].reset();
+                        state.apply_listeners()?;
                     }
-                    KeyCode::Char('-') | KeyCode::Char('_') => {
-                        state.adjust_parameter(-0.1)?;
+                },
+
+                // Instances
+                KeyCode::Char('i') | KeyCode::Char('I') => state.cycle_instance(),
+                KeyCode::Char('p') | KeyCode::Char('P') => state.spawn_random()?,
+                KeyCode::Char('t') | KeyCode::Char('T') => {
+                    state.coordinate_input = Some(String::new());
+                }
+
+                // Motion paths
+                KeyCode::Char('o') | KeyCode::Char('O') => state.cycle_path(),
+                KeyCode::Char('j') | KeyCode::Char('J') => state.adjust_path_speed(1.0 / 1.5),
+                KeyCode::Char('k') | KeyCode::Char('K') => state.adjust_path_speed(1.5),
+                KeyCode::Char('u') | KeyCode::Char('U') => state.cycle_looping(),
+
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    state.show_radar = !state.show_radar;
+                }
+                KeyCode::Char('x') | KeyCode::Char('X') => {
+                    state.show_mixer = true;
+                }
+                KeyCode::Char('f') | KeyCode::Char('F') => state.toggle_spectrum()?,
+
+                // Listeners
+                KeyCode::Char('l') | KeyCode::Char('L') => {
+                    state.mover = match state.mover {
+                        Mover::Source => Mover::Listener,
+                        Mover::Listener => Mover::Source,
+                    };
+                }
+                KeyCode::Char(c @ '1'..='4') => {
+                    let index = c as usize - '1' as usize;
+                    if index < state.listeners.len() {
+                        state.selected_listener = index;
+                        state.mover = Mover::Listener;
                     }
+                }
+                KeyCode::Char('[') | KeyCode::Char('{') => {
+                    let step = if modifiers.contains(KeyModifiers::SHIFT) { YAW_STEP / 3.0 } else { YAW_STEP };
+                    state.turn(-step)?;
+                }
+                KeyCode::Char(']') | KeyCode::Char('}') => {
+                    let step = if modifiers.contains(KeyModifiers::SHIFT) { YAW_STEP / 3.0 } else { YAW_STEP };
+                    state.turn(step)?;
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') => state.add_listener()?,
+                KeyCode::Delete => state.remove_listener()?,
+                KeyCode::Char(',') | KeyCode::Char('<') => state.adjust_listener_weight(-0.1)?,
+                KeyCode::Char('.') | KeyCode::Char('>') => state.adjust_listener_weight(0.1)?,
+
+                // Parameter adjustment, by a step derived from the range
+                KeyCode::Char('+') | KeyCode::Char('=') => match state.focus {
+                    Focus::Globals => state.adjust_global(1.0)?,
+                    Focus::Snapshots => state.adjust_intensity(1.0)?,
+                    _ => state.adjust_parameter(1.0)?,
+                },
+                KeyCode::Char('-') | KeyCode::Char('_') => match state.focus {
+                    Focus::Globals => state.adjust_global(-1.0)?,
+                    Focus::Snapshots => state.adjust_intensity(-1.0)?,
+                    _ => state.adjust_parameter(-1.0)?,
+                },
 
-                    _ => {}
+                KeyCode::Char('c') | KeyCode::Char('C') => state.check_leaks(),
+
+                // Global parameters, typed as name=value or id=label
+                KeyCode::Char('g') | KeyCode::Char('G') => {
+                    state.global_input = Some(String::new());
+                    state.focus = Focus::Globals;
                 }
+
+                _ => {}
             }
         }
     }
 
-    // Cleanup
+    // Cleanup; whatever is still unreleased after this was lost track of
     state.stop_all_events()?;
-    for bank in state.banks.iter() {
-        bank.unload()?;
+    for snapshot in &mut state.snapshots {
+        snapshot.stop(StopMode::Immediate)?;
     }
-    state.studio.release()?;
+    if let Some(spectrum) = state.spectrum.take() {
+        spectrum.remove()?;
+    }
+    state.registry.update();
+    let leaks = state.registry.leaks();
+    state.session.close()?;
 
     // Restore terminal
     disable_raw_mode()?;
     clear_screen();
 
+    if !leaks.is_empty() {
+        println!("⚠️  {} instance(s) leaked:", leaks.len());
+        for leak in &leaks {
+            println!("   - {}", leak);
+        }
+    }
     println!("Interactive harness closed. Thanks for testing!");
 
     Ok(())
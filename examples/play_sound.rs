@@ -1,21 +1,67 @@
-// Play a sound file with FMOD 2.03.09
-// Run with: cargo run --example play_sound [path/to/sound.wav/mp3/ogg]
+// Play a sound file with FMOD 2.03.09, optionally through a chain of DSP effects
+// Run with: cargo run --example play_sound [path/to/sound.wav/mp3/ogg] [--fx effect[:param=value,...]]... [--output nosound]
+// Examples:
+//   cargo run --example play_sound assets/audio/bird.ogg --fx lowpass:cutoff=800 --fx reverb:decay=3000,wet=-3
+//   cargo run --example play_sound assets/audio/bird.ogg --fx echo:delay=250,feedback=40 --fx pitch:pitch=1.5
+//   cargo run --example play_sound --list-fx
 
-use libfmod::{System, Init, Mode, TimeUnit};
-use std::{env, thread, time::Duration};
+use crossterm::{
+    cursor, execute,
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    style::{Color, Print, ResetColor, SetForegroundColor},
+    terminal::{Clear, ClearType, disable_raw_mode, enable_raw_mode},
+};
+use libfmod::{Channel, System, Init, Mode, Sound, TimeUnit};
+use libfmod_demos::{EffectKind, EffectRack, EffectSpec, OutputOptions};
+use std::{
+    io::{self, IsTerminal, Write},
+    thread,
+    time::Duration,
+};
+
+// Print the effects --fx accepts and their parameters
+fn list_effects() {
+    println!("Effects for --fx name[:param=value,...] (chained in the order given):\n");
+    for kind in EffectKind::ALL {
+        println!("  {}", kind);
+        for parameter in kind.parameters() {
+            println!("    {:<20} {} to {} {}", parameter.name, parameter.minimum, parameter.maximum, parameter.unit);
+        }
+    }
+}
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = env::args().collect();
+    let (output, args) = OutputOptions::from_process_args()?;
 
     println!("\n🎵 FMOD 2.03.09 Sound Player\n");
 
+    // The sound file and the effect chain
+    let mut sound_arg = None;
+    let mut specs = Vec::new();
+    let mut rest = args.iter().skip(1);
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--list-fx" => {
+                list_effects();
+                return Ok(());
+            }
+            "--fx" => {
+                let spec = rest.next().ok_or("--fx needs a value, e.g. echo:delay=250")?;
+                specs.push(spec.parse::<EffectSpec>()?);
+            }
+            flag if flag.starts_with("--") => return Err(format!("unknown option {}", flag).into()),
+            file => sound_arg = Some(file.to_string()),
+        }
+    }
+
     // Check for sound file argument
-    if args.len() < 2 {
-        println!("Usage: {} <path/to/sound/file>", args[0]);
+    let Some(sound_file) = sound_arg else {
+        println!("Usage: {} <path/to/sound/file> [--fx effect[:param=value,...]]... [--list-fx] {}", args[0], OutputOptions::USAGE);
         println!("\nSupported formats: WAV, MP3, OGG, FLAC, etc.");
         println!("\nExample:");
         println!("  cargo run --example play_sound /usr/share/sounds/freedesktop/stereo/bell.oga");
         println!("  cargo run --example play_sound ~/Music/song.mp3");
+        println!("  cargo run --example play_sound ~/Music/song.mp3 --fx highpass:cutoff=1200 --fx chorus");
 
         // Try to find a system sound to suggest
         let test_sounds = vec![
@@ -36,12 +82,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
 
         return Ok(());
-    }
-
-    let sound_file = &args[1];
+    };
 
     // Check if file exists
-    if !std::path::Path::new(sound_file).exists() {
+    if !std::path::Path::new(&sound_file).exists() {
         println!("❌ File not found: {}", sound_file);
         return Ok(());
     }
@@ -58,12 +102,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("✅ FMOD {}.{:02}.{:02} (build {})", major, minor, patch, build);
 
     // Initialize system
-    system.init(512, Init::NORMAL, None)?;
-    println!("✅ System initialized");
+    output.init_system(&system, 512, Init::NORMAL)?;
+    println!("✅ System initialized (output: {})", output.mode);
 
     // Create sound
     println!("\nLoading: {}", sound_file);
-    let sound = match system.create_sound(sound_file, Mode::DEFAULT, None) {
+    let sound = match system.create_sound(&sound_file, Mode::DEFAULT, None) {
         Ok(s) => {
             println!("✅ Sound loaded successfully");
             s
@@ -81,10 +125,52 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("ℹ️  Duration: {:.1} seconds", seconds);
     }
 
-    // Play sound
+    // Play it, then release the sound and system however playback ended
+    let played = play(&system, sound, &specs);
+    let released = sound.release().and(system.release());
+    played?;
+    released?;
+    println!("✅ System released");
+
+    println!("\n🎉 Success! FMOD 2.03.09 audio playback works!");
+
+    Ok(())
+}
+
+// Play the sound through the effect chain until it ends, then take the effects off again
+fn play(system: &System, sound: Sound, specs: &[EffectSpec]) -> Result<(), Box<dyn std::error::Error>> {
+    // Paused until the effects are in place
     println!("\n▶️  Playing sound...");
-    let channel = system.play_sound(sound, None, false)?;
+    let channel = system.play_sound(sound, None, true)?;
+    let mut rack = EffectRack::build(system, channel, specs)?;
+    for (n, effect) in rack.effects().iter().enumerate() {
+        println!("   Effect {}: {}", n + 1, effect.kind);
+    }
+
+    let result = (|| -> Result<(), Box<dyn std::error::Error>> {
+        channel.set_paused(false)?;
+
+        // With effects on a terminal, edit the chain live until the sound ends
+        if !rack.is_empty() && io::stdin().is_terminal() {
+            enable_raw_mode()?;
+            let result = run_rack(system, &channel, &mut rack);
+            let restored = disable_raw_mode();
+            println!();
+            result?;
+            restored?;
+            return Ok(());
+        }
+        wait_for_end(system, &channel)
+    })();
+    let removed = rack.remove();
+    result?;
+    removed?;
+    println!("\n✅ Playback complete!");
+    Ok(())
+}
 
+// Show the playback position until the sound ends
+fn wait_for_end(system: &System, channel: &Channel) -> Result<(), Box<dyn std::error::Error>> {
     // Show volume control hint
     println!("   Use your system volume control if needed");
     println!("   Press Ctrl+C to stop\n");
@@ -107,7 +193,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 if new_pos != position {
                     position = new_pos;
                     print!("\r   Playing... {} seconds", position);
-                    use std::io::{self, Write};
                     io::stdout().flush()?;
                 }
             }
@@ -116,15 +201,143 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             thread::sleep(Duration::from_millis(50));
         }
     }
+    println!();
+    Ok(())
+}
 
-    println!("\n\n✅ Playback complete!");
-
-    // Cleanup
-    sound.release()?;
-    system.release()?;
-    println!("✅ System released");
+// Which effect and parameter the keys edit
+struct RackCursor {
+    effect: usize,
+    parameter: usize,
+}
 
-    println!("\n🎉 Success! FMOD 2.03.09 audio playback works!");
+// Redraw the chain and apply keys until the sound ends or Esc/Q
+//
+// A key whose FMOD call fails shows the error under the chain instead of
+// ending playback; it stays there until the next key.
+fn run_rack(system: &System, channel: &Channel, rack: &mut EffectRack) -> Result<(), Box<dyn std::error::Error>> {
+    let mut cursor = RackCursor { effect: 0, parameter: 0 };
+    let mut status: Option<String> = None;
+    while channel.is_playing().unwrap_or(false) {
+        system.update()?;
+        draw_rack(channel, rack, &cursor, status.as_deref())?;
 
+        if !event::poll(Duration::from_millis(50))? {
+            continue;
+        }
+        let Event::Key(KeyEvent { code, modifiers, .. }) = event::read()? else {
+            continue;
+        };
+        let count = rack.effect_mut(cursor.effect).map_or(0, |effect| effect.kind.parameters().len());
+        let applied = match code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => break,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => break,
+            KeyCode::Up => {
+                cursor.effect = cursor.effect.saturating_sub(1);
+                cursor.parameter = 0;
+                Ok(())
+            }
+            KeyCode::Down => {
+                cursor.effect = (cursor.effect + 1).min(rack.len() - 1);
+                cursor.parameter = 0;
+                Ok(())
+            }
+            KeyCode::Left => {
+                cursor.parameter = cursor.parameter.saturating_sub(1);
+                Ok(())
+            }
+            KeyCode::Right => {
+                cursor.parameter = (cursor.parameter + 1).min(count.saturating_sub(1));
+                Ok(())
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Char('-') | KeyCode::Char('_') | KeyCode::PageUp | KeyCode::PageDown => {
+                let steps = match code {
+                    KeyCode::Char('+') | KeyCode::Char('=') => 1.0,
+                    KeyCode::Char('-') | KeyCode::Char('_') => -1.0,
+                    KeyCode::PageUp => 10.0,
+                    _ => -10.0,
+                };
+                rack.effect_mut(cursor.effect)
+                    .map_or(Ok(()), |effect| effect.adjust(cursor.parameter, steps))
+                    .map_err(|e| e.to_string())
+            }
+            KeyCode::Char('b') | KeyCode::Char('B') => rack
+                .effect_mut(cursor.effect)
+                .map_or(Ok(()), |effect| effect.toggle_bypass())
+                .map_err(|e| e.to_string()),
+            KeyCode::Char('[') | KeyCode::Char(']') => {
+                let offset = if code == KeyCode::Char('[') { -1 } else { 1 };
+                rack.move_effect(cursor.effect, offset)
+                    .map(|target| cursor.effect = target)
+                    .map_err(|e| e.to_string())
+            }
+            KeyCode::Char(' ') => channel
+                .get_paused()
+                .and_then(|paused| channel.set_paused(!paused))
+                .map_err(|e| format!("pause failed: {:?}", e)),
+            _ => Ok(()),
+        };
+        status = applied.err();
+    }
     Ok(())
-}
\ No newline at end of file
+}
+
+// `[#####-----]` showing where `fraction` (0-1) lies
+fn value_bar(fraction: f32, width: usize) -> String {
+    let filled = (fraction.clamp(0.0, 1.0) * width as f32).round() as usize;
+    format!("[{}{}]", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+fn draw_rack(channel: &Channel, rack: &EffectRack, cursor: &RackCursor, status: Option<&str>) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    execute!(stdout, Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+
+    let position = channel.get_position(TimeUnit::MS).unwrap_or(0) as f32 / 1000.0;
+    let paused = if channel.get_paused().unwrap_or(false) { "  PAUSED" } else { "" };
+    execute!(stdout,
+        SetForegroundColor(Color::Cyan),
+        Print(format!("Effect chain, signal flows top to bottom ({:.1}s{})\r\n\r\n", position, paused)),
+        ResetColor
+    )?;
+
+    for (n, effect) in rack.effects().iter().enumerate() {
+        let selected = n == cursor.effect;
+        execute!(stdout,
+            SetForegroundColor(if selected { Color::Green } else { Color::White }),
+            Print(format!("{} {}. {:<12}", if selected { ">" } else { " " }, n + 1, effect.kind)),
+            SetForegroundColor(if effect.bypass { Color::DarkGrey } else { Color::Green }),
+            Print(if effect.bypass { "bypassed\r\n" } else { "active\r\n" }),
+            ResetColor
+        )?;
+        if !selected {
+            continue;
+        }
+        for (p, (parameter, value)) in effect.kind.parameters().iter().zip(&effect.values).enumerate() {
+            let chosen = p == cursor.parameter;
+            execute!(stdout,
+                SetForegroundColor(if chosen { Color::Green } else { Color::White }),
+                Print(format!("    {} {:<20} {:>8} {} {:<8} {:.2} {}\r\n",
+                    if chosen { ">" } else { " " },
+                    parameter.name,
+                    parameter.minimum,
+                    value_bar(parameter.fraction(*value), 20),
+                    parameter.maximum,
+                    value,
+                    parameter.unit)),
+                ResetColor
+            )?;
+        }
+    }
+
+    execute!(stdout, Print("\r\n"))?;
+    if let Some(message) = status {
+        execute!(stdout, SetForegroundColor(Color::Red), Print(format!("❌ {}\r\n", message)), ResetColor)?;
+    }
+    execute!(stdout,
+        SetForegroundColor(Color::DarkGrey),
+        Print("[Up/Down] Effect  [Left/Right] Parameter  [+/-] Adjust (PgUp/PgDn: x10)  [B] Bypass\r\n"),
+        Print("[ [ / ] ] Move effect earlier/later  [Space] Pause  [Esc/Q] Stop\r\n"),
+        ResetColor
+    )?;
+    stdout.flush()
+}
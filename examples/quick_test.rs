@@ -2,10 +2,19 @@
 // Run with: cargo run --example quick_test
 
 use libfmod::{System, Init};
+use libfmod_demos::OutputOptions;
 
 fn main() {
     println!("=== FMOD 2.03.09 Quick Test ===\n");
 
+    let output = match OutputOptions::from_process_args() {
+        Ok((output, _args)) => output,
+        Err(e) => {
+            println!("✗ {}", e);
+            return;
+        }
+    };
+
     // Test 1: Create system
     print!("1. Creating FMOD System... ");
     let system = match System::create() {
@@ -46,9 +55,9 @@ fn main() {
 
     // Test 3: Initialize system
     print!("3. Initializing system... ");
-    match system.init(512, Init::NORMAL, None) {
+    match output.init_system(&system, 512, Init::NORMAL) {
         Ok(_) => {
-            println!("✓ SUCCESS (512 channels)");
+            println!("✓ SUCCESS (512 channels, output: {})", output.mode);
         }
         Err(e) => {
             println!("✗ FAILED: {:?}", e);
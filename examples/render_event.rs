@@ -0,0 +1,200 @@
+// Render FMOD Studio events offline to a WAV file (non-realtime, no sound card needed)
+// Run with: ./run_demos.sh render_event [options] <event>[@start[:stop]] [--param NAME=VALUE[@time]]...
+// Examples:
+//   ./run_demos.sh render_event event:/Weapons/Explosion --length 3 --out explosion.wav
+//   ./run_demos.sh render_event "event:/Vehicles/Ride-on Mower" --param RPM=0 --param RPM=1000@2 \
+//       --param RPM=2000@4 --length 8 --out rpm_sweep.wav
+//   ./run_demos.sh render_event event:/Ambience/Country@0:4 event:/Weapons/Explosion@2 --length 6
+
+use libfmod::Vector;
+use libfmod_demos::{RenderEvent, RenderSpec, render};
+use std::{env, time::Duration};
+
+const DEFAULT_BANKS: [&str; 5] = [
+    "Master.bank",
+    "Master.strings.bank",
+    "SFX.bank",
+    "Vehicles.bank",
+    "Music.bank",
+];
+
+fn print_usage(program: &str) {
+    println!("Usage: {} [options] <event>[@start[:stop]] [event options]...", program);
+    println!();
+    println!("Options:");
+    println!("  --out <file.wav>         Output file (default: render.wav)");
+    println!("  --length <time>          Render length (default: 5s)");
+    println!("  --rate <hz>              Sample rate (default: 48000)");
+    println!("  --bank <path>            Load a bank (repeatable, default: SDK example banks)");
+    println!();
+    println!("Event options (apply to the preceding event):");
+    println!("  --param NAME=VALUE[@time]  Set a parameter at a time (default: at start)");
+    println!("  --pos X,Y,Z              3D position (listener at origin facing +Z)");
+    println!();
+    println!("Times are seconds (2, 2.5s) or milliseconds (500ms).");
+}
+
+fn parse_time(value: &str) -> Result<Duration, String> {
+    let (number, scale) = if let Some(ms) = value.strip_suffix("ms") {
+        (ms, 0.001)
+    } else if let Some(s) = value.strip_suffix('s') {
+        (s, 1.0)
+    } else {
+        (value, 1.0)
+    };
+    let seconds: f64 = number
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid time '{}'", value))?;
+    if seconds < 0.0 {
+        return Err(format!("negative time '{}'", value));
+    }
+    if !seconds.is_finite() {
+        return Err(format!("invalid time '{}'", value));
+    }
+    Duration::try_from_secs_f64(seconds * scale).map_err(|_| format!("invalid time '{}'", value))
+}
+
+/// `event:/Path[@start[:stop]]`
+fn parse_event(spec: &str) -> Result<RenderEvent, String> {
+    let Some((path, timing)) = spec.rsplit_once('@') else {
+        return Ok(RenderEvent::new(spec));
+    };
+    let mut event = RenderEvent::new(path);
+    match timing.split_once(':') {
+        Some((start, stop)) => {
+            event = event.start_at(parse_time(start)?).stop_at(parse_time(stop)?);
+        }
+        None => event = event.start_at(parse_time(timing)?),
+    }
+    Ok(event)
+}
+
+/// `NAME=VALUE[@time]`
+fn parse_param(spec: &str, event: &RenderEvent) -> Result<(String, f32, Duration), String> {
+    let (assignment, at) = match spec.rsplit_once('@') {
+        Some((assignment, at)) => (assignment, parse_time(at)?),
+        None => (spec, event.start),
+    };
+    let (name, value) = assignment
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE, got '{}'", spec))?;
+    let value: f32 = value
+        .parse()
+        .map_err(|_| format!("invalid value for {}: '{}'", name, value))?;
+    Ok((name.to_string(), value, at))
+}
+
+fn parse_position(spec: &str) -> Result<Vector, String> {
+    let parts: Vec<f32> = spec
+        .split(',')
+        .map(|p| p.trim().parse::<f32>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| format!("invalid position '{}', expected X,Y,Z", spec))?;
+    match parts.as_slice() {
+        [x, y, z] => Ok(Vector { x: *x, y: *y, z: *z }),
+        _ => Err(format!("invalid position '{}', expected X,Y,Z", spec)),
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<RenderSpec, String> {
+    let mut output = "render.wav".to_string();
+    let mut length = Duration::from_secs(5);
+    let mut rate = 48000;
+    let mut banks = Vec::new();
+    let mut events: Vec<RenderEvent> = Vec::new();
+
+    let mut iter = args.iter().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| format!("{} needs a value", flag))
+        };
+        match arg.as_str() {
+            "--out" => output = value("--out")?,
+            "--length" => length = parse_time(&value("--length")?)?,
+            "--rate" => {
+                rate = value("--rate")?
+                    .parse()
+                    .map_err(|_| "invalid --rate".to_string())?
+            }
+            "--bank" => banks.push(value("--bank")?),
+            "--param" => {
+                let spec = value("--param")?;
+                let event = events
+                    .pop()
+                    .ok_or_else(|| "--param must follow an event".to_string())?;
+                let (name, value, at) = parse_param(&spec, &event)?;
+                events.push(event.parameter(name, value, at));
+            }
+            "--pos" => {
+                let spec = value("--pos")?;
+                let event = events
+                    .pop()
+                    .ok_or_else(|| "--pos must follow an event".to_string())?;
+                events.push(event.position(parse_position(&spec)?));
+            }
+            flag if flag.starts_with("--") => return Err(format!("unknown option {}", flag)),
+            event => events.push(parse_event(event)?),
+        }
+    }
+
+    if events.is_empty() {
+        return Err("no events given".to_string());
+    }
+
+    let mut spec = RenderSpec::new(output, length).sample_rate(rate);
+    if banks.is_empty() {
+        spec = spec.example_banks(&DEFAULT_BANKS);
+    }
+    for bank in banks {
+        spec = spec.bank(bank);
+    }
+    for event in events {
+        spec = spec.event(event);
+    }
+    Ok(spec)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() < 2 || args.iter().any(|a| a == "--help" || a == "-h") {
+        print_usage(&args[0]);
+        return Ok(());
+    }
+
+    let spec = match parse_args(&args) {
+        Ok(spec) => spec,
+        Err(e) => {
+            println!("❌ {}\n", e);
+            print_usage(&args[0]);
+            std::process::exit(2);
+        }
+    };
+
+    println!("\n🎚️  FMOD Offline Render\n");
+    for event in &spec.events {
+        print!("  {} @ {:.2}s", event.path, event.start.as_secs_f32());
+        if let Some(stop) = event.stop {
+            print!(" → stop {:.2}s", stop.as_secs_f32());
+        }
+        println!();
+        for change in &event.parameters {
+            println!("    {:.2}s  {} = {}", change.at.as_secs_f32(), change.name, change.value);
+        }
+    }
+    println!();
+
+    let report = render(&spec)?;
+
+    println!("✅ Wrote {}", report.output.display());
+    println!("   {:.2}s, {} Hz, {} channel(s), {} frames",
+        report.duration().as_secs_f32(), report.sample_rate, report.channels, report.frames);
+    println!("   Rendered in {:.2}s ({} mixer updates, {:.1}x real time)",
+        report.elapsed.as_secs_f32(), report.updates,
+        report.duration().as_secs_f32() / report.elapsed.as_secs_f32().max(0.001));
+
+    Ok(())
+}
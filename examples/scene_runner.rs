@@ -0,0 +1,85 @@
+// Run declarative audio test scenes written in YAML, TOML or JSON
+// Run with: ./run_demos.sh scene_runner [--check] <scene.yaml|.toml|.json>... [--output ...]
+// Examples:
+//   ./run_demos.sh scene_runner scenes/drive_by.yaml                        # real time
+//   ./run_demos.sh scene_runner scenes/*.yaml --output nosound-nrt          # offline, no sound card
+//   ./run_demos.sh scene_runner scenes/drive_by.yaml --output wavwriter-nrt --output-file drive_by.wav
+//   ./run_demos.sh scene_runner --check scenes/*                            # validate only
+
+use libfmod_demos::{OutputOptions, Scene, run_scene};
+use std::path::Path;
+
+fn print_usage(program: &str) {
+    println!("Usage: {} [--check] <scene>... {}", program, OutputOptions::USAGE);
+    println!();
+    println!("Options:");
+    println!("  --check    Parse and validate the scenes without running them");
+    println!();
+    println!("Scenes are .yaml/.yml, .toml or .json files; see scenes/README.md for the format.");
+    println!("Realtime outputs play the scene in real time; nosound-nrt and wavwriter-nrt");
+    println!("run it offline, as fast as the mixer allows.");
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let (output, args) = OutputOptions::from_process_args()?;
+
+    if args.len() < 2 || args.iter().any(|a| a == "--help" || a == "-h") {
+        print_usage(&args[0]);
+        return Ok(());
+    }
+
+    let mut check_only = false;
+    let mut files = Vec::new();
+    for arg in args.iter().skip(1) {
+        match arg.as_str() {
+            "--check" => check_only = true,
+            flag if flag.starts_with("--") => {
+                eprintln!("❌ unknown option {}\n", flag);
+                print_usage(&args[0]);
+                std::process::exit(2);
+            }
+            file => files.push(file.to_string()),
+        }
+    }
+
+    // Load everything first so a broken file fails before anything plays
+    let mut scenes = Vec::new();
+    let mut failed = false;
+    for file in &files {
+        match Scene::load(Path::new(file)) {
+            Ok(scene) => scenes.push((file, scene)),
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+
+    for (file, scene) in &scenes {
+        let name = if scene.name.is_empty() { file.as_str() } else { scene.name.as_str() };
+        println!("\n🎬 {} ({:.1}s, {} events, {} snapshots)",
+                 name, scene.length, scene.events.len(), scene.snapshots.len());
+        if !scene.description.is_empty() {
+            println!("   {}", scene.description);
+        }
+        if check_only {
+            println!("   OK");
+            continue;
+        }
+
+        let base_dir = Path::new(file).parent().unwrap_or(Path::new("."));
+        let report = run_scene(scene, base_dir, &output, |cue| {
+            println!("   [{:>7.2}s] {}", cue.at.as_secs_f32(), cue.message);
+        })?;
+        println!("✅ {:.2}s of scene time in {:.2}s ({}, {} updates)",
+                 report.length.as_secs_f32(),
+                 report.elapsed.as_secs_f32(),
+                 report.mode,
+                 report.ticks);
+    }
+
+    Ok(())
+}
@@ -1,8 +1,8 @@
 // Test FMOD Studio bank loading and management with FMOD 2.03.09
 // Run with: ./run_fmod.sh studio_banks_test
 
-use libfmod::{Studio, StudioInit, Init, LoadBank};
-use libfmod_demos;
+use libfmod::LoadBank;
+use libfmod_demos::{OutputOptions, StudioSession};
 use std::path::Path;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -10,12 +10,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("====================================\n");
 
     // Initialize Studio System
-    print!("Creating Studio System... ");
-    let studio = Studio::create()?;
-    println!("✓");
-
-    print!("Initializing Studio... ");
-    studio.initialize(1024, StudioInit::NORMAL, Init::NORMAL, None)?;
+    // Banks are loaded by hand below, since loading them is what we test
+    print!("Creating and initializing Studio System... ");
+    let (output, _args) = OutputOptions::from_process_args()?;
+    let session = StudioSession::builder().output_options(&output).build()?;
+    let studio = session.studio();
     println!("✓\n");
 
     // Define bank paths - using FMOD SDK examples
@@ -128,7 +127,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Release Studio
-    studio.release()?;
+    session.close()?;
 
     println!("\n====================================");
     println!("✅ All bank tests completed!");
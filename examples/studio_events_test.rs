@@ -1,28 +1,56 @@
 // Test FMOD Studio event playback and sound variations with FMOD 2.03.09
 // Run with: ./run_fmod.sh studio_events_test
+// Exits with a non-zero status if any check fails (works with --output nosound)
 
-use libfmod::{Studio, StudioInit, Init, LoadBank, StopMode};
-use libfmod_demos;
-use std::thread;
+use libfmod::{PlaybackState, StopMode};
+use libfmod_demos::{InstanceRegistry, Meter, OutputOptions, StudioSession, TestReport, UpdateLoop, analysis::to_db};
 use std::time::Duration;
 
+/// Anything quieter than this on the master bus counts as "nothing heard"
+const AUDIBLE_DB: f32 = -60.0;
+
+/// Run the clock for `duration` and return the loudest master peak seen (linear)
+fn run_metered(clock: &mut UpdateLoop, meter: &Meter, duration: Duration) -> Result<f32, libfmod::Error> {
+    let mut loudest = 0.0f32;
+    clock.run_for_with(duration, |_| {
+        loudest = loudest.max(meter.read()?.max_peak());
+        Ok::<_, libfmod::Error>(())
+    })?;
+    Ok(loudest)
+}
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+fn audible(report: &mut TestReport, name: &str, peak: f32) -> bool {
+    let db = to_db(peak);
+    report.check(name, db > AUDIBLE_DB, format!("peak {:.1} dB", db))
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n🎵 FMOD Studio Events & Variations Test (2.03.09)\n");
     println!("=================================================\n");
 
-    // Initialize Studio System
-    let studio = Studio::create()?;
-    studio.initialize(1024, StudioInit::NORMAL, Init::NORMAL, None)?;
-
-    // Load banks
+    // Initialize Studio System and load banks
     println!("Loading banks...");
-    let bank_dir = libfmod_demos::get_example_banks_dir()?;
-
-    let master = studio.load_bank_file(&format!("{}/Master.bank", bank_dir), LoadBank::NORMAL)?;
-    let strings = studio.load_bank_file(&format!("{}/Master.strings.bank", bank_dir), LoadBank::NORMAL)?;
-    let sfx = studio.load_bank_file(&format!("{}/SFX.bank", bank_dir), LoadBank::NORMAL)?;
-    let vehicles = studio.load_bank_file(&format!("{}/Vehicles.bank", bank_dir), LoadBank::NORMAL)?;
-    let music = studio.load_bank_file(&format!("{}/Music.bank", bank_dir), LoadBank::NORMAL)?;
+    let (output, _args) = OutputOptions::from_process_args()?;
+    let session = StudioSession::builder()
+        .output_options(&output)
+        .example_banks(&[
+            "Master.bank",
+            "Master.strings.bank",
+            "SFX.bank",
+            "Vehicles.bank",
+            "Music.bank",
+        ])
+        .build()?;
+    let studio = session.studio();
+    let meter = Meter::master(&session.core()?)?;
+    let mut clock = UpdateLoop::for_output(studio, &output)?;
+    let mut report = TestReport::new();
+    // Every instance goes through the registry so leaks show up at the end
+    let mut registry = InstanceRegistry::new();
 
     println!("✓ Banks loaded\n");
 
@@ -31,19 +59,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("--------------------------------------");
 
     let explosion_desc = studio.get_event("event:/Weapons/Explosion")?;
+    report.check("explosion is one-shot", explosion_desc.is_oneshot()?, "is_oneshot");
     println!("Playing explosion (one-shot)...");
 
     for i in 1..=3 {
         println!("  Explosion #{}", i);
-        let explosion = explosion_desc.create_instance()?;
+        let explosion = registry.create(&explosion_desc, "TEST 1 explosion")?;
         explosion.start()?;
-        explosion.release()?; // Release immediately, sound continues
+        registry.release(&explosion)?; // Release immediately, sound continues
 
         // Update and wait
-        for _ in 0..20 {
-            studio.update()?;
-            thread::sleep(Duration::from_millis(50));
-        }
+        let peak = run_metered(&mut clock, &meter, ms(1000))?;
+        audible(&mut report, &format!("explosion #{} audible", i), peak);
     }
 
     // Test 2: Looping Ambient Event
@@ -51,27 +78,30 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("---------------------------------");
 
     let ambience_desc = studio.get_event("event:/Ambience/Country")?;
-    let ambience = ambience_desc.create_instance()?;
+    let ambience = registry.create(&ambience_desc, "TEST 2 ambience")?;
 
     println!("Starting ambient loop...");
     ambience.start()?;
 
+    let mut peak = 0.0f32;
     for i in 1..=3 {
         println!("  Playing... {} seconds", i);
-        for _ in 0..20 {
-            studio.update()?;
-            thread::sleep(Duration::from_millis(50));
-        }
+        peak = peak.max(run_metered(&mut clock, &meter, ms(1000))?);
     }
+    let state = ambience.get_playback_state()?;
+    report.check("ambience playing", state == PlaybackState::Playing, format!("{:?}", state));
+    audible(&mut report, "ambience audible", peak);
 
     println!("Stopping ambient with fadeout...");
     ambience.stop(StopMode::AllowFadeout)?;
 
-    for _ in 0..40 {
-        studio.update()?;
-        thread::sleep(Duration::from_millis(50));
-    }
-    ambience.release()?;
+    let fade_start = clock.time();
+    let stopped = clock.run_until(ms(2000), |_| {
+        Ok::<_, libfmod::Error>(ambience.get_playback_state()? == PlaybackState::Stopped)
+    })?;
+    let fade = clock.time() - fade_start;
+    report.check("ambience stopped after fadeout", stopped, format!("after {:.2}s", fade.as_secs_f32()));
+    registry.release(&ambience)?;
 
     // Test 3: Multiple Instances (Footsteps with variations)
     println!("\n👟 TEST 3: Sound Variations - Footsteps");
@@ -81,20 +111,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     println!("Playing footsteps (each plays different variation):");
 
+    let mut heard = 0;
     for step in 1..=8 {
         println!("  Step {}", step);
-        let footstep = footstep_desc.create_instance()?;
+        let footstep = registry.create(&footstep_desc, "TEST 3 footsteps")?;
 
         // Start the footstep
         footstep.start()?;
-        footstep.release()?;
+        registry.release(&footstep)?;
 
         // Short pause between steps
-        for _ in 0..5 {
-            studio.update()?;
-            thread::sleep(Duration::from_millis(50));
+        if to_db(run_metered(&mut clock, &meter, ms(250))?) > AUDIBLE_DB {
+            heard += 1;
         }
     }
+    report.check("all footsteps audible", heard == 8, format!("{}/8 heard", heard));
 
     // Test 4: UI Sounds (Cancel)
     println!("\n🔘 TEST 4: UI Sound - Cancel");
@@ -104,87 +135,95 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     for i in 1..=2 {
         println!("  Cancel sound #{}", i);
-        let cancel = cancel_desc.create_instance()?;
+        let cancel = registry.create(&cancel_desc, "TEST 4 UI")?;
         cancel.start()?;
-        cancel.release()?;
+        registry.release(&cancel)?;
 
-        for _ in 0..10 {
-            studio.update()?;
-            thread::sleep(Duration::from_millis(50));
-        }
+        let peak = run_metered(&mut clock, &meter, ms(500))?;
+        audible(&mut report, &format!("cancel #{} audible", i), peak);
     }
 
+    // Released one-shots must have cleaned themselves up by now
+    let leftover = explosion_desc.get_instance_count()? + cancel_desc.get_instance_count()?;
+    report.check("one-shot instances released", leftover == 0, format!("{} left", leftover));
+
     // Test 5: Vehicle Engine (continuous with variations)
     println!("\n🚜 TEST 5: Vehicle Engine");
     println!("-------------------------");
 
     let vehicle_desc = studio.get_event("event:/Vehicles/Ride-on Mower")?;
-    let vehicle = vehicle_desc.create_instance()?;
+    let vehicle = registry.create(&vehicle_desc, "TEST 5 engine")?;
 
     println!("Starting engine...");
     vehicle.start()?;
 
     println!("Running for 3 seconds...");
-    for _ in 0..60 {
-        studio.update()?;
-        thread::sleep(Duration::from_millis(50));
-    }
+    run_metered(&mut clock, &meter, ms(3000))?;
+    let state = vehicle.get_playback_state()?;
+    report.check("engine playing", state == PlaybackState::Playing, format!("{:?}", state));
 
     println!("Stopping engine...");
     vehicle.stop(StopMode::AllowFadeout)?;
 
-    for _ in 0..20 {
-        studio.update()?;
-        thread::sleep(Duration::from_millis(50));
-    }
-    vehicle.release()?;
+    run_metered(&mut clock, &meter, ms(1000))?;
+    let state = vehicle.get_playback_state()?;
+    report.check(
+        "engine stopping",
+        matches!(state, PlaybackState::Stopping | PlaybackState::Stopped),
+        format!("{:?}", state),
+    );
+    registry.release(&vehicle)?;
 
     // Test 6: Music Track
     println!("\n🎵 TEST 6: Music Playback");
     println!("-------------------------");
 
-    if let Ok(music_desc) = studio.get_event("event:/Music/Level 01") {
-        let music_inst = music_desc.create_instance()?;
+    let music_desc = studio.get_event("event:/Music/Level 01");
+    report.check("music event found", music_desc.is_ok(), "event:/Music/Level 01");
+    if let Ok(music_desc) = music_desc {
+        let music_inst = registry.create(&music_desc, "TEST 6 music")?;
 
         println!("Starting music...");
         music_inst.start()?;
 
         println!("Playing for 5 seconds...");
+        let mut peak = 0.0f32;
         for i in 1..=5 {
             println!("  {} seconds...", i);
-            for _ in 0..20 {
-                studio.update()?;
-                thread::sleep(Duration::from_millis(50));
-            }
+            peak = peak.max(run_metered(&mut clock, &meter, ms(1000))?);
         }
+        let state = music_inst.get_playback_state()?;
+        report.check("music playing", state == PlaybackState::Playing, format!("{:?}", state));
+        audible(&mut report, "music audible", peak);
 
         println!("Fading out music...");
         music_inst.stop(StopMode::AllowFadeout)?;
 
-        for _ in 0..40 {
-            studio.update()?;
-            thread::sleep(Duration::from_millis(50));
-        }
-        music_inst.release()?;
+        run_metered(&mut clock, &meter, ms(2000))?;
+        registry.release(&music_inst)?;
+    }
+
+    // Nothing may be left unreleased or hanging on a sustain point
+    println!("\n🔎 Instance lifecycle");
+    println!("---------------------");
+    clock.run_for(ms(500))?;
+    registry.update();
+    for (path, counts) in registry.counts() {
+        println!("  {}: {} live ({} released, still stopping)", path, counts.live, counts.released);
+    }
+    let leaks = registry.leaks();
+    for leak in &leaks {
+        println!("  ⚠️  {}", leak);
     }
+    report.check("no leaked instances", leaks.is_empty(), format!("{} leaked", leaks.len()));
 
     // Clean up
     println!("\nCleaning up...");
-    music.unload()?;
-    vehicles.unload()?;
-    sfx.unload()?;
-    strings.unload()?;
-    master.unload()?;
-    studio.release()?;
+    session.close()?;
 
     println!("\n=================================================");
-    println!("✅ All event tests completed!");
-    println!("   - One-shot events work");
-    println!("   - Looping sounds work");
-    println!("   - Sound variations play correctly");
-    println!("   - Multiple instances work");
-    println!("   - Music playback works");
+    report.finish()?;
     println!("=================================================\n");
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,25 +1,28 @@
 // Test FMOD Studio real-time parameter control with FMOD 2.03.09
 // Run with: ./run_fmod.sh studio_parameters_test
+// Exits with a non-zero status if any check fails (works with --output nosound)
 
-use libfmod::{Studio, StudioInit, Init, LoadBank, StopMode};
-use libfmod_demos;
-use std::thread;
+use libfmod::StopMode;
+use libfmod_demos::{
+    Curve, GlobalParameter, Interpolation, Meter, OutputOptions, ParameterError, ParameterTarget, ParameterValue,
+    StudioSession, TestReport, UpdateLoop, set_global_parameter, set_parameter,
+};
 use std::time::Duration;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n🎛️  FMOD Studio Parameters Test (2.03.09)\n");
     println!("==========================================\n");
 
-    // Initialize Studio System
-    let studio = Studio::create()?;
-    studio.initialize(1024, StudioInit::NORMAL, Init::NORMAL, None)?;
-
-    // Load banks
-    let bank_dir = libfmod_demos::get_example_banks_dir()?;
-    let master = studio.load_bank_file(&format!("{}/Master.bank", bank_dir), LoadBank::NORMAL)?;
-    let strings = studio.load_bank_file(&format!("{}/Master.strings.bank", bank_dir), LoadBank::NORMAL)?;
-    let sfx = studio.load_bank_file(&format!("{}/SFX.bank", bank_dir), LoadBank::NORMAL)?;
-    let vehicles = studio.load_bank_file(&format!("{}/Vehicles.bank", bank_dir), LoadBank::NORMAL)?;
+    // Initialize Studio System and load banks
+    let (output, _args) = OutputOptions::from_process_args()?;
+    let session = StudioSession::builder()
+        .output_options(&output)
+        .example_banks(&["Master.bank", "Master.strings.bank", "SFX.bank", "Vehicles.bank"])
+        .build()?;
+    let studio = session.studio();
+    let meter = Meter::master(&session.core()?)?;
+    let mut clock = UpdateLoop::for_output(studio, &output)?;
+    let mut report = TestReport::new();
 
     println!("Banks loaded ✓\n");
 
@@ -32,6 +35,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Note: Parameter descriptions are on the Studio System level in FMOD 2.03
     println!("Note: Events use global and local parameters");
+    let rpm_desc = vehicle_desc.get_parameter_description_by_name("RPM")?;
+    println!("RPM range: {:.0} - {:.0}", rpm_desc.minimum, rpm_desc.maximum);
 
     println!("\nStarting engine...");
     vehicle.start()?;
@@ -45,39 +50,41 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         println!("  RPM: {:.0}", rpm);
 
-        // Try setting by name (common parameter names)
-        vehicle.set_parameter_by_name("RPM", rpm, false).ok();
-        vehicle.set_parameter_by_name("rpm", rpm, false).ok();
-        vehicle.set_parameter_by_name("EngineRPM", rpm, false).ok();
+        report.check_ok(format!("set RPM {:.0}", rpm), set_parameter(&vehicle, "RPM", rpm));
 
         // Let it play at this RPM
-        for _ in 0..20 {
-            studio.update()?;
-            thread::sleep(Duration::from_millis(50));
-        }
+        let mut peak = 0.0f32;
+        clock.run_for_with(Duration::from_millis(1000), |_| {
+            peak = peak.max(meter.read()?.max_peak());
+            Ok::<_, libfmod::Error>(())
+        })?;
+
+        // Values outside the parameter's range are clamped by FMOD
+        let (value, _final_value) = vehicle.get_parameter_by_name("RPM")?;
+        let expected = rpm.clamp(rpm_desc.minimum, rpm_desc.maximum);
+        report.check_close(format!("RPM {:.0} applied", rpm), value, expected, 0.5);
+        report.check(format!("engine audible at RPM {:.0}", rpm), peak > 0.001, format!("peak {:.4}", peak));
     }
 
-    println!("Decreasing RPM...");
-    for rpm_stage in (0..=2).rev() {
-        let rpm = rpm_stage as f32 * 1000.0;
-        println!("  RPM: {:.0}", rpm);
-
-        vehicle.set_parameter_by_name("RPM", rpm, false).ok();
-        vehicle.set_parameter_by_name("rpm", rpm, false).ok();
-
-        for _ in 0..15 {
-            studio.update()?;
-            thread::sleep(Duration::from_millis(50));
-        }
-    }
+    // Automated ramp back down: the value read back each tick must follow the curve
+    println!("Ramping RPM down to idle...");
+    let ramp_down = Curve::ramp(rpm_desc.maximum.min(2000.0), 0.0, Duration::from_secs(2), Interpolation::Linear);
+    let ramp_start = clock.time();
+    clock.automate(ParameterTarget::Instance(vehicle), "RPM", ramp_down.clone())?;
+    let mut worst = 0.0f32;
+    clock.run_for_with(Duration::from_millis(2500), |tick| {
+        let (value, _final_value) = vehicle.get_parameter_by_name("RPM")?;
+        worst = worst.max((value - ramp_down.value_at(tick.time - ramp_start)).abs());
+        Ok::<_, libfmod::Error>(())
+    })?;
+    report.check("RPM follows automated ramp", worst <= 0.5, format!("worst error {:.2}", worst));
+    let (value, _final_value) = vehicle.get_parameter_by_name("RPM")?;
+    report.check_close("RPM ramp ends at idle", value, 0.0, 0.5);
 
     println!("Stopping engine...");
     vehicle.stop(StopMode::AllowFadeout)?;
 
-    for _ in 0..30 {
-        studio.update()?;
-        thread::sleep(Duration::from_millis(50));
-    }
+    clock.run_for(Duration::from_millis(1500))?;
     vehicle.release()?;
 
     // Test 2: Footsteps with Surface Parameter
@@ -105,19 +112,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             let footstep = footstep_desc.create_instance()?;
 
-            // Set surface parameter
-            footstep.set_parameter_by_name("Surface", value, false).ok();
-            footstep.set_parameter_by_name("surface", value, false).ok();
-            footstep.set_parameter_by_name("Material", value, false).ok();
+            report.check_ok(format!("{} step {} set Surface", name, step), set_parameter(&footstep, "Surface", value));
+
+            let (applied, _final_value) = footstep.get_parameter_by_name("Surface")?;
+            report.check_close(format!("{} step {} surface", name, step), applied, value, 0.001);
 
             footstep.start()?;
             footstep.release()?;
 
             // Pause between steps
-            for _ in 0..6 {
-                studio.update()?;
-                thread::sleep(Duration::from_millis(50));
-            }
+            clock.run_for(Duration::from_millis(300))?;
         }
     }
 
@@ -144,11 +148,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     for (volume, label) in volumes {
         println!("  Volume: {}", label);
         ambience.set_volume(volume)?;
-
-        for _ in 0..15 {
-            studio.update()?;
-            thread::sleep(Duration::from_millis(50));
-        }
+        let (applied, _final_volume) = ambience.get_volume()?;
+        report.check_close(format!("volume {}", label), applied, volume, 0.001);
+
+        let mut peak = 0.0f32;
+        clock.run_for_with(Duration::from_millis(750), |_| {
+            peak = peak.max(meter.read()?.max_peak());
+            Ok::<_, libfmod::Error>(())
+        })?;
+        report.check(format!("ambience audible at {}", label), peak > 0.001, format!("peak {:.4}", peak));
     }
 
     // Pitch control
@@ -163,55 +171,84 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     for (pitch, label) in pitches {
         println!("  Pitch: {}", label);
         ambience.set_pitch(pitch)?;
+        let (applied, _final_pitch) = ambience.get_pitch()?;
+        report.check_close(format!("pitch {}", label), applied, pitch, 0.001);
 
-        for _ in 0..20 {
-            studio.update()?;
-            thread::sleep(Duration::from_millis(50));
-        }
+        clock.run_for(Duration::from_millis(1000))?;
     }
 
     println!("\nStopping ambient...");
     ambience.stop(StopMode::AllowFadeout)?;
 
-    for _ in 0..20 {
-        studio.update()?;
-        thread::sleep(Duration::from_millis(50));
-    }
+    clock.run_for(Duration::from_millis(1000))?;
     ambience.release()?;
 
     // Test 4: Global Parameter (if available)
     println!("\n🌍 TEST 4: Global Parameters");
     println!("-----------------------------");
 
-    // Try to set a global parameter (affects all events)
-    println!("Setting global parameters:");
-
-    studio.set_parameter_by_name("TimeOfDay", 0.0, false).ok();
-    println!("  TimeOfDay = Morning");
+    // Every global parameter the banks define, with the values FMOD holds
+    let globals = GlobalParameter::list(studio)?;
+    if globals.is_empty() {
+        println!("  (the loaded banks define no global parameters)");
+    }
+    for mut global in globals {
+        println!("  {} [{}] = {:.2} (final {:.2}){}",
+                 global.info.name,
+                 global.id_string(),
+                 global.value,
+                 global.final_value,
+                 global.label().map(|label| format!(" \"{}\"", label)).unwrap_or_default());
+        if !global.info.is_settable() {
+            continue;
+        }
 
-    studio.set_parameter_by_name("Weather", 1.0, false).ok();
-    println!("  Weather = Rainy");
+        // Labeled globals are set by their last label, the rest to mid-range
+        let name = global.info.name.clone();
+        match global.info.labels.last().cloned() {
+            Some(label) => {
+                let set = global.set(studio, &ParameterValue::Label(label.clone()));
+                if report.check_ok(format!("set global {} to \"{}\"", name, label), set).is_some() {
+                    report.check(format!("global {} label applied", name), global.label() == Some(label.as_str()),
+                                 format!("now {:?}", global.label()));
+                }
+            }
+            None => {
+                let value = (global.info.minimum + global.info.maximum) / 2.0;
+                if report.check_ok(format!("set global {}", name), global.set(studio, &ParameterValue::Number(value))).is_some() {
+                    report.check_close(format!("global {} applied", name), global.value, value, 0.001);
+                }
+            }
+        }
+    }
 
-    studio.set_parameter_by_name("Tension", 0.5, false).ok();
-    println!("  Tension = Medium");
+    // Misspelled or missing names must be rejected, not silently ignored
+    println!("\nRejecting unknown parameter names:");
+    let vehicle = vehicle_desc.create_instance()?;
+    let misspelled = set_parameter(&vehicle, "RMP", 1000.0);
+    vehicle.release()?;
+    let suggested = matches!(&misspelled, Err(ParameterError::Unknown { suggestions, .. })
+                             if suggestions.iter().any(|s| s == "RPM"));
+    report.check("misspelled \"RMP\" rejected, suggesting RPM", suggested, outcome(&misspelled));
 
-    // These would affect any playing events that use these parameters
+    let missing = set_global_parameter(studio, "TimeOfDay", 0.0);
+    let rejected = matches!(missing, Err(ParameterError::Unknown { .. }));
+    report.check("missing global \"TimeOfDay\" rejected", rejected, outcome(&missing));
 
     // Clean up
     println!("\nCleaning up...");
-    vehicles.unload()?;
-    sfx.unload()?;
-    strings.unload()?;
-    master.unload()?;
-    studio.release()?;
+    session.close()?;
 
     println!("\n==========================================");
-    println!("✅ Parameter tests completed!");
-    println!("   - Engine RPM control tested");
-    println!("   - Surface parameters tested");
-    println!("   - Volume/Pitch control works");
-    println!("   - Global parameters set");
+    report.finish()?;
     println!("==========================================\n");
 
     Ok(())
-}
\ No newline at end of file
+}
+
+fn outcome<T>(result: &Result<T, ParameterError>) -> String {
+    match result {
+        Ok(_) => "accepted".to_string(),
+        Err(e) => e.to_string(),
+    }
+}
@@ -2,10 +2,25 @@
 // Run with: cargo run --example verify_203
 
 use libfmod::{System, Init};
+use libfmod_demos::OutputOptions;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let (output, _args) = OutputOptions::from_process_args()?;
 
-fn main() -> Result<(), libfmod::Error> {
     println!("\n🎵 FMOD 2.03.09 Verification Test\n");
 
+    // Check the SDK layout before touching the libraries
+    match libfmod_demos::FmodSdk::locate() {
+        Ok(sdk) => {
+            println!("✅ FMOD SDK: {} (from {})", sdk.root().display(), sdk.source());
+            match sdk.version() {
+                Some(version) => println!("✅ SDK Version: {}", version),
+                None => println!("⚠️  SDK Version: unknown (fmod_common.h not found)"),
+            }
+        }
+        Err(e) => println!("⚠️  {}", e),
+    }
+
     // Create and verify version
     let system = System::create()?;
     let (version, build) = system.get_version()?;
@@ -18,8 +33,8 @@ fn main() -> Result<(), libfmod::Error> {
     println!("✅ Build Number: {}", build);
 
     // Initialize
-    system.init(512, Init::NORMAL, None)?;
-    println!("✅ System initialized");
+    output.init_system(&system, 512, Init::NORMAL)?;
+    println!("✅ System initialized (output: {})", output.mode);
 
     // Clean shutdown
     system.release()?;
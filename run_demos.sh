@@ -79,10 +79,14 @@ if [ -z "$EXAMPLE" ]; then
     echo
     echo "Available examples:"
     echo "  verify_fmod         - Verify FMOD is working correctly"
-    echo "  play_sound <file>   - Play an audio file"
+    echo "  play_sound <file> [--fx ...] - Play an audio file through optional DSP effects"
     echo "  harness_demo        - Non-interactive FMOD feature demos"
     echo "  interactive_harness - Interactive 3D audio testing"
     echo "  quick_test          - Run comprehensive test suite"
+    echo "  render_event <event> - Render events offline to a WAV file"
+    echo "  golden_audio_test   - Compare offline renders to reference WAVs"
+    echo "  bank_inspect [banks] - List events, parameters, buses and VCAs in banks"
+    echo "  scene_runner <scene> - Run YAML/TOML/JSON test scenes (real time or offline)"
     echo
     echo "Examples:"
     echo "  ./run_demos.sh verify_fmod"
@@ -0,0 +1,213 @@
+use std::f32::consts::PI;
+use std::fmt;
+
+use crate::wav::WavFile;
+
+/// Levels below this are treated as silence when converting to decibels
+pub const SILENCE_DB: f32 = -120.0;
+
+/// Frame size used for the spectral analysis
+const FFT_SIZE: usize = 2048;
+
+/// Summary statistics used to compare renders
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioStats {
+    pub rms: f32,
+    pub peak: f32,
+    /// Magnitude-weighted mean frequency of the whole file, in Hz
+    pub spectral_centroid: f32,
+    pub duration_secs: f64,
+}
+
+impl AudioStats {
+    /// Analyze all channels of a WAV file
+    ///
+    /// RMS and peak are taken over every sample; the spectral centroid is
+    /// computed on the mono mix using Hann-windowed, half-overlapping frames.
+    pub fn of(wav: &WavFile) -> Self {
+        let samples = &wav.samples;
+        let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        let rms = if samples.is_empty() {
+            0.0
+        } else {
+            (samples.iter().map(|s| (*s as f64) * (*s as f64)).sum::<f64>() / samples.len() as f64).sqrt() as f32
+        };
+
+        AudioStats {
+            rms,
+            peak,
+            spectral_centroid: spectral_centroid(&wav.mono_mix(), wav.sample_rate),
+            duration_secs: wav.duration_secs(),
+        }
+    }
+
+    pub fn rms_db(&self) -> f32 {
+        to_db(self.rms)
+    }
+
+    pub fn peak_db(&self) -> f32 {
+        to_db(self.peak)
+    }
+}
+
+impl fmt::Display for AudioStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "RMS {:6.1} dB  Peak {:6.1} dB  Centroid {:7.0} Hz  Length {:.3}s",
+            self.rms_db(),
+            self.peak_db(),
+            self.spectral_centroid,
+            self.duration_secs
+        )
+    }
+}
+
+/// Linear amplitude to decibels, floored at [`SILENCE_DB`]
+pub fn to_db(level: f32) -> f32 {
+    if level <= 0.0 {
+        SILENCE_DB
+    } else {
+        (20.0 * level.log10()).max(SILENCE_DB)
+    }
+}
+
+/// Magnitude-weighted mean frequency of a mono signal (0 for silence)
+pub fn spectral_centroid(samples: &[f32], sample_rate: u32) -> f32 {
+    let spectrum = average_spectrum(samples);
+    let bin_hz = sample_rate as f32 / FFT_SIZE as f32;
+
+    let (weighted, total) = spectrum
+        .iter()
+        .enumerate()
+        .fold((0.0f64, 0.0f64), |(weighted, total), (bin, magnitude)| {
+            let magnitude = *magnitude as f64;
+            (weighted + bin as f64 * bin_hz as f64 * magnitude, total + magnitude)
+        });
+
+    if total <= f64::EPSILON {
+        0.0
+    } else {
+        (weighted / total) as f32
+    }
+}
+
+/// Mean magnitude spectrum (`FFT_SIZE / 2` bins) over Hann-windowed frames
+pub fn average_spectrum(samples: &[f32]) -> Vec<f32> {
+    let hop = FFT_SIZE / 2;
+    let window: Vec<f32> = (0..FFT_SIZE)
+        .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / FFT_SIZE as f32).cos())
+        .collect();
+
+    let mut sum = vec![0.0f32; FFT_SIZE / 2];
+    let mut frames = 0;
+    let mut start = 0;
+    while start < samples.len() {
+        let mut re: Vec<f32> = (0..FFT_SIZE)
+            .map(|i| samples.get(start + i).copied().unwrap_or(0.0) * window[i])
+            .collect();
+        let mut im = vec![0.0f32; FFT_SIZE];
+        fft(&mut re, &mut im);
+        for (bin, total) in sum.iter_mut().enumerate() {
+            *total += (re[bin] * re[bin] + im[bin] * im[bin]).sqrt();
+        }
+        frames += 1;
+        start += hop;
+    }
+
+    if frames > 0 {
+        for total in &mut sum {
+            *total /= frames as f32;
+        }
+    }
+    sum
+}
+
+/// In-place iterative radix-2 FFT; `re.len()` must be a power of two
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    debug_assert!(n.is_power_of_two() && im.len() == n);
+
+    // Bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f32;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let a = start + k;
+                let b = a + len / 2;
+                let tr = re[b] * cos - im[b] * sin;
+                let ti = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - tr;
+                im[b] = im[a] - ti;
+                re[a] += tr;
+                im[a] += ti;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wav::SampleFormat;
+
+    fn tone(frequency: f32, amplitude: f32, sample_rate: u32, seconds: f32) -> WavFile {
+        let count = (sample_rate as f32 * seconds) as usize;
+        WavFile {
+            sample_rate,
+            channels: 1,
+            format: SampleFormat::Float32,
+            samples: (0..count)
+                .map(|i| amplitude * (2.0 * PI * frequency * i as f32 / sample_rate as f32).sin())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn sine_levels() {
+        let stats = AudioStats::of(&tone(1000.0, 0.5, 48000, 1.0));
+        assert!((stats.peak - 0.5).abs() < 1e-3);
+        assert!((stats.rms - 0.5 / 2f32.sqrt()).abs() < 1e-3);
+        assert!((stats.duration_secs - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn centroid_of_a_sine_is_its_frequency() {
+        for frequency in [440.0, 1000.0, 5000.0] {
+            let centroid = spectral_centroid(&tone(frequency, 0.5, 48000, 0.5).samples, 48000);
+            assert!((centroid - frequency).abs() < frequency * 0.05, "{} Hz: centroid {}", frequency, centroid);
+        }
+    }
+
+    #[test]
+    fn silence() {
+        let stats = AudioStats::of(&tone(1000.0, 0.0, 48000, 0.1));
+        assert_eq!(stats.rms_db(), SILENCE_DB);
+        assert_eq!(stats.peak_db(), SILENCE_DB);
+        assert_eq!(stats.spectral_centroid, 0.0);
+    }
+
+    #[test]
+    fn decibels() {
+        assert!(to_db(1.0).abs() < 1e-6);
+        assert!((to_db(0.5) + 6.0206).abs() < 1e-3);
+        assert_eq!(to_db(1e-9), SILENCE_DB);
+    }
+}
@@ -0,0 +1,360 @@
+use libfmod::{EventInstance, Studio};
+use serde::{Deserialize, Serialize};
+use std::{f32::consts::TAU, time::Duration};
+
+/// How a keyframe's value moves towards the next keyframe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Interpolation {
+    #[default]
+    Linear,
+    /// Slow start and slow end (smoothstep)
+    #[serde(alias = "ease-in-out")]
+    Ease,
+    /// Hold the value, then jump at the next keyframe
+    Step,
+    /// Equal ratios in equal times, which sounds even for frequencies and
+    /// gains; linear when either value is not above zero
+    Exponential,
+}
+
+impl Interpolation {
+    /// Value `fraction` (0.0 to 1.0) of the way from `from` to `to`
+    pub fn blend(self, from: f32, to: f32, fraction: f32) -> f32 {
+        let t = fraction.clamp(0.0, 1.0);
+        match self {
+            Interpolation::Linear => from + (to - from) * t,
+            Interpolation::Ease => from + (to - from) * t * t * (3.0 - 2.0 * t),
+            Interpolation::Step if t < 1.0 => from,
+            Interpolation::Step => to,
+            Interpolation::Exponential if from > 0.0 && to > 0.0 => from * (to / from).powf(t),
+            Interpolation::Exponential => from + (to - from) * t,
+        }
+    }
+}
+
+/// A value at a point in time, and how to get from it to the next keyframe
+///
+/// In scene files a keyframe is `[seconds, value]`,
+/// `[seconds, value, interpolation]` or `{ time, value, interpolation }`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "KeyframeRepr")]
+pub struct Keyframe {
+    /// Seconds from the start of the curve
+    pub time: f32,
+    pub value: f32,
+    pub interpolation: Interpolation,
+}
+
+impl Keyframe {
+    pub fn new(time: Duration, value: f32, interpolation: Interpolation) -> Self {
+        Keyframe {
+            time: time.as_secs_f32(),
+            value,
+            interpolation,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeyframeRepr {
+    Pair(f32, f32),
+    Triple(f32, f32, Interpolation),
+    Map {
+        time: f32,
+        value: f32,
+        #[serde(default)]
+        interpolation: Interpolation,
+    },
+}
+
+impl From<KeyframeRepr> for Keyframe {
+    fn from(repr: KeyframeRepr) -> Self {
+        let (time, value, interpolation) = match repr {
+            KeyframeRepr::Pair(time, value) => (time, value, Interpolation::Linear),
+            KeyframeRepr::Triple(time, value, interpolation) => (time, value, interpolation),
+            KeyframeRepr::Map { time, value, interpolation } => (time, value, interpolation),
+        };
+        Keyframe { time, value, interpolation }
+    }
+}
+
+/// LFO waveforms, all starting at the centre value heading up (except square)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Waveform {
+    #[default]
+    Sine,
+    Triangle,
+    Square,
+    /// Rising ramp from `center - depth` to `center + depth`
+    Saw,
+}
+
+/// A low-frequency oscillator: `center` plus or minus `depth`, `rate` times per second
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Lfo {
+    pub center: f32,
+    pub depth: f32,
+    /// Cycles per second
+    pub rate: f32,
+    #[serde(default)]
+    pub waveform: Waveform,
+    /// Starting point within the cycle, 0.0 to 1.0
+    #[serde(default)]
+    pub phase: f32,
+}
+
+impl Lfo {
+    pub fn value_at(&self, time: Duration) -> f32 {
+        let cycle = (time.as_secs_f32() * self.rate + self.phase).rem_euclid(1.0);
+        let wave = match self.waveform {
+            Waveform::Sine => (TAU * cycle).sin(),
+            Waveform::Triangle => {
+                // 0 -> 1 -> -1 -> 0 over one cycle
+                if cycle < 0.25 {
+                    4.0 * cycle
+                } else if cycle < 0.75 {
+                    2.0 - 4.0 * cycle
+                } else {
+                    4.0 * cycle - 4.0
+                }
+            }
+            Waveform::Square if cycle < 0.5 => 1.0,
+            Waveform::Square => -1.0,
+            Waveform::Saw => 2.0 * cycle - 1.0,
+        };
+        self.center + self.depth * wave
+    }
+}
+
+/// A parameter value over time
+///
+/// In scene files: a number (constant), a list of keyframes, or an LFO
+/// table such as `{ center: 1000, depth: 500, rate: 0.5, waveform: sine }`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Curve {
+    Constant(f32),
+    /// Held before the first keyframe and after the last
+    Keyframes(Vec<Keyframe>),
+    Lfo(Lfo),
+}
+
+impl Curve {
+    pub fn constant(value: f32) -> Self {
+        Curve::Constant(value)
+    }
+
+    /// From `from` to `to` over `duration`, then hold
+    pub fn ramp(from: f32, to: f32, duration: Duration, interpolation: Interpolation) -> Self {
+        Curve::Keyframes(vec![
+            Keyframe::new(Duration::ZERO, from, interpolation),
+            Keyframe::new(duration, to, interpolation),
+        ])
+    }
+
+    /// Keyframes from `(time, value)` pairs, all with the same interpolation
+    pub fn keyframes(points: &[(Duration, f32)], interpolation: Interpolation) -> Self {
+        Curve::Keyframes(
+            points
+                .iter()
+                .map(|&(time, value)| Keyframe::new(time, value, interpolation))
+                .collect(),
+        )
+    }
+
+    /// Sine LFO around `center`
+    pub fn lfo(center: f32, depth: f32, rate: f32) -> Self {
+        Curve::Lfo(Lfo {
+            center,
+            depth,
+            rate,
+            waveform: Waveform::Sine,
+            phase: 0.0,
+        })
+    }
+
+    pub fn value_at(&self, time: Duration) -> f32 {
+        let keyframes = match self {
+            Curve::Constant(value) => return *value,
+            Curve::Lfo(lfo) => return lfo.value_at(time),
+            Curve::Keyframes(keyframes) => keyframes,
+        };
+        let seconds = time.as_secs_f32();
+        let Some(first) = keyframes.first() else {
+            return 0.0;
+        };
+        if seconds <= first.time {
+            return first.value;
+        }
+        for pair in keyframes.windows(2) {
+            let (from, to) = (&pair[0], &pair[1]);
+            if seconds < to.time {
+                let span = to.time - from.time;
+                let fraction = if span > 0.0 { (seconds - from.time) / span } else { 1.0 };
+                return from.interpolation.blend(from.value, to.value, fraction);
+            }
+        }
+        keyframes[keyframes.len() - 1].value
+    }
+
+    /// When the value stops changing; `None` for an LFO, which never does
+    pub fn end(&self) -> Option<Duration> {
+        match self {
+            Curve::Constant(_) => Some(Duration::ZERO),
+            // Times past `Duration::MAX` saturate instead of panicking
+            Curve::Keyframes(keyframes) => Some(
+                Duration::try_from_secs_f32(keyframes.last().map_or(0.0, |keyframe| keyframe.time.max(0.0)))
+                    .unwrap_or(Duration::MAX),
+            ),
+            Curve::Lfo(_) => None,
+        }
+    }
+
+    /// Check for empty, out-of-order or non-finite keyframes and a bad LFO rate
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Curve::Constant(_) => Ok(()),
+            Curve::Keyframes(keyframes) if keyframes.is_empty() => Err("no keyframes".to_string()),
+            Curve::Keyframes(keyframes) if keyframes.iter().any(|k| !k.time.is_finite()) => {
+                Err("keyframe times must be finite numbers".to_string())
+            }
+            Curve::Keyframes(keyframes) if keyframes.windows(2).any(|pair| pair[1].time < pair[0].time) => {
+                Err("keyframe times must not go backwards".to_string())
+            }
+            Curve::Keyframes(_) => Ok(()),
+            Curve::Lfo(lfo) if !lfo.rate.is_finite() || lfo.rate < 0.0 => {
+                Err("LFO rate must be a finite number, not negative".to_string())
+            }
+            Curve::Lfo(_) => Ok(()),
+        }
+    }
+}
+
+/// Where an automated parameter lives
+#[derive(Debug, Clone, Copy)]
+pub enum ParameterTarget {
+    Instance(EventInstance),
+    /// A Studio-level (global) parameter
+    Global,
+}
+
+impl ParameterTarget {
+    pub fn set(&self, studio: &Studio, name: &str, value: f32) -> Result<(), libfmod::Error> {
+        match self {
+            ParameterTarget::Instance(instance) => instance.set_parameter_by_name(name, value, false),
+            ParameterTarget::Global => studio.set_parameter_by_name(name, value, false),
+        }
+    }
+
+    /// False once the instance has been released
+    pub fn is_valid(&self) -> bool {
+        match self {
+            ParameterTarget::Instance(instance) => instance.is_valid(),
+            ParameterTarget::Global => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(seconds: f32) -> Duration {
+        Duration::from_secs_f32(seconds)
+    }
+
+    #[test]
+    fn interpolations_hit_both_endpoints() {
+        for interpolation in [
+            Interpolation::Linear,
+            Interpolation::Ease,
+            Interpolation::Step,
+            Interpolation::Exponential,
+        ] {
+            assert_eq!(interpolation.blend(100.0, 1000.0, 0.0), 100.0, "{:?}", interpolation);
+            assert!((interpolation.blend(100.0, 1000.0, 1.0) - 1000.0).abs() < 1e-3, "{:?}", interpolation);
+        }
+    }
+
+    #[test]
+    fn interpolation_midpoints() {
+        assert_eq!(Interpolation::Linear.blend(0.0, 10.0, 0.5), 5.0);
+        assert_eq!(Interpolation::Ease.blend(0.0, 10.0, 0.5), 5.0);
+        assert!(Interpolation::Ease.blend(0.0, 10.0, 0.1) < 1.0);
+        assert_eq!(Interpolation::Step.blend(0.0, 10.0, 0.99), 0.0);
+        assert!((Interpolation::Exponential.blend(100.0, 10000.0, 0.5) - 1000.0).abs() < 0.1);
+        // Falls back to linear through zero
+        assert_eq!(Interpolation::Exponential.blend(0.0, 10.0, 0.5), 5.0);
+    }
+
+    #[test]
+    fn keyframes_hold_outside_their_range() {
+        let curve = Curve::keyframes(&[(secs(1.0), 10.0), (secs(3.0), 30.0)], Interpolation::Linear);
+        assert_eq!(curve.value_at(Duration::ZERO), 10.0);
+        assert_eq!(curve.value_at(secs(1.0)), 10.0);
+        assert_eq!(curve.value_at(secs(2.0)), 20.0);
+        assert_eq!(curve.value_at(secs(3.0)), 30.0);
+        assert_eq!(curve.value_at(secs(10.0)), 30.0);
+        assert_eq!(curve.end(), Some(secs(3.0)));
+        let far = Curve::Keyframes(vec![Keyframe { time: 1e30, value: 1.0, interpolation: Interpolation::Linear }]);
+        assert_eq!(far.end(), Some(Duration::MAX));
+    }
+
+    #[test]
+    fn keyframe_uses_its_own_interpolation() {
+        let curve = Curve::Keyframes(vec![
+            Keyframe::new(Duration::ZERO, 0.0, Interpolation::Step),
+            Keyframe::new(secs(1.0), 1.0, Interpolation::Linear),
+            Keyframe::new(secs(2.0), 3.0, Interpolation::Linear),
+        ]);
+        assert_eq!(curve.value_at(secs(0.5)), 0.0);
+        assert_eq!(curve.value_at(secs(1.5)), 2.0);
+    }
+
+    #[test]
+    fn lfo_waveforms() {
+        let mut lfo = Lfo {
+            center: 10.0,
+            depth: 2.0,
+            rate: 1.0,
+            waveform: Waveform::Sine,
+            phase: 0.0,
+        };
+        assert!((lfo.value_at(Duration::ZERO) - 10.0).abs() < 1e-4);
+        assert!((lfo.value_at(secs(0.25)) - 12.0).abs() < 1e-4);
+        lfo.waveform = Waveform::Triangle;
+        assert!((lfo.value_at(secs(0.75)) - 8.0).abs() < 1e-4);
+        lfo.waveform = Waveform::Square;
+        assert_eq!(lfo.value_at(secs(0.6)), 8.0);
+        lfo.waveform = Waveform::Saw;
+        assert_eq!(lfo.value_at(Duration::ZERO), 8.0);
+        assert_eq!(Curve::Lfo(lfo).end(), None);
+    }
+
+    #[test]
+    fn parses_every_keyframe_form() {
+        let curve: Curve = serde_json::from_str(r#"[[0, 1], [1, 2, "step"], {"time": 2, "value": 3}]"#).unwrap();
+        assert_eq!(
+            curve,
+            Curve::Keyframes(vec![
+                Keyframe::new(Duration::ZERO, 1.0, Interpolation::Linear),
+                Keyframe::new(secs(1.0), 2.0, Interpolation::Step),
+                Keyframe::new(secs(2.0), 3.0, Interpolation::Linear),
+            ])
+        );
+        assert_eq!(serde_json::from_str::<Curve>("0.5").unwrap(), Curve::Constant(0.5));
+    }
+
+    #[test]
+    fn validate_rejects_bad_curves() {
+        assert!(Curve::Keyframes(Vec::new()).validate().is_err());
+        assert!(Curve::keyframes(&[(secs(2.0), 0.0), (secs(1.0), 1.0)], Interpolation::Linear).validate().is_err());
+        assert!(Curve::Keyframes(vec![Keyframe { time: f32::NAN, value: 0.0, interpolation: Interpolation::Linear }]).validate().is_err());
+        assert!(Curve::lfo(0.0, 1.0, -1.0).validate().is_err());
+        assert!(Curve::ramp(0.0, 1.0, secs(1.0), Interpolation::Ease).validate().is_ok());
+    }
+}
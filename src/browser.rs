@@ -0,0 +1,415 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+/// Prefix stripped from event paths before splitting them into folders
+const EVENT_PREFIX: &str = "event:/";
+
+/// One visible line of an [`EventTree`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeRow {
+    Folder {
+        /// Folder path without the `event:/` prefix, e.g. `Vehicles/Cars`
+        key: String,
+        name: String,
+        depth: usize,
+        expanded: bool,
+        /// Events below this folder that match the current search
+        events: usize,
+    },
+    Event {
+        /// Index into the paths the tree was built from
+        index: usize,
+        name: String,
+        depth: usize,
+    },
+}
+
+impl TreeRow {
+    pub fn depth(&self) -> usize {
+        match self {
+            TreeRow::Folder { depth, .. } | TreeRow::Event { depth, .. } => *depth,
+        }
+    }
+}
+
+/// Collapsible folder tree over `event:/Folder/Name` paths, with search
+///
+/// Holds only the browsing state (expanded folders, search text, selection
+/// and scroll offset); drawing is left to the caller. Rows are recomputed
+/// from the paths on every call, so the tree stays cheap to keep in sync.
+#[derive(Debug, Clone, Default)]
+pub struct EventTree {
+    paths: Vec<String>,
+    /// Indices into `paths`, sorted by path
+    order: Vec<usize>,
+    expanded: HashSet<String>,
+    query: String,
+    selected: usize,
+    scroll: usize,
+}
+
+fn relative(path: &str) -> &str {
+    path.strip_prefix(EVENT_PREFIX).unwrap_or(path)
+}
+
+impl EventTree {
+    pub fn new(paths: Vec<String>) -> Self {
+        let mut order: Vec<usize> = (0..paths.len()).collect();
+        order.sort_by(|a, b| paths[*a].cmp(&paths[*b]));
+        EventTree {
+            paths,
+            order,
+            ..Default::default()
+        }
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Filter to events whose path contains `query` (case-insensitive)
+    ///
+    /// While a search is active every folder with a match is shown expanded.
+    pub fn set_query(&mut self, query: impl Into<String>) {
+        self.query = query.into();
+        self.selected = 0;
+        self.scroll = 0;
+        // Land on the first matching event rather than its folder
+        if let Some(first) = self.rows().iter().position(|r| matches!(r, TreeRow::Event { .. })) {
+            self.selected = first;
+        }
+    }
+
+    pub fn expand_all(&mut self) {
+        for &index in &self.order {
+            let segments: Vec<&str> = relative(&self.paths[index]).split('/').collect();
+            for depth in 1..segments.len() {
+                self.expanded.insert(segments[..depth].join("/"));
+            }
+        }
+    }
+
+    pub fn collapse_all(&mut self) {
+        self.expanded.clear();
+        self.selected = 0;
+        self.scroll = 0;
+    }
+
+    fn is_expanded(&self, key: &str) -> bool {
+        !self.query.is_empty() || self.expanded.contains(key)
+    }
+
+    /// The visible rows, in path order
+    pub fn rows(&self) -> Vec<TreeRow> {
+        let query = self.query.to_lowercase();
+        let matching: Vec<usize> = self
+            .order
+            .iter()
+            .copied()
+            .filter(|i| query.is_empty() || self.paths[*i].to_lowercase().contains(&query))
+            .collect();
+
+        // Matching events below each folder
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for &index in &matching {
+            let segments: Vec<&str> = relative(&self.paths[index]).split('/').collect();
+            for depth in 1..segments.len() {
+                *counts.entry(segments[..depth].join("/")).or_default() += 1;
+            }
+        }
+
+        let mut rows = Vec::new();
+        let mut open: Vec<&str> = Vec::new();
+        for &index in &matching {
+            let segments: Vec<&str> = relative(&self.paths[index]).split('/').collect();
+            let (name, folders) = segments.split_last().expect("split yields at least one segment");
+
+            // Sorted paths keep each folder's contents together, so only
+            // folders that differ from the previous event's need a row
+            let common = open.iter().zip(folders).take_while(|(a, b)| a == b).count();
+            open.truncate(common);
+
+            let mut visible = (0..common).all(|depth| self.is_expanded(&folders[..=depth].join("/")));
+            for depth in common..folders.len() {
+                let key = folders[..=depth].join("/");
+                let expanded = self.is_expanded(&key);
+                if visible {
+                    rows.push(TreeRow::Folder {
+                        events: counts.get(&key).copied().unwrap_or(0),
+                        name: folders[depth].to_string(),
+                        key,
+                        depth,
+                        expanded,
+                    });
+                }
+                visible &= expanded;
+                open.push(folders[depth]);
+            }
+
+            if visible {
+                rows.push(TreeRow::Event {
+                    index,
+                    name: name.to_string(),
+                    depth: folders.len(),
+                });
+            }
+        }
+        rows
+    }
+
+    pub fn selected_row(&self) -> Option<TreeRow> {
+        self.rows().get(self.selected).cloned()
+    }
+
+    /// Index of the selected event, if an event (not a folder) is selected
+    pub fn selected_event(&self) -> Option<usize> {
+        match self.selected_row()? {
+            TreeRow::Event { index, .. } => Some(index),
+            TreeRow::Folder { .. } => None,
+        }
+    }
+
+    /// Position of the selection within [`EventTree::rows`]
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Move the selection by `offset` rows, stopping at either end
+    pub fn move_selection(&mut self, offset: isize) {
+        let count = self.rows().len();
+        if count == 0 {
+            self.selected = 0;
+            return;
+        }
+        self.selected = (self.selected as isize + offset).clamp(0, count as isize - 1) as usize;
+    }
+
+    /// Select the row for event `index`, expanding its folders
+    pub fn select_event(&mut self, index: usize) {
+        let segments: Vec<&str> = relative(&self.paths[index]).split('/').collect();
+        for depth in 1..segments.len() {
+            self.expanded.insert(segments[..depth].join("/"));
+        }
+        if let Some(position) = self
+            .rows()
+            .iter()
+            .position(|r| matches!(r, TreeRow::Event { index: i, .. } if *i == index))
+        {
+            self.selected = position;
+        }
+    }
+
+    /// Expand the selected folder; returns false if an event is selected
+    pub fn expand(&mut self) -> bool {
+        match self.selected_row() {
+            Some(TreeRow::Folder { key, .. }) => {
+                self.expanded.insert(key);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Collapse the selected folder, or jump from an event to its folder
+    pub fn collapse(&mut self) {
+        let rows = self.rows();
+        match rows.get(self.selected) {
+            Some(TreeRow::Folder { key, expanded: true, .. }) => {
+                self.expanded.remove(key);
+            }
+            Some(row) => {
+                // Select the parent folder: the nearest row above with a smaller depth
+                let depth = row.depth();
+                if let Some(parent) = rows[..self.selected].iter().rposition(|r| r.depth() < depth) {
+                    self.selected = parent;
+                }
+            }
+            None => {}
+        }
+        self.clamp();
+    }
+
+    /// Toggle the selected folder open or closed
+    pub fn toggle(&mut self) {
+        if let Some(TreeRow::Folder { key, expanded, .. }) = self.selected_row() {
+            if expanded {
+                self.expanded.remove(&key);
+            } else {
+                self.expanded.insert(key);
+            }
+        }
+        self.clamp();
+    }
+
+    fn clamp(&mut self) {
+        let count = self.rows().len();
+        self.selected = self.selected.min(count.saturating_sub(1));
+    }
+
+    /// Rows to draw in a window of `height` lines, scrolled to keep the selection visible
+    pub fn window(&mut self, height: usize) -> Range<usize> {
+        let count = self.rows().len();
+        self.clamp();
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if height > 0 && self.selected >= self.scroll + height {
+            self.scroll = self.selected + 1 - height;
+        }
+        self.scroll = self.scroll.min(count.saturating_sub(height));
+        self.scroll..(self.scroll + height).min(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Deliberately unsorted, with a top-level event and two levels of folders
+    const PATHS: [&str; 6] = [
+        "event:/Vehicles/Ride-on Mower",
+        "event:/Character/Player Footsteps",
+        "event:/Weapons/Explosion",
+        "event:/Vehicles/Cars/Car Engine",
+        "event:/Character/Dialogue",
+        "event:/Ambience",
+    ];
+
+    fn tree() -> EventTree {
+        EventTree::new(PATHS.iter().map(|path| path.to_string()).collect())
+    }
+
+    /// Rows as indented text: folders end in `/` (`+` when collapsed), events in their name
+    fn lines(tree: &EventTree) -> Vec<String> {
+        tree.rows()
+            .iter()
+            .map(|row| match row {
+                TreeRow::Folder { name, depth, expanded, events, .. } => {
+                    format!("{}{}{} ({})", "  ".repeat(*depth), name, if *expanded { "/" } else { "+" }, events)
+                }
+                TreeRow::Event { name, depth, .. } => format!("{}{}", "  ".repeat(*depth), name),
+            })
+            .collect()
+    }
+
+    fn select(tree: &mut EventTree, line: &str) {
+        let position = lines(tree).iter().position(|l| l.trim_start() == line).unwrap();
+        tree.move_selection(position as isize - tree.selected() as isize);
+    }
+
+    #[test]
+    fn starts_collapsed_in_path_order() {
+        assert_eq!(lines(&tree()), ["Ambience", "Character+ (2)", "Vehicles+ (2)", "Weapons+ (1)"]);
+    }
+
+    #[test]
+    fn expand_all_shows_nested_folders() {
+        let mut tree = tree();
+        tree.expand_all();
+        assert_eq!(
+            lines(&tree),
+            [
+                "Ambience",
+                "Character/ (2)",
+                "  Dialogue",
+                "  Player Footsteps",
+                "Vehicles/ (2)",
+                "  Cars/ (1)",
+                "    Car Engine",
+                "  Ride-on Mower",
+                "Weapons/ (1)",
+                "  Explosion",
+            ]
+        );
+        tree.collapse_all();
+        assert_eq!(lines(&tree).len(), 4);
+    }
+
+    #[test]
+    fn expand_and_collapse_the_selected_folder() {
+        let mut tree = tree();
+        select(&mut tree, "Vehicles+ (2)");
+        assert!(tree.expand());
+        assert_eq!(lines(&tree)[2..5], ["Vehicles/ (2)", "  Cars+ (1)", "  Ride-on Mower"]);
+
+        // From an event, collapse jumps to its folder; a second collapse closes it
+        select(&mut tree, "Ride-on Mower");
+        assert!(!tree.expand());
+        tree.collapse();
+        assert_eq!(tree.selected_row().map(|row| row.depth()), Some(0));
+        assert!(matches!(tree.selected_row(), Some(TreeRow::Folder { ref name, .. }) if name == "Vehicles"));
+        tree.collapse();
+        assert_eq!(lines(&tree), ["Ambience", "Character+ (2)", "Vehicles+ (2)", "Weapons+ (1)"]);
+
+        tree.toggle();
+        assert_eq!(lines(&tree).len(), 6);
+        tree.toggle();
+        assert_eq!(lines(&tree).len(), 4);
+    }
+
+    #[test]
+    fn search_filters_and_opens_matching_folders() {
+        let mut tree = tree();
+        tree.set_query("ENGINE");
+        assert_eq!(lines(&tree), ["Vehicles/ (1)", "  Cars/ (1)", "    Car Engine"]);
+        // The selection lands on the first matching event, not its folder
+        assert_eq!(tree.selected_event(), Some(3));
+
+        tree.set_query("er/");
+        assert_eq!(lines(&tree), ["Character/ (2)", "  Dialogue", "  Player Footsteps"]);
+
+        tree.set_query("no such event");
+        assert!(tree.rows().is_empty());
+        assert_eq!(tree.selected_row(), None);
+
+        tree.set_query("");
+        assert_eq!(lines(&tree).len(), 4);
+    }
+
+    #[test]
+    fn select_event_expands_its_folders() {
+        let mut tree = tree();
+        tree.select_event(3);
+        assert_eq!(tree.selected_event(), Some(3));
+        assert!(lines(&tree).contains(&"    Car Engine".to_string()));
+    }
+
+    #[test]
+    fn selection_is_clamped_to_the_rows() {
+        let mut tree = tree();
+        tree.move_selection(-5);
+        assert_eq!(tree.selected(), 0);
+        tree.move_selection(100);
+        assert_eq!(tree.selected(), 3);
+
+        // Collapsing everything starts the selection over at the top
+        tree.expand_all();
+        tree.move_selection(100);
+        assert_eq!(tree.selected(), 9);
+        tree.collapse_all();
+        assert_eq!(tree.selected(), 0);
+
+        // Collapsing the last folder from its event keeps the selection on a row
+        tree.expand_all();
+        tree.move_selection(100);
+        tree.collapse();
+        tree.collapse();
+        assert_eq!(lines(&tree).last().map(String::as_str), Some("Weapons+ (1)"));
+        assert_eq!(tree.selected(), tree.rows().len() - 1);
+    }
+
+    #[test]
+    fn window_scrolls_to_keep_the_selection_visible() {
+        let mut tree = tree();
+        tree.expand_all();
+        assert_eq!(tree.window(4), 0..4);
+        tree.move_selection(6);
+        assert_eq!(tree.window(4), 3..7);
+        tree.move_selection(100);
+        assert_eq!(tree.window(4), 6..10);
+        tree.move_selection(-8);
+        assert_eq!(tree.window(4), 1..5);
+        assert_eq!(tree.window(20), 0..10);
+    }
+}
@@ -0,0 +1,364 @@
+use libfmod::Studio;
+use std::{
+    fmt, thread,
+    time::{Duration, Instant},
+};
+
+use crate::automation::{Curve, ParameterTarget};
+use crate::output::OutputOptions;
+
+/// Tick length used by the demos when the output runs in real time
+pub const DEFAULT_STEP: Duration = Duration::from_millis(50);
+
+/// How an [`UpdateLoop`] advances its clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockMode {
+    /// Advance by the measured wall clock time, ticking about every `step`
+    RealTime,
+    /// Advance exactly `step` per tick, sleeping against a fixed schedule so
+    /// jitter in one tick is made up in the next
+    ///
+    /// A loop that falls a whole step behind (e.g. idle between runs) picks
+    /// the schedule up from the current time rather than catching up.
+    FixedStep,
+    /// Advance exactly `step` per tick without sleeping
+    ///
+    /// Only meaningful with the non-realtime outputs, where every
+    /// `studio.update()` mixes one DSP block.
+    FastForward,
+}
+
+impl fmt::Display for ClockMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ClockMode::RealTime => "real time",
+            ClockMode::FixedStep => "fixed step",
+            ClockMode::FastForward => "fast-forward",
+        };
+        f.write_str(name)
+    }
+}
+
+/// One completed tick of an [`UpdateLoop`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tick {
+    /// Number of ticks before this one
+    pub index: u64,
+    /// Clock time after this tick
+    pub time: Duration,
+    /// Time this tick advanced the clock by
+    pub delta: Duration,
+}
+
+/// Returns whether to keep calling it on later ticks
+type TickCallback<'a> = Box<dyn FnMut(&Tick) -> Result<bool, libfmod::Error> + 'a>;
+
+/// Drives `studio.update()` on a shared timing model
+///
+/// Replaces the `for _ in 0..n { studio.update()?; thread::sleep(..) }` loops:
+/// the same code runs in real time against a sound card or as fast as the
+/// mixer allows with the non-realtime outputs.
+///
+/// Each tick calls `studio.update()`, then the registered callbacks, then
+/// waits for the next tick according to the [`ClockMode`].
+pub struct UpdateLoop<'a> {
+    studio: Studio,
+    mode: ClockMode,
+    step: Duration,
+    time: Duration,
+    ticks: u64,
+    /// When the next fixed-step tick is due
+    next_deadline: Option<Instant>,
+    last: Option<Instant>,
+    callbacks: Vec<TickCallback<'a>>,
+}
+
+impl<'a> UpdateLoop<'a> {
+    pub fn new(studio: &Studio, mode: ClockMode) -> Self {
+        UpdateLoop {
+            studio: *studio,
+            mode,
+            step: DEFAULT_STEP,
+            time: Duration::ZERO,
+            ticks: 0,
+            next_deadline: None,
+            last: None,
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// Pick the clock matching the session's output
+    ///
+    /// Non-realtime outputs fast-forward one DSP block per tick, so the clock
+    /// stays in step with the audio; everything else uses a fixed 50ms step.
+    pub fn for_output(studio: &Studio, output: &OutputOptions) -> Result<Self, libfmod::Error> {
+        if output.mode.is_realtime() {
+            return Ok(Self::new(studio, ClockMode::FixedStep));
+        }
+
+        let core = studio.get_core_system()?;
+        let (block_length, _) = core.get_dsp_buffer_size()?;
+        let (sample_rate, _, _) = core.get_software_format()?;
+        let block = Duration::from_secs_f64(block_length as f64 / sample_rate.max(1) as f64);
+        Ok(Self::new(studio, ClockMode::FastForward).step(block))
+    }
+
+    /// Set the tick length (default 50ms)
+    pub fn step(mut self, step: Duration) -> Self {
+        self.step = step.max(Duration::from_micros(1));
+        self
+    }
+
+    /// Call `callback` after every tick, for as long as the loop lives
+    pub fn on_tick(&mut self, mut callback: impl FnMut(&Tick) -> Result<(), libfmod::Error> + 'a) -> &mut Self {
+        self.callbacks.push(Box::new(move |tick| callback(tick).map(|()| true)));
+        self
+    }
+
+    /// Drive parameter `name` along `curve` from now on, every tick
+    ///
+    /// The curve's time 0 is the current clock time, and its first value is
+    /// set straight away. Automation of an instance ends quietly once the
+    /// instance is released; a finished keyframe curve leaves its last value.
+    /// Either way the callback is dropped from the loop.
+    pub fn automate(
+        &mut self,
+        target: ParameterTarget,
+        name: impl Into<String>,
+        curve: Curve,
+    ) -> Result<&mut Self, libfmod::Error> {
+        let name = name.into();
+        let studio = self.studio;
+        let start = self.time;
+        target.set(&studio, &name, curve.value_at(Duration::ZERO))?;
+
+        self.callbacks.push(Box::new(move |tick| {
+            if !target.is_valid() {
+                return Ok(false);
+            }
+            let elapsed = tick.time.saturating_sub(start);
+            target.set(&studio, &name, curve.value_at(elapsed))?;
+            Ok(curve.end().is_none_or(|end| elapsed < end))
+        }));
+        Ok(self)
+    }
+
+    pub fn mode(&self) -> ClockMode {
+        self.mode
+    }
+
+    pub fn tick_length(&self) -> Duration {
+        self.step
+    }
+
+    /// Clock time since the first tick
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Convert a duration to the number of ticks it spans, rounding up
+    pub fn ticks_in(&self, duration: Duration) -> u64 {
+        u64::try_from(duration.as_nanos().div_ceil(self.step.as_nanos())).unwrap_or(u64::MAX)
+    }
+
+    /// Update once, run the callbacks and wait for the next tick
+    pub fn tick(&mut self) -> Result<Tick, libfmod::Error> {
+        let now = Instant::now();
+
+        self.studio.update()?;
+
+        let (delta, wake) = schedule(self.mode, self.step, self.last, self.next_deadline, now);
+        self.last = Some(now);
+        if self.mode == ClockMode::FixedStep {
+            self.next_deadline = wake;
+        }
+
+        let tick = Tick {
+            index: self.ticks,
+            time: self.time + delta,
+            delta,
+        };
+        self.ticks += 1;
+        self.time = tick.time;
+
+        // Drop the callbacks that are done; the first error skips the rest
+        let mut failed = None;
+        self.callbacks.retain_mut(|callback| {
+            if failed.is_some() {
+                return true;
+            }
+            callback(&tick).unwrap_or_else(|error| {
+                failed = Some(error);
+                true
+            })
+        });
+        if let Some(error) = failed {
+            return Err(error);
+        }
+
+        if let Some(wake) = wake {
+            sleep_until(wake);
+        }
+
+        Ok(tick)
+    }
+
+    /// Tick until `duration` of clock time has passed
+    pub fn run_for(&mut self, duration: Duration) -> Result<(), libfmod::Error> {
+        self.run_for_with(duration, |_| Ok(()))
+    }
+
+    /// Tick until `duration` has passed, calling `f` after each tick
+    pub fn run_for_with<E>(
+        &mut self,
+        duration: Duration,
+        mut f: impl FnMut(&Tick) -> Result<(), E>,
+    ) -> Result<(), E>
+    where
+        E: From<libfmod::Error>,
+    {
+        let end = self.time + duration;
+        while self.time < end {
+            let tick = self.tick()?;
+            f(&tick)?;
+        }
+        Ok(())
+    }
+
+    /// Tick until `predicate` returns true or `timeout` has passed
+    ///
+    /// Returns whether the predicate was met, e.g. to wait for an instance to
+    /// reach `PlaybackState::Stopped` after a fadeout.
+    pub fn run_until<E>(
+        &mut self,
+        timeout: Duration,
+        mut predicate: impl FnMut(&Tick) -> Result<bool, E>,
+    ) -> Result<bool, E>
+    where
+        E: From<libfmod::Error>,
+    {
+        let end = self.time + timeout;
+        while self.time < end {
+            let tick = self.tick()?;
+            if predicate(&tick)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl fmt::Debug for UpdateLoop<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpdateLoop")
+            .field("mode", &self.mode)
+            .field("step", &self.step)
+            .field("time", &self.time)
+            .field("ticks", &self.ticks)
+            .field("callbacks", &self.callbacks.len())
+            .finish()
+    }
+}
+
+/// Clock advance for a tick that started at `now`, and when to wake for the next one
+///
+/// `last` is when the previous tick started and `deadline` when it was due to
+/// wake. `None` means no wait, as in fast-forward.
+fn schedule(
+    mode: ClockMode,
+    step: Duration,
+    last: Option<Instant>,
+    deadline: Option<Instant>,
+    now: Instant,
+) -> (Duration, Option<Instant>) {
+    match mode {
+        ClockMode::RealTime => {
+            let delta = last.map_or(step, |last| now - last);
+            (delta, Some(now + step))
+        }
+        ClockMode::FixedStep => {
+            // Re-anchor after falling behind (a breakpoint, an idle gap
+            // between runs) instead of bursting through the missed ticks
+            let wake = match deadline {
+                Some(deadline) if deadline + step > now => deadline + step,
+                _ => now + step,
+            };
+            (step, Some(wake))
+        }
+        ClockMode::FastForward => (step, None),
+    }
+}
+
+fn sleep_until(deadline: Instant) {
+    let now = Instant::now();
+    if deadline > now {
+        thread::sleep(deadline - now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP: Duration = Duration::from_millis(50);
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn real_time_advances_by_the_measured_gap() {
+        let start = Instant::now();
+        assert_eq!(schedule(ClockMode::RealTime, STEP, None, None, start), (STEP, Some(start + STEP)));
+
+        let now = start + ms(73);
+        assert_eq!(schedule(ClockMode::RealTime, STEP, Some(start), None, now), (ms(73), Some(now + STEP)));
+    }
+
+    #[test]
+    fn fast_forward_never_waits() {
+        let now = Instant::now();
+        let block = Duration::from_micros(21_333);
+        assert_eq!(schedule(ClockMode::FastForward, block, Some(now - ms(500)), None, now), (block, None));
+    }
+
+    #[test]
+    fn fixed_step_keeps_a_fixed_schedule() {
+        let start = Instant::now();
+        // The first tick anchors the schedule one step from now
+        let (delta, first) = schedule(ClockMode::FixedStep, STEP, None, None, start);
+        assert_eq!((delta, first), (STEP, Some(start + STEP)));
+
+        // Waking late by 10ms is made up by the next deadline, not carried forward
+        let late = start + ms(60);
+        let (delta, second) = schedule(ClockMode::FixedStep, STEP, Some(start), first, late);
+        assert_eq!((delta, second), (STEP, Some(start + ms(100))));
+
+        // Still within a step of the schedule: keep it
+        let behind = start + ms(140);
+        assert_eq!(
+            schedule(ClockMode::FixedStep, STEP, Some(late), second, behind),
+            (STEP, Some(start + ms(150)))
+        );
+    }
+
+    #[test]
+    fn fixed_step_re_anchors_after_falling_behind() {
+        let start = Instant::now();
+        let deadline = Some(start + STEP);
+
+        // A whole step (or more) behind: pick up from now instead of bursting
+        let idle = start + ms(100);
+        assert_eq!(schedule(ClockMode::FixedStep, STEP, Some(start), deadline, idle), (STEP, Some(idle + STEP)));
+
+        let long_idle = start + Duration::from_secs(5);
+        assert_eq!(
+            schedule(ClockMode::FixedStep, STEP, Some(start), deadline, long_idle),
+            (STEP, Some(long_idle + STEP))
+        );
+    }
+}
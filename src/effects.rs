@@ -0,0 +1,586 @@
+use libfmod::{Channel, Dsp, DspType, System};
+use std::{fmt, str::FromStr};
+
+use crate::error::{FmodError, write_suggestions};
+use crate::parameter::closest_matches;
+
+/// Value string length asked of `Dsp::get_parameter_float`
+const VALUE_STRING_LENGTH: i32 = 32;
+
+/// Steps across a parameter's range per adjustment
+const STEPS_PER_RANGE: f32 = 50.0;
+
+/// A float parameter of a built-in DSP effect, by its FMOD index
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EffectParameter {
+    pub name: &'static str,
+    pub index: i32,
+    pub minimum: f32,
+    pub maximum: f32,
+    pub unit: &'static str,
+    /// Only powers of two in the range are accepted (FFT sizes)
+    pub powers_of_two: bool,
+}
+
+impl EffectParameter {
+    const fn new(name: &'static str, index: i32, minimum: f32, maximum: f32, unit: &'static str) -> Self {
+        EffectParameter { name, index, minimum, maximum, unit, powers_of_two: false }
+    }
+
+    const fn power_of_two(name: &'static str, index: i32, minimum: f32, maximum: f32) -> Self {
+        EffectParameter { powers_of_two: true, ..Self::new(name, index, minimum, maximum, "") }
+    }
+
+    /// One adjustment step: a fiftieth of the range
+    pub fn step(&self) -> f32 {
+        (self.maximum - self.minimum) / STEPS_PER_RANGE
+    }
+
+    /// Where `value` lies in the range, 0.0 to 1.0 (on a log scale for powers of two)
+    pub fn fraction(&self, value: f32) -> f32 {
+        if self.powers_of_two {
+            let octaves = (self.maximum / self.minimum).log2();
+            return ((value / self.minimum).log2() / octaves).clamp(0.0, 1.0);
+        }
+        ((value - self.minimum) / (self.maximum - self.minimum)).clamp(0.0, 1.0)
+    }
+
+    /// `value` clamped to the range, and to the nearest power of two if only those are accepted
+    pub fn constrain(&self, value: f32) -> f32 {
+        let value = value.clamp(self.minimum, self.maximum);
+        if self.powers_of_two {
+            return value.log2().round().exp2().clamp(self.minimum, self.maximum);
+        }
+        value
+    }
+
+    /// `value` moved by `steps` fiftieths of the range, or by `steps` doublings for powers of two
+    pub fn stepped(&self, value: f32, steps: f32) -> f32 {
+        if self.powers_of_two {
+            let doublings = if steps.abs() < 1.0 { steps.signum() } else { steps.round() };
+            return self.constrain(self.constrain(value) * doublings.exp2());
+        }
+        self.constrain(value + steps * self.step())
+    }
+}
+
+/// The built-in FMOD effects the rack can build
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffectKind {
+    Echo,
+    Lowpass,
+    Highpass,
+    SfxReverb,
+    PitchShift,
+    Compressor,
+    Chorus,
+    Flange,
+    Distortion,
+    Tremolo,
+    Limiter,
+    ParamEq,
+}
+
+const ECHO: &[EffectParameter] = &[
+    EffectParameter::new("delay", 0, 1.0, 5000.0, "ms"),
+    EffectParameter::new("feedback", 1, 0.0, 100.0, "%"),
+    EffectParameter::new("dry", 2, -80.0, 10.0, "dB"),
+    EffectParameter::new("wet", 3, -80.0, 10.0, "dB"),
+];
+const FILTER: &[EffectParameter] = &[
+    EffectParameter::new("cutoff", 0, 10.0, 22000.0, "Hz"),
+    EffectParameter::new("resonance", 1, 1.0, 10.0, ""),
+];
+const SFX_REVERB: &[EffectParameter] = &[
+    EffectParameter::new("decay", 0, 100.0, 20000.0, "ms"),
+    EffectParameter::new("early-delay", 1, 0.0, 300.0, "ms"),
+    EffectParameter::new("late-delay", 2, 0.0, 100.0, "ms"),
+    EffectParameter::new("hf-reference", 3, 20.0, 20000.0, "Hz"),
+    EffectParameter::new("hf-decay-ratio", 4, 10.0, 100.0, "%"),
+    EffectParameter::new("diffusion", 5, 10.0, 100.0, "%"),
+    EffectParameter::new("density", 6, 0.0, 100.0, "%"),
+    EffectParameter::new("low-shelf-frequency", 7, 20.0, 1000.0, "Hz"),
+    EffectParameter::new("low-shelf-gain", 8, -36.0, 12.0, "dB"),
+    EffectParameter::new("high-cut", 9, 20.0, 20000.0, "Hz"),
+    EffectParameter::new("early-late-mix", 10, 0.0, 100.0, "%"),
+    EffectParameter::new("wet", 11, -80.0, 20.0, "dB"),
+    EffectParameter::new("dry", 12, -80.0, 20.0, "dB"),
+];
+const PITCH_SHIFT: &[EffectParameter] = &[
+    EffectParameter::new("pitch", 0, 0.5, 2.0, ""),
+    EffectParameter::power_of_two("fft-size", 1, 256.0, 4096.0),
+];
+const COMPRESSOR: &[EffectParameter] = &[
+    EffectParameter::new("threshold", 0, -60.0, 0.0, "dB"),
+    EffectParameter::new("ratio", 1, 1.0, 50.0, ":1"),
+    EffectParameter::new("attack", 2, 0.1, 500.0, "ms"),
+    EffectParameter::new("release", 3, 10.0, 5000.0, "ms"),
+    EffectParameter::new("makeup", 4, -30.0, 30.0, "dB"),
+];
+const CHORUS: &[EffectParameter] = &[
+    EffectParameter::new("mix", 0, 0.0, 100.0, "%"),
+    EffectParameter::new("rate", 1, 0.0, 20.0, "Hz"),
+    EffectParameter::new("depth", 2, 0.0, 100.0, "%"),
+];
+const FLANGE: &[EffectParameter] = &[
+    EffectParameter::new("mix", 0, 0.0, 100.0, "%"),
+    EffectParameter::new("depth", 1, 0.01, 1.0, ""),
+    EffectParameter::new("rate", 2, 0.0, 20.0, "Hz"),
+];
+const DISTORTION: &[EffectParameter] = &[EffectParameter::new("level", 0, 0.0, 1.0, "")];
+const TREMOLO: &[EffectParameter] = &[
+    EffectParameter::new("frequency", 0, 0.1, 20.0, "Hz"),
+    EffectParameter::new("depth", 1, 0.0, 1.0, ""),
+    EffectParameter::new("shape", 2, 0.0, 1.0, ""),
+];
+const LIMITER: &[EffectParameter] = &[
+    EffectParameter::new("release", 0, 1.0, 1000.0, "ms"),
+    EffectParameter::new("ceiling", 1, -12.0, 0.0, "dB"),
+    EffectParameter::new("gain", 2, 0.0, 12.0, "dB"),
+];
+const PARAM_EQ: &[EffectParameter] = &[
+    EffectParameter::new("center", 0, 20.0, 22000.0, "Hz"),
+    EffectParameter::new("bandwidth", 1, 0.2, 5.0, "oct"),
+    EffectParameter::new("gain", 2, -30.0, 30.0, "dB"),
+];
+
+impl EffectKind {
+    pub const ALL: [EffectKind; 12] = [
+        EffectKind::Echo,
+        EffectKind::Lowpass,
+        EffectKind::Highpass,
+        EffectKind::SfxReverb,
+        EffectKind::PitchShift,
+        EffectKind::Compressor,
+        EffectKind::Chorus,
+        EffectKind::Flange,
+        EffectKind::Distortion,
+        EffectKind::Tremolo,
+        EffectKind::Limiter,
+        EffectKind::ParamEq,
+    ];
+
+    /// Name used on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            EffectKind::Echo => "echo",
+            EffectKind::Lowpass => "lowpass",
+            EffectKind::Highpass => "highpass",
+            EffectKind::SfxReverb => "reverb",
+            EffectKind::PitchShift => "pitch",
+            EffectKind::Compressor => "compressor",
+            EffectKind::Chorus => "chorus",
+            EffectKind::Flange => "flange",
+            EffectKind::Distortion => "distortion",
+            EffectKind::Tremolo => "tremolo",
+            EffectKind::Limiter => "limiter",
+            EffectKind::ParamEq => "eq",
+        }
+    }
+
+    pub fn dsp_type(&self) -> DspType {
+        match self {
+            EffectKind::Echo => DspType::Echo,
+            EffectKind::Lowpass => DspType::Lowpass,
+            EffectKind::Highpass => DspType::Highpass,
+            EffectKind::SfxReverb => DspType::Sfxreverb,
+            EffectKind::PitchShift => DspType::Pitchshift,
+            EffectKind::Compressor => DspType::Compressor,
+            EffectKind::Chorus => DspType::Chorus,
+            EffectKind::Flange => DspType::Flange,
+            EffectKind::Distortion => DspType::Distortion,
+            EffectKind::Tremolo => DspType::Tremolo,
+            EffectKind::Limiter => DspType::Limiter,
+            EffectKind::ParamEq => DspType::Parameq,
+        }
+    }
+
+    /// The float parameters the rack exposes, in FMOD's order
+    pub fn parameters(&self) -> &'static [EffectParameter] {
+        match self {
+            EffectKind::Echo => ECHO,
+            EffectKind::Lowpass | EffectKind::Highpass => FILTER,
+            EffectKind::SfxReverb => SFX_REVERB,
+            EffectKind::PitchShift => PITCH_SHIFT,
+            EffectKind::Compressor => COMPRESSOR,
+            EffectKind::Chorus => CHORUS,
+            EffectKind::Flange => FLANGE,
+            EffectKind::Distortion => DISTORTION,
+            EffectKind::Tremolo => TREMOLO,
+            EffectKind::Limiter => LIMITER,
+            EffectKind::ParamEq => PARAM_EQ,
+        }
+    }
+
+    /// Look up an effect by its command-line name (case-insensitive)
+    pub fn from_name(name: &str) -> Result<Self, EffectError> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| EffectError::UnknownEffect {
+                name: name.to_string(),
+                suggestions: closest_matches(name, Self::ALL.iter().map(|kind| kind.name()))
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect(),
+            })
+    }
+
+    /// Position of a parameter by name (case-insensitive)
+    pub fn parameter(&self, name: &str) -> Result<usize, EffectError> {
+        let parameters = self.parameters();
+        parameters
+            .iter()
+            .position(|parameter| parameter.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| EffectError::UnknownParameter {
+                effect: self.name(),
+                name: name.to_string(),
+                available: parameters.iter().map(|parameter| parameter.name).collect(),
+                suggestions: closest_matches(name, parameters.iter().map(|parameter| parameter.name))
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect(),
+            })
+    }
+}
+
+impl fmt::Display for EffectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Errors raised while parsing or building an effect chain
+#[derive(Debug)]
+pub enum EffectError {
+    UnknownEffect { name: String, suggestions: Vec<String> },
+    UnknownParameter {
+        effect: &'static str,
+        name: String,
+        available: Vec<&'static str>,
+        suggestions: Vec<String>,
+    },
+    /// Not `name=number`, or the number is outside the parameter's range
+    BadValue { effect: &'static str, setting: String, reason: String },
+    Fmod { context: String, error: libfmod::Error },
+}
+
+impl fmt::Display for EffectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EffectError::UnknownEffect { name, suggestions } => {
+                write!(f, "unknown effect \"{}\"", name)?;
+                write_suggestions(f, suggestions)?;
+                let names: Vec<&str> = EffectKind::ALL.iter().map(|kind| kind.name()).collect();
+                write!(f, " (effects: {})", names.join(", "))
+            }
+            EffectError::UnknownParameter {
+                effect,
+                name,
+                available,
+                suggestions,
+            } => {
+                write!(f, "{} has no parameter \"{}\"", effect, name)?;
+                write_suggestions(f, suggestions)?;
+                write!(f, " (available: {})", available.join(", "))
+            }
+            EffectError::BadValue { effect, setting, reason } => write!(f, "{}: \"{}\": {}", effect, setting, reason),
+            EffectError::Fmod { context, error } => write!(f, "{} failed: {:?}", context, error),
+        }
+    }
+}
+
+impl std::error::Error for EffectError {}
+
+impl FmodError for EffectError {
+    fn from_fmod(context: String, error: libfmod::Error) -> Self {
+        EffectError::Fmod { context, error }
+    }
+}
+
+/// An effect and its parameter settings as given on the command line
+///
+/// Written `name[:param=value,...]`, e.g. `echo:delay=250,feedback=40` or
+/// `reverb`; parameters not given keep FMOD's defaults.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectSpec {
+    pub kind: EffectKind,
+    /// Position in `kind.parameters()` and the value to set
+    pub settings: Vec<(usize, f32)>,
+}
+
+impl FromStr for EffectSpec {
+    type Err = EffectError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (name, settings_text) = text.split_once(':').unwrap_or((text, ""));
+        let kind = EffectKind::from_name(name.trim())?;
+        let mut settings = Vec::new();
+        for setting in settings_text.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let bad_value = |reason: String| EffectError::BadValue {
+                effect: kind.name(),
+                setting: setting.to_string(),
+                reason,
+            };
+            let (name, value) = setting
+                .split_once('=')
+                .ok_or_else(|| bad_value("expected param=value".to_string()))?;
+            let index = kind.parameter(name.trim())?;
+            let parameter = &kind.parameters()[index];
+            let value: f32 = value
+                .trim()
+                .parse()
+                .map_err(|_| bad_value(format!("\"{}\" is not a number", value.trim())))?;
+            if !(parameter.minimum..=parameter.maximum).contains(&value) {
+                let range = format!("{} to {} {}", parameter.minimum, parameter.maximum, parameter.unit);
+                return Err(bad_value(format!("{} is outside {}", value, range.trim_end())));
+            }
+            if parameter.powers_of_two && parameter.constrain(value) != value {
+                return Err(bad_value(format!("{} is not a power of two", value)));
+            }
+            settings.push((index, value));
+        }
+        Ok(EffectSpec { kind, settings })
+    }
+}
+
+/// A DSP effect in a rack, with its parameter values as last read or set
+#[derive(Debug, Clone)]
+pub struct Effect {
+    pub kind: EffectKind,
+    pub dsp: Dsp,
+    /// One per `kind.parameters()`
+    pub values: Vec<f32>,
+    pub bypass: bool,
+}
+
+impl Effect {
+    /// Create the DSP and apply the spec's settings
+    pub fn create(system: &System, spec: &EffectSpec) -> Result<Self, EffectError> {
+        let kind = spec.kind;
+        let dsp = system
+            .create_dsp_by_type(kind.dsp_type())
+            .map_err(EffectError::fmod(format!("create {} DSP", kind)))?;
+        let mut effect = Effect {
+            kind,
+            dsp,
+            values: vec![0.0; kind.parameters().len()],
+            bypass: false,
+        };
+        let applied = effect.refresh().and_then(|()| {
+            spec.settings
+                .iter()
+                .try_for_each(|(index, value)| effect.set(*index, *value))
+        });
+        if let Err(e) = applied {
+            let _ = effect.dsp.release();
+            return Err(e);
+        }
+        Ok(effect)
+    }
+
+    /// Read every parameter back from the DSP
+    pub fn refresh(&mut self) -> Result<(), EffectError> {
+        for (n, parameter) in self.kind.parameters().iter().enumerate() {
+            let (value, _) = self
+                .dsp
+                .get_parameter_float(parameter.index, VALUE_STRING_LENGTH)
+                .map_err(EffectError::fmod(format!("read {} {}", self.kind, parameter.name)))?;
+            self.values[n] = value;
+        }
+        self.bypass = self.dsp.get_bypass().map_err(EffectError::fmod("get_bypass"))?;
+        Ok(())
+    }
+
+    /// Set parameter `index` (position in `kind.parameters()`), clamped to its range
+    pub fn set(&mut self, index: usize, value: f32) -> Result<(), EffectError> {
+        let Some(parameter) = self.kind.parameters().get(index) else {
+            return Ok(());
+        };
+        let value = parameter.constrain(value);
+        self.dsp
+            .set_parameter_float(parameter.index, value)
+            .map_err(EffectError::fmod(format!("set {} {}", self.kind, parameter.name)))?;
+        self.values[index] = value;
+        Ok(())
+    }
+
+    /// Step parameter `index` by `steps` fiftieths of its range (doublings for FFT sizes)
+    pub fn adjust(&mut self, index: usize, steps: f32) -> Result<(), EffectError> {
+        if let Some(parameter) = self.kind.parameters().get(index) {
+            self.set(index, parameter.stepped(self.values[index], steps))?;
+        }
+        Ok(())
+    }
+
+    pub fn toggle_bypass(&mut self) -> Result<(), EffectError> {
+        self.dsp.set_bypass(!self.bypass).map_err(EffectError::fmod("set_bypass"))?;
+        self.bypass = !self.bypass;
+        Ok(())
+    }
+}
+
+/// A chain of DSP effects on a channel, first effect first in the signal path
+///
+/// FMOD processes a channel's DSPs from the highest index down to 0 (the
+/// head), so effect `n` of `len` sits at index `len - 1 - n`, after the
+/// channel's fader.
+#[derive(Debug)]
+pub struct EffectRack {
+    channel: Channel,
+    effects: Vec<Effect>,
+}
+
+impl EffectRack {
+    pub fn build(system: &System, channel: Channel, specs: &[EffectSpec]) -> Result<Self, EffectError> {
+        let mut rack = EffectRack {
+            channel,
+            effects: Vec::new(),
+        };
+        for spec in specs {
+            let added = Effect::create(system, spec).and_then(|effect| {
+                // Each new effect goes to the head, after the ones added before it
+                match channel.add_dsp(0, effect.dsp) {
+                    Ok(()) => Ok(effect),
+                    Err(error) => {
+                        let _ = effect.dsp.release();
+                        Err(EffectError::from_fmod(format!("add {} DSP", spec.kind), error))
+                    }
+                }
+            });
+            match added {
+                Ok(effect) => rack.effects.push(effect),
+                Err(e) => {
+                    // Take the effects added so far back off the channel
+                    let _ = rack.remove();
+                    return Err(e);
+                }
+            }
+        }
+        Ok(rack)
+    }
+
+    pub fn effects(&self) -> &[Effect] {
+        &self.effects
+    }
+
+    pub fn effect_mut(&mut self, index: usize) -> Option<&mut Effect> {
+        self.effects.get_mut(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.effects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// Move effect `index` earlier (negative) or later in the chain;
+    /// returns its new position
+    pub fn move_effect(&mut self, index: usize, offset: isize) -> Result<usize, EffectError> {
+        if index >= self.effects.len() {
+            return Ok(index);
+        }
+        let target = (index as isize + offset).clamp(0, self.effects.len() as isize - 1) as usize;
+        let effect = self.effects.remove(index);
+        self.effects.insert(target, effect);
+        let len = self.effects.len();
+        for (n, effect) in self.effects.iter().enumerate() {
+            self.channel
+                .set_dsp_index(effect.dsp, (len - 1 - n) as i32)
+                .map_err(EffectError::fmod(format!("move {} DSP", effect.kind)))?;
+        }
+        Ok(target)
+    }
+
+    /// Take every effect off the channel and release the DSPs
+    ///
+    /// Every DSP is released even if one fails; the first error is returned.
+    pub fn remove(self) -> Result<(), EffectError> {
+        let mut result = Ok(());
+        for effect in self.effects {
+            // The channel may already be gone once the sound has ended
+            let _ = self.channel.remove_dsp(effect.dsp);
+            let released = effect.dsp.release().map_err(EffectError::fmod(format!("release {} DSP", effect.kind)));
+            result = result.and(released);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(text: &str) -> String {
+        text.parse::<EffectSpec>().unwrap_err().to_string()
+    }
+
+    #[test]
+    fn parses_names_and_settings() {
+        assert_eq!(
+            "echo".parse::<EffectSpec>().unwrap(),
+            EffectSpec { kind: EffectKind::Echo, settings: Vec::new() }
+        );
+        assert_eq!(
+            " Reverb : wet = -6 , decay=2500 ".parse::<EffectSpec>().unwrap(),
+            EffectSpec { kind: EffectKind::SfxReverb, settings: vec![(11, -6.0), (0, 2500.0)] }
+        );
+        assert_eq!("lowpass:cutoff=800,".parse::<EffectSpec>().unwrap().settings, vec![(0, 800.0)]);
+    }
+
+    #[test]
+    fn every_kind_is_found_by_its_name() {
+        for kind in EffectKind::ALL {
+            assert_eq!(EffectKind::from_name(&kind.name().to_uppercase()).unwrap(), kind);
+            for (position, parameter) in kind.parameters().iter().enumerate() {
+                assert_eq!(kind.parameter(parameter.name).unwrap(), position);
+                assert!(parameter.minimum < parameter.maximum, "{} {}", kind, parameter.name);
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_names_get_suggestions() {
+        assert!(error("revrb").starts_with("unknown effect \"revrb\"; did you mean \"reverb\"?"));
+        assert!(error("lowpass:cutof=100").starts_with("lowpass has no parameter \"cutof\"; did you mean \"cutoff\"?"));
+        assert!(!error("wobble").contains("did you mean"));
+    }
+
+    #[test]
+    fn rejects_malformed_and_out_of_range_values() {
+        assert_eq!(error("echo:delay"), "echo: \"delay\": expected param=value");
+        assert_eq!(error("echo:delay=abc"), "echo: \"delay=abc\": \"abc\" is not a number");
+        assert_eq!(error("echo:delay=9000"), "echo: \"delay=9000\": 9000 is outside 1 to 5000 ms");
+        assert_eq!(error("pitch:pitch=3"), "pitch: \"pitch=3\": 3 is outside 0.5 to 2");
+        assert!(matches!("echo:delay=NaN".parse::<EffectSpec>(), Err(EffectError::BadValue { .. })));
+        assert_eq!(error("pitch:fft-size=1000"), "pitch: \"fft-size=1000\": 1000 is not a power of two");
+        assert_eq!("pitch:fft-size=2048".parse::<EffectSpec>().unwrap().settings, vec![(1, 2048.0)]);
+    }
+
+    #[test]
+    fn parameter_steps_and_fractions() {
+        let cutoff = &FILTER[0];
+        assert_eq!(cutoff.fraction(cutoff.minimum), 0.0);
+        assert_eq!(cutoff.fraction(cutoff.maximum), 1.0);
+        assert_eq!(cutoff.fraction(cutoff.maximum * 2.0), 1.0);
+        assert!((cutoff.step() * STEPS_PER_RANGE - (cutoff.maximum - cutoff.minimum)).abs() < 1e-2);
+        assert_eq!(cutoff.stepped(cutoff.maximum, 1.0), cutoff.maximum);
+    }
+
+    #[test]
+    fn fft_size_steps_between_powers_of_two() {
+        let fft_size = &PITCH_SHIFT[1];
+        assert_eq!(fft_size.stepped(1024.0, 1.0), 2048.0);
+        assert_eq!(fft_size.stepped(1024.0, -1.0), 512.0);
+        assert_eq!(fft_size.stepped(1024.0, 0.5), 2048.0);
+        assert_eq!(fft_size.stepped(1024.0, 10.0), 4096.0);
+        assert_eq!(fft_size.stepped(256.0, -1.0), 256.0);
+        assert_eq!(fft_size.constrain(1000.0), 1024.0);
+        assert_eq!(fft_size.constrain(100_000.0), 4096.0);
+        assert_eq!(fft_size.fraction(256.0), 0.0);
+        assert_eq!(fft_size.fraction(1024.0), 0.5);
+        assert_eq!(fft_size.fraction(4096.0), 1.0);
+    }
+}
@@ -0,0 +1,22 @@
+use std::fmt;
+
+/// Error types with an `Fmod { context, error }` variant for a failed FMOD call
+pub(crate) trait FmodError: Sized {
+    fn from_fmod(context: String, error: libfmod::Error) -> Self;
+
+    /// `map_err` adapter naming what was being done:
+    /// `.map_err(SceneError::fmod("EventInstance::start"))?`
+    fn fmod(context: impl Into<String>) -> impl FnOnce(libfmod::Error) -> Self {
+        let context = context.into();
+        move |error| Self::from_fmod(context, error)
+    }
+}
+
+/// Append `; did you mean "a" or "b"?` to an unknown-name message, if there are suggestions
+pub(crate) fn write_suggestions(f: &mut fmt::Formatter<'_>, suggestions: &[String]) -> fmt::Result {
+    if suggestions.is_empty() {
+        return Ok(());
+    }
+    let quoted: Vec<String> = suggestions.iter().map(|s| format!("\"{}\"", s)).collect();
+    write!(f, "; did you mean {}?", quoted.join(" or "))
+}
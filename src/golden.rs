@@ -0,0 +1,373 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::analysis::AudioStats;
+use crate::render::{RenderError, RenderEvent, RenderSpec, render};
+use crate::wav::WavFile;
+
+/// Default location of the reference renders (not checked in; see `tests/golden/README.md`)
+pub const GOLDEN_DIR: &str = "tests/golden";
+
+/// Seed used for every golden render so random variations repeat
+pub const GOLDEN_SEED: u32 = 0x464D_4F44;
+
+const GOLDEN_BANKS: [&str; 4] = ["Master.bank", "Master.strings.bank", "SFX.bank", "Vehicles.bank"];
+
+/// How far a render may drift from its reference before the test fails
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerances {
+    pub rms_db: f32,
+    pub peak_db: f32,
+    /// Allowed relative deviation of the spectral centroid (0.1 = 10%)
+    pub centroid_ratio: f32,
+    pub length_secs: f64,
+}
+
+impl Default for Tolerances {
+    fn default() -> Self {
+        Tolerances {
+            rms_db: 1.5,
+            peak_db: 2.0,
+            centroid_ratio: 0.15,
+            length_secs: 0.01,
+        }
+    }
+}
+
+/// One metric compared against its reference
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub name: &'static str,
+    pub unit: &'static str,
+    pub actual: f64,
+    pub expected: f64,
+    pub tolerance: f64,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        (self.actual - self.expected).abs() <= self.tolerance
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:<9} {:>9.3} {} (expected {:.3} ± {:.3})",
+            if self.passed() { "✓" } else { "✗" },
+            self.name,
+            self.actual,
+            self.unit,
+            self.expected,
+            self.tolerance
+        )
+    }
+}
+
+/// Result of comparing a render to its reference
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub actual: AudioStats,
+    pub reference: AudioStats,
+    pub checks: Vec<Check>,
+}
+
+impl Comparison {
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(Check::passed)
+    }
+}
+
+/// Compare RMS, peak, spectral centroid and length within `tolerances`
+pub fn compare(actual: &AudioStats, reference: &AudioStats, tolerances: &Tolerances) -> Comparison {
+    let centroid_tolerance = (reference.spectral_centroid * tolerances.centroid_ratio).max(1.0);
+    let checks = vec![
+        Check {
+            name: "rms",
+            unit: "dB",
+            actual: actual.rms_db() as f64,
+            expected: reference.rms_db() as f64,
+            tolerance: tolerances.rms_db as f64,
+        },
+        Check {
+            name: "peak",
+            unit: "dB",
+            actual: actual.peak_db() as f64,
+            expected: reference.peak_db() as f64,
+            tolerance: tolerances.peak_db as f64,
+        },
+        Check {
+            name: "centroid",
+            unit: "Hz",
+            actual: actual.spectral_centroid as f64,
+            expected: reference.spectral_centroid as f64,
+            tolerance: centroid_tolerance as f64,
+        },
+        Check {
+            name: "length",
+            unit: "s",
+            actual: actual.duration_secs,
+            expected: reference.duration_secs,
+            tolerance: tolerances.length_secs,
+        },
+    ];
+
+    Comparison {
+        actual: *actual,
+        reference: *reference,
+        checks,
+    }
+}
+
+/// A named, repeatable render compared against a reference WAV
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub length: Duration,
+    pub events: Vec<RenderEvent>,
+    pub tolerances: Tolerances,
+}
+
+impl Scenario {
+    pub fn render_spec(&self, output: impl Into<PathBuf>) -> RenderSpec {
+        let mut spec = RenderSpec::new(output, self.length)
+            .random_seed(GOLDEN_SEED)
+            .example_banks(&GOLDEN_BANKS);
+        for event in &self.events {
+            spec = spec.event(event.clone());
+        }
+        spec
+    }
+
+    pub fn reference_path(&self, golden_dir: &Path) -> PathBuf {
+        golden_dir.join(format!("{}.wav", self.name))
+    }
+}
+
+/// The scenarios from `harness_demo`, rendered offline
+pub fn builtin_scenarios() -> Vec<Scenario> {
+    let secs = Duration::from_secs_f32;
+
+    // Same RPM steps as the harness_demo parameter demo, held for 1s each
+    let rpm_steps = [0.0, 250.0, 500.0, 750.0, 1000.0, 1250.0, 1500.0, 1750.0, 2000.0, 1000.0, 0.0];
+    let mut mower = RenderEvent::new("event:/Vehicles/Ride-on Mower").stop_at(secs(rpm_steps.len() as f32));
+    for (i, rpm) in rpm_steps.iter().enumerate() {
+        mower = mower.parameter("RPM", *rpm, secs(i as f32));
+    }
+
+    // Ten steps, 0.5s apart, cycling through the four surfaces
+    let footsteps = (1..=10)
+        .map(|step| {
+            RenderEvent::new("event:/Character/Player Footsteps")
+                .start_at(secs((step - 1) as f32 * 0.5))
+                .parameter("Surface", (step % 4) as f32, Duration::ZERO)
+        })
+        .collect();
+
+    vec![
+        Scenario {
+            name: "explosion",
+            description: "Explosion one-shot",
+            length: secs(3.0),
+            events: vec![RenderEvent::new("event:/Weapons/Explosion")],
+            tolerances: Tolerances::default(),
+        },
+        Scenario {
+            name: "mower_rpm_sweep",
+            description: "Ride-on Mower RPM 0 → 2000 → 0 sweep",
+            length: secs(rpm_steps.len() as f32 + 2.0),
+            events: vec![mower],
+            tolerances: Tolerances::default(),
+        },
+        Scenario {
+            name: "footsteps_surface_cycle",
+            description: "Footsteps cycling concrete/gravel/wood/metal",
+            length: secs(5.5),
+            events: footsteps,
+            // Each step picks a random variation, so allow more spread
+            tolerances: Tolerances {
+                rms_db: 2.5,
+                peak_db: 3.0,
+                centroid_ratio: 0.25,
+                ..Tolerances::default()
+            },
+        },
+    ]
+}
+
+pub fn find_scenario(name: &str) -> Option<Scenario> {
+    builtin_scenarios().into_iter().find(|s| s.name == name)
+}
+
+/// Errors that stop a scenario from being evaluated at all
+#[derive(Debug)]
+pub enum GoldenError {
+    Render(RenderError),
+    Io { path: PathBuf, error: io::Error },
+}
+
+impl fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoldenError::Render(e) => write!(f, "render failed: {}", e),
+            GoldenError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for GoldenError {}
+
+impl From<RenderError> for GoldenError {
+    fn from(e: RenderError) -> Self {
+        GoldenError::Render(e)
+    }
+}
+
+/// What happened to a scenario
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    Compared(Comparison),
+    /// No reference exists yet; the render's stats are reported
+    MissingReference(AudioStats),
+    /// The render was stored as the new reference
+    Blessed(AudioStats),
+}
+
+impl Outcome {
+    /// A comparison outside tolerance, or a missing reference unless `allow_missing`
+    pub fn failed(&self, allow_missing: bool) -> bool {
+        match self {
+            Outcome::Compared(comparison) => !comparison.passed(),
+            Outcome::MissingReference(_) => !allow_missing,
+            Outcome::Blessed(_) => false,
+        }
+    }
+
+    pub fn skipped(&self, allow_missing: bool) -> bool {
+        allow_missing && matches!(self, Outcome::MissingReference(_))
+    }
+}
+
+/// Render `scenario` into `out_dir` and compare it to the reference in `golden_dir`
+///
+/// With `bless` the render replaces the reference instead of being compared.
+pub fn run_scenario(
+    scenario: &Scenario,
+    out_dir: &Path,
+    golden_dir: &Path,
+    bless: bool,
+) -> Result<Outcome, GoldenError> {
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |error| GoldenError::Io { path, error }
+    };
+
+    fs::create_dir_all(out_dir).map_err(io_error(out_dir))?;
+    let rendered = out_dir.join(format!("{}.wav", scenario.name));
+    render(&scenario.render_spec(&rendered))?;
+
+    let actual = AudioStats::of(&WavFile::read(&rendered).map_err(io_error(&rendered))?);
+    let reference_path = scenario.reference_path(golden_dir);
+
+    if bless {
+        fs::create_dir_all(golden_dir).map_err(io_error(golden_dir))?;
+        fs::copy(&rendered, &reference_path).map_err(io_error(&reference_path))?;
+        return Ok(Outcome::Blessed(actual));
+    }
+
+    if !reference_path.exists() {
+        return Ok(Outcome::MissingReference(actual));
+    }
+
+    let reference = AudioStats::of(&WavFile::read(&reference_path).map_err(io_error(&reference_path))?);
+    Ok(Outcome::Compared(compare(&actual, &reference, &scenario.tolerances)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(rms: f32, peak: f32, spectral_centroid: f32, duration_secs: f64) -> AudioStats {
+        AudioStats {
+            rms,
+            peak,
+            spectral_centroid,
+            duration_secs,
+        }
+    }
+
+    fn check(actual: f64, expected: f64, tolerance: f64) -> Check {
+        Check {
+            name: "rms",
+            unit: "dB",
+            actual,
+            expected,
+            tolerance,
+        }
+    }
+
+    fn failing(comparison: &Comparison) -> Vec<&'static str> {
+        comparison.checks.iter().filter(|c| !c.passed()).map(|c| c.name).collect()
+    }
+
+    #[test]
+    fn check_passes_within_tolerance_inclusive() {
+        assert!(check(-12.0, -12.0, 0.0).passed());
+        assert!(check(-10.5, -12.0, 1.5).passed());
+        assert!(check(-13.5, -12.0, 1.5).passed());
+        assert!(!check(-10.4, -12.0, 1.5).passed());
+        assert!(!check(f64::NAN, -12.0, 1.5).passed());
+    }
+
+    #[test]
+    fn identical_stats_pass() {
+        let reference = stats(0.25, 0.9, 1200.0, 3.0);
+        let comparison = compare(&reference, &reference, &Tolerances::default());
+        assert!(comparison.passed());
+        let names: Vec<&str> = comparison.checks.iter().map(|c| c.name).collect();
+        assert_eq!(names, ["rms", "peak", "centroid", "length"]);
+    }
+
+    #[test]
+    fn each_metric_fails_on_its_own() {
+        let reference = stats(0.25, 0.5, 1000.0, 3.0);
+        let tolerances = Tolerances::default();
+        // Doubling the level is +6 dB, beyond the 1.5 and 2 dB tolerances
+        assert_eq!(failing(&compare(&stats(0.5, 0.5, 1000.0, 3.0), &reference, &tolerances)), ["rms"]);
+        assert_eq!(failing(&compare(&stats(0.25, 1.0, 1000.0, 3.0), &reference, &tolerances)), ["peak"]);
+        assert_eq!(failing(&compare(&stats(0.25, 0.5, 1200.0, 3.0), &reference, &tolerances)), ["centroid"]);
+        assert_eq!(failing(&compare(&stats(0.25, 0.5, 1000.0, 3.1), &reference, &tolerances)), ["length"]);
+    }
+
+    #[test]
+    fn centroid_tolerance_is_relative_with_a_floor() {
+        let tolerances = Tolerances::default();
+        let reference = stats(0.25, 0.5, 1000.0, 3.0);
+        let comparison = compare(&stats(0.25, 0.5, 1140.0, 3.0), &reference, &tolerances);
+        assert!(comparison.passed());
+        assert!((comparison.checks[2].tolerance - 150.0).abs() < 1e-3);
+
+        // Silence has no centroid; 1 Hz either way still passes
+        let silent = stats(0.0, 0.0, 0.0, 3.0);
+        assert!(compare(&stats(0.0, 0.0, 1.0, 3.0), &silent, &tolerances).passed());
+    }
+
+    #[test]
+    fn missing_references_fail_unless_allowed() {
+        let rendered = stats(0.25, 0.5, 1000.0, 3.0);
+        let missing = Outcome::MissingReference(rendered);
+        assert!(missing.failed(false) && !missing.skipped(false));
+        assert!(!missing.failed(true) && missing.skipped(true));
+
+        let blessed = Outcome::Blessed(rendered);
+        assert!(!blessed.failed(false) && !blessed.skipped(true));
+
+        let regressed = Outcome::Compared(compare(&stats(0.5, 0.5, 1000.0, 3.0), &rendered, &Tolerances::default()));
+        assert!(regressed.failed(true) && !regressed.skipped(true));
+    }
+}
@@ -0,0 +1,484 @@
+use libfmod::{Bank, EventDescription, Guid, ParameterDescription, ParameterFlags, Studio};
+use serde::Serialize;
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Buffer size passed to `Bank::get_string_info`
+const MAX_STRING_LENGTH: i32 = 512;
+
+/// Labels are only listed for discrete parameters with at most this many values
+const MAX_LABELS: i32 = 64;
+
+/// Everything a set of loaded banks contains
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Inspection {
+    pub banks: Vec<BankInfo>,
+    /// Parameters shared by every event (Studio level)
+    pub global_parameters: Vec<ParameterInfo>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BankInfo {
+    /// File the bank was loaded from
+    pub file: PathBuf,
+    /// `bank:/` path, when the strings bank is loaded
+    pub path: Option<String>,
+    pub events: Vec<EventInfo>,
+    pub snapshots: Vec<EventInfo>,
+    pub buses: Vec<String>,
+    pub vcas: Vec<String>,
+    pub strings: Vec<StringEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EventInfo {
+    pub path: String,
+    pub id: String,
+    /// Timeline length in milliseconds (0 when the event has no timeline)
+    pub length_ms: i32,
+    pub is_3d: bool,
+    pub is_oneshot: bool,
+    pub is_stream: bool,
+    pub has_sustain_point: bool,
+    pub min_distance: f32,
+    pub max_distance: f32,
+    pub parameters: Vec<ParameterInfo>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ParameterInfo {
+    pub name: String,
+    pub minimum: f32,
+    pub maximum: f32,
+    pub default: f32,
+    /// `ParameterType` variant, e.g. `GameControlled` or `AutomaticDistance`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub flags: Vec<&'static str>,
+    /// Value labels of labeled parameters, indexed from `minimum`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+}
+
+impl ParameterInfo {
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.contains(&flag)
+    }
+
+    /// Whether game code may set the parameter (not read-only or automatic)
+    pub fn is_settable(&self) -> bool {
+        !self.has_flag("readonly") && !self.has_flag("automatic")
+    }
+
+    /// Increment for one adjustment step
+    ///
+    /// Discrete and labeled parameters move one whole value; continuous ones
+    /// about a twentieth of their range, rounded to 1, 2 or 5 times a power of ten.
+    pub fn step(&self) -> f32 {
+        if self.has_flag("discrete") || self.has_flag("labeled") {
+            return 1.0;
+        }
+        let raw = (self.maximum - self.minimum) / 20.0;
+        if raw <= 0.0 || !raw.is_finite() {
+            return 0.1;
+        }
+        let magnitude = 10f32.powf(raw.log10().floor());
+        // Allow for rounding, or a range of 1 would step by 0.1 since 5 * 0.01 < 0.05 in f32
+        [1.0, 2.0, 5.0, 10.0]
+            .into_iter()
+            .map(|m| m * magnitude)
+            .find(|step| *step >= raw * 0.9999)
+            .unwrap_or(10.0 * magnitude)
+    }
+
+    /// Position of `value` within the range, 0.0 to 1.0
+    pub fn fraction(&self, value: f32) -> f32 {
+        let range = self.maximum - self.minimum;
+        if range <= 0.0 {
+            0.0
+        } else {
+            ((value - self.minimum) / range).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Label for `value`, if the parameter is labeled
+    pub fn label(&self, value: f32) -> Option<&str> {
+        let index = (value - self.minimum).round();
+        if index < 0.0 {
+            return None;
+        }
+        self.labels.get(index as usize).map(String::as_str)
+    }
+}
+
+/// One entry of a strings bank
+#[derive(Debug, Clone, Serialize)]
+pub struct StringEntry {
+    pub id: String,
+    pub path: String,
+}
+
+/// Format a GUID the way FMOD Studio displays it
+pub fn format_guid(guid: &Guid) -> String {
+    let d = &guid.data_4;
+    format!(
+        "{{{:08x}-{:04x}-{:04x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}}}",
+        guid.data_1, guid.data_2, guid.data_3, d[0], d[1], d[2], d[3], d[4], d[5], d[6], d[7]
+    )
+}
+
+/// Names of the flags set on a parameter, lowercase
+pub fn parameter_flag_names(flags: ParameterFlags) -> Vec<&'static str> {
+    [
+        (ParameterFlags::READONLY, "readonly"),
+        (ParameterFlags::AUTOMATIC, "automatic"),
+        (ParameterFlags::GLOBAL, "global"),
+        (ParameterFlags::DISCRETE, "discrete"),
+        (ParameterFlags::LABELED, "labeled"),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| flags.contains(*flag))
+    .map(|(_, name)| name)
+    .collect()
+}
+
+pub(crate) fn parameter_info(
+    description: &ParameterDescription,
+    label: impl Fn(i32) -> Result<String, libfmod::Error>,
+) -> ParameterInfo {
+    let mut labels = Vec::new();
+    if description.flags.contains(ParameterFlags::LABELED) {
+        let count = ((description.maximum - description.minimum).round() as i32).saturating_add(1);
+        if count <= MAX_LABELS {
+            labels = (0..count).map_while(|i| label(i).ok()).collect();
+        }
+    }
+
+    ParameterInfo {
+        name: description.name.clone(),
+        minimum: description.minimum,
+        maximum: description.maximum,
+        default: description.defaultvalue,
+        kind: format!("{:?}", description.type_),
+        flags: parameter_flag_names(description.flags),
+        labels,
+    }
+}
+
+/// Describe an event or snapshot and all of its parameters
+pub fn inspect_event(event: &EventDescription) -> Result<EventInfo, libfmod::Error> {
+    let id = format_guid(&event.get_id()?);
+    // Paths need the strings bank; fall back to the GUID without it
+    let path = event.get_path().unwrap_or_else(|_| id.clone());
+    let (min_distance, max_distance) = event.get_min_max_distance()?;
+
+    Ok(EventInfo {
+        path,
+        id,
+        length_ms: event.get_length()?,
+        is_3d: event.is_3d()?,
+        is_oneshot: event.is_oneshot()?,
+        is_stream: event.is_stream()?,
+        has_sustain_point: event.has_sustain_point()?,
+        min_distance,
+        max_distance,
+        parameters: event_parameters(event)?,
+    })
+}
+
+/// Parameters of an event or snapshot, in the event's order
+pub fn event_parameters(event: &EventDescription) -> Result<Vec<ParameterInfo>, libfmod::Error> {
+    let mut parameters = Vec::new();
+    for index in 0..event.get_parameter_description_count()? {
+        let description = event.get_parameter_description_by_index(index)?;
+        parameters.push(parameter_info(&description, |label| {
+            event.get_parameter_label_by_index(index, label)
+        }));
+    }
+    Ok(parameters)
+}
+
+/// Describe everything in a loaded bank
+pub fn inspect_bank(bank: &Bank, file: &Path) -> Result<BankInfo, libfmod::Error> {
+    let mut events = Vec::new();
+    let mut snapshots = Vec::new();
+    for event in bank.get_event_list(bank.get_event_count()?)? {
+        let info = inspect_event(&event)?;
+        if event.is_snapshot()? {
+            snapshots.push(info);
+        } else {
+            events.push(info);
+        }
+    }
+    events.sort_by(|a, b| a.path.cmp(&b.path));
+    snapshots.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut buses = bank
+        .get_bus_list(bank.get_bus_count()?)?
+        .iter()
+        .map(|bus| bus.get_path())
+        .collect::<Result<Vec<_>, _>>()?;
+    buses.sort();
+
+    let mut vcas = bank
+        .get_vca_list(bank.get_vca_count()?)?
+        .iter()
+        .map(|vca| vca.get_path())
+        .collect::<Result<Vec<_>, _>>()?;
+    vcas.sort();
+
+    let mut strings = Vec::new();
+    for index in 0..bank.get_string_count()? {
+        let (id, path) = bank.get_string_info(index, MAX_STRING_LENGTH)?;
+        strings.push(StringEntry {
+            id: format_guid(&id),
+            path,
+        });
+    }
+    strings.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(BankInfo {
+        file: file.to_path_buf(),
+        path: bank.get_path().ok(),
+        events,
+        snapshots,
+        buses,
+        vcas,
+        strings,
+    })
+}
+
+/// Studio-level (global) parameters
+pub fn global_parameters(studio: &Studio) -> Result<Vec<ParameterInfo>, libfmod::Error> {
+    let count = studio.get_parameter_description_count()?;
+    let mut parameters: Vec<ParameterInfo> = studio
+        .get_parameter_description_list(count)?
+        .iter()
+        .map(|description| {
+            parameter_info(description, |label| {
+                studio.get_parameter_label_by_id(description.id, label)
+            })
+        })
+        .collect();
+    parameters.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(parameters)
+}
+
+/// Inspect loaded banks; `files` are the paths they were loaded from, in the same order
+pub fn inspect(studio: &Studio, banks: &[Bank], files: &[PathBuf]) -> Result<Inspection, libfmod::Error> {
+    let banks = banks
+        .iter()
+        .zip(files)
+        .map(|(bank, file)| inspect_bank(bank, file))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Inspection {
+        banks,
+        global_parameters: global_parameters(studio)?,
+    })
+}
+
+/// Every event (not snapshot) in the loaded banks, sorted by path
+pub fn list_events(studio: &Studio) -> Result<Vec<(EventDescription, EventInfo)>, libfmod::Error> {
+    let mut events = Vec::new();
+    for bank in studio.get_bank_list(studio.get_bank_count()?)? {
+        for event in bank.get_event_list(bank.get_event_count()?)? {
+            if !event.is_snapshot()? {
+                events.push((event, inspect_event(&event)?));
+            }
+        }
+    }
+    events.sort_by(|(_, a), (_, b)| a.path.cmp(&b.path));
+    events.dedup_by(|(_, a), (_, b)| a.id == b.id);
+    Ok(events)
+}
+
+/// Every snapshot in the loaded banks, sorted by path
+pub fn list_snapshots(studio: &Studio) -> Result<Vec<(EventDescription, EventInfo)>, libfmod::Error> {
+    let mut snapshots = Vec::new();
+    for bank in studio.get_bank_list(studio.get_bank_count()?)? {
+        for event in bank.get_event_list(bank.get_event_count()?)? {
+            if event.is_snapshot()? {
+                snapshots.push((event, inspect_event(&event)?));
+            }
+        }
+    }
+    snapshots.sort_by(|(_, a), (_, b)| a.path.cmp(&b.path));
+    snapshots.dedup_by(|(_, a), (_, b)| a.id == b.id);
+    Ok(snapshots)
+}
+
+/// Expand bank arguments into bank files, in a loadable order
+///
+/// Each argument may be a `.bank` file, a directory (all `.bank` files in
+/// it) or a glob pattern. `Master.bank` and the strings banks are loaded
+/// first so event paths resolve.
+pub fn find_banks<S: AsRef<str>>(inputs: &[S]) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    for input in inputs {
+        let input = input.as_ref();
+        let path = Path::new(input);
+        if path.is_dir() {
+            let entries = fs::read_dir(path).map_err(|e| format!("{}: {}", input, e))?;
+            for entry in entries {
+                let file = entry.map_err(|e| format!("{}: {}", input, e))?.path();
+                if file.extension().is_some_and(|ext| ext == "bank") {
+                    files.push(file);
+                }
+            }
+        } else if input.contains(['*', '?', '[']) {
+            let matches = glob::glob(input).map_err(|e| format!("{}: {}", input, e))?;
+            let before = files.len();
+            files.extend(matches.filter_map(Result::ok));
+            if files.len() == before {
+                return Err(format!("{}: no banks match", input));
+            }
+        } else if path.is_file() {
+            files.push(path.to_path_buf());
+        } else {
+            return Err(format!("{}: no such bank file or directory", input));
+        }
+    }
+
+    files.sort_by_key(|file| {
+        let name = file.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let rank = if name == "Master.bank" {
+            0
+        } else if name.ends_with(".strings.bank") {
+            1
+        } else {
+            2
+        };
+        (rank, name)
+    });
+    // The same bank can be named by several arguments, and not always adjacently
+    let mut seen = HashSet::new();
+    files.retain(|file| seen.insert(fs::canonicalize(file).unwrap_or_else(|_| file.clone())));
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, process};
+
+    fn parameter(minimum: f32, maximum: f32, flags: &[&'static str], labels: &[&str]) -> ParameterInfo {
+        ParameterInfo {
+            name: "Test".to_string(),
+            minimum,
+            maximum,
+            default: minimum,
+            kind: "GameControlled".to_string(),
+            flags: flags.to_vec(),
+            labels: labels.iter().map(|label| label.to_string()).collect(),
+        }
+    }
+
+    /// An empty directory under the system temp dir, unique to this test
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("libfmod-demos-inspect-{}-{}", process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn touch(dir: &Path, names: &[&str]) {
+        for name in names {
+            fs::write(dir.join(name), b"").unwrap();
+        }
+    }
+
+    fn file_names(files: &[PathBuf]) -> Vec<String> {
+        files
+            .iter()
+            .map(|file| file.file_name().unwrap().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn continuous_steps_round_to_1_2_or_5() {
+        for (minimum, maximum, step) in [(0.0, 1.0, 0.05), (0.0, 2000.0, 100.0), (-30.0, 30.0, 5.0), (0.0, 3.0, 0.2)] {
+            let actual = parameter(minimum, maximum, &[], &[]).step();
+            assert!((actual - step).abs() < step * 1e-3, "{}..{}: {}", minimum, maximum, actual);
+        }
+    }
+
+    #[test]
+    fn discrete_and_degenerate_steps() {
+        assert_eq!(parameter(0.0, 100.0, &["discrete"], &[]).step(), 1.0);
+        assert_eq!(parameter(0.0, 3.0, &["labeled"], &[]).step(), 1.0);
+        assert_eq!(parameter(5.0, 5.0, &[], &[]).step(), 0.1);
+        assert_eq!(parameter(-f32::MAX, f32::MAX, &[], &[]).step(), 0.1);
+    }
+
+    #[test]
+    fn fraction_is_clamped_to_the_range() {
+        let rpm = parameter(0.0, 2000.0, &[], &[]);
+        assert_eq!(rpm.fraction(500.0), 0.25);
+        assert_eq!(rpm.fraction(-10.0), 0.0);
+        assert_eq!(rpm.fraction(5000.0), 1.0);
+        assert_eq!(parameter(1.0, 1.0, &[], &[]).fraction(1.0), 0.0);
+    }
+
+    #[test]
+    fn labels_are_indexed_from_the_minimum() {
+        let surface = parameter(1.0, 3.0, &["labeled"], &["Concrete", "Gravel", "Wood"]);
+        assert_eq!(surface.label(1.0), Some("Concrete"));
+        assert_eq!(surface.label(2.4), Some("Gravel"));
+        assert_eq!(surface.label(3.0), Some("Wood"));
+        assert_eq!(surface.label(0.0), None);
+        assert_eq!(surface.label(4.0), None);
+        assert_eq!(parameter(0.0, 1.0, &[], &[]).label(0.0), None);
+    }
+
+    #[test]
+    fn settable_excludes_readonly_and_automatic() {
+        assert!(parameter(0.0, 1.0, &["global"], &[]).is_settable());
+        assert!(!parameter(0.0, 1.0, &["readonly"], &[]).is_settable());
+        assert!(!parameter(0.0, 1.0, &["automatic"], &[]).is_settable());
+    }
+
+    #[test]
+    fn find_banks_orders_master_and_strings_first() {
+        let dir = scratch_dir("order");
+        touch(&dir, &["Vehicles.bank", "Master.strings.bank", "SFX.bank", "Master.bank", "notes.txt"]);
+        let files = find_banks(&[dir.to_string_lossy()]).unwrap();
+        assert_eq!(
+            file_names(&files),
+            ["Master.bank", "Master.strings.bank", "SFX.bank", "Vehicles.bank"]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn find_banks_dedupes_by_canonical_path() {
+        let dir = scratch_dir("dedupe");
+        touch(&dir, &["Master.bank", "SFX.bank"]);
+        let sfx = dir.join("SFX.bank");
+        let sfx_again = dir.join(".").join("SFX.bank");
+        let pattern = dir.join("*.bank");
+        let inputs = [
+            sfx.to_string_lossy(),
+            pattern.to_string_lossy(),
+            sfx_again.to_string_lossy(),
+        ];
+        let files = find_banks(&inputs).unwrap();
+        assert_eq!(file_names(&files), ["Master.bank", "SFX.bank"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn find_banks_reports_missing_inputs() {
+        let dir = scratch_dir("missing");
+        let missing = dir.join("Nope.bank");
+        let error = find_banks(&[missing.to_string_lossy()]).unwrap_err();
+        assert!(error.ends_with("no such bank file or directory"), "{}", error);
+        let pattern = dir.join("*.bank");
+        let error = find_banks(&[pattern.to_string_lossy()]).unwrap_err();
+        assert!(error.ends_with("no banks match"), "{}", error);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
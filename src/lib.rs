@@ -1,17 +1,65 @@
-use std::env;
+pub mod analysis;
+pub mod automation;
+pub mod browser;
+pub mod clock;
+pub mod effects;
+mod error;
+pub mod golden;
+pub mod inspect;
+pub mod meter;
+pub mod mixer;
+pub mod motion;
+pub mod output;
+pub mod parameter;
+pub mod radar;
+pub mod registry;
+pub mod render;
+pub mod report;
+pub mod scene;
+pub mod sdk;
+pub mod session;
+pub mod snapshot;
+pub mod spatial;
+pub mod wav;
 
-/// Get the FMOD SDK directory from environment variable
+pub use analysis::AudioStats;
+pub use automation::{Curve, Interpolation, Keyframe, Lfo, ParameterTarget, Waveform};
+pub use browser::{EventTree, TreeRow};
+pub use clock::{ClockMode, Tick, UpdateLoop};
+pub use effects::{Effect, EffectError, EffectKind, EffectParameter, EffectRack, EffectSpec};
+pub use golden::{Comparison, Scenario, Tolerances};
+pub use inspect::{BankInfo, EventInfo, Inspection, ParameterInfo};
+pub use meter::{Levels, Meter, Spectrum};
+pub use mixer::{BusStrip, Mixer, VcaStrip};
+pub use motion::{Follower, Looping, MotionPath, Shape};
+pub use output::{OutputMode, OutputOptions};
+pub use parameter::{
+    GlobalParameter, ParameterError, ParameterValue, Parameters, edit_global_parameter, set_global_parameter, set_parameter,
+};
+pub use radar::{Blip, Radar};
+pub use registry::{InstanceCounts, InstanceRegistry, Leak, LeakKind, PlaybackInfo, TrackedInstance};
+pub use render::{ParameterChange, RenderError, RenderEvent, RenderReport, RenderSpec, render};
+pub use report::{TestFailure, TestReport};
+pub use scene::{Scene, SceneError, SceneReport, run_scene};
+pub use sdk::{FmodSdk, SdkComponent, SdkError, SdkSource, SdkVersion};
+pub use session::{SessionError, StudioSession, StudioSessionBuilder};
+pub use snapshot::Snapshot;
+pub use wav::{SampleFormat, WavFile};
+
+/// Get the FMOD SDK directory from `FMOD_SDK_DIR`, the config file or common install prefixes
 pub fn get_fmod_sdk_dir() -> Result<String, String> {
-    env::var("FMOD_SDK_DIR")
-        .map_err(|_| "FMOD_SDK_DIR environment variable not set. Please set it to your FMOD SDK path.".to_string())
+    let sdk = FmodSdk::locate().map_err(|e| e.to_string())?;
+    Ok(sdk.root().to_string_lossy().into_owned())
 }
 
 /// Get the path to FMOD Studio example banks
 pub fn get_example_banks_dir() -> Result<String, String> {
-    Ok(format!("{}/api/studio/examples/media", get_fmod_sdk_dir()?))
+    let sdk = FmodSdk::locate().map_err(|e| e.to_string())?;
+    Ok(sdk.example_banks_dir().to_string_lossy().into_owned())
 }
 
 /// Get the path to a specific example bank file
 pub fn get_example_bank_path(bank_name: &str) -> Result<String, String> {
-    Ok(format!("{}/{}", get_example_banks_dir()?, bank_name))
-}
\ No newline at end of file
+    let sdk = FmodSdk::locate().map_err(|e| e.to_string())?;
+    Ok(sdk.example_bank_path(bank_name).to_string_lossy().into_owned())
+}
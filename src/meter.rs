@@ -0,0 +1,201 @@
+use libfmod::{ChannelGroup, Dsp, DspParameterFft, DspType, System, ffi::FMOD_CHANNELCONTROL_DSP_HEAD};
+
+use crate::analysis::to_db;
+
+/// Lowest frequency shown by [`Spectrum::bands`]
+pub const SPECTRUM_FLOOR_HZ: f32 = 20.0;
+
+/// Per-channel levels from one metering read
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Levels {
+    pub peak: Vec<f32>,
+    pub rms: Vec<f32>,
+}
+
+impl Levels {
+    /// Loudest channel peak, linear
+    pub fn max_peak(&self) -> f32 {
+        self.peak.iter().fold(0.0f32, |a, b| a.max(*b))
+    }
+
+    /// Loudest channel RMS, linear
+    pub fn max_rms(&self) -> f32 {
+        self.rms.iter().fold(0.0f32, |a, b| a.max(*b))
+    }
+
+    pub fn max_peak_db(&self) -> f32 {
+        to_db(self.max_peak())
+    }
+}
+
+/// Output metering on a channel group's head DSP
+///
+/// Metering runs inside the mixer, so it also works with the `nosound`
+/// outputs on machines without a sound card.
+#[derive(Debug, Clone, Copy)]
+pub struct Meter {
+    dsp: Dsp,
+}
+
+impl Meter {
+    /// Meter the final mix on the core system's master channel group
+    pub fn master(core: &System) -> Result<Self, libfmod::Error> {
+        Self::on_channel_group(&core.get_master_channel_group()?)
+    }
+
+    pub fn on_channel_group(group: &ChannelGroup) -> Result<Self, libfmod::Error> {
+        let dsp = group.get_dsp(FMOD_CHANNELCONTROL_DSP_HEAD)?;
+        dsp.set_metering_enabled(false, true)?;
+        Ok(Meter { dsp })
+    }
+
+    pub fn dsp(&self) -> Dsp {
+        self.dsp
+    }
+
+    /// Levels of the most recently mixed block
+    pub fn read(&self) -> Result<Levels, libfmod::Error> {
+        let (_input, output) = self.dsp.get_metering_info()?;
+        let channels = (output.numchannels.max(0) as usize).min(output.peaklevel.len());
+        Ok(Levels {
+            peak: output.peaklevel[..channels].to_vec(),
+            rms: output.rmslevel[..channels].to_vec(),
+        })
+    }
+}
+
+/// An FFT DSP on a channel group's output, for spectrum displays
+///
+/// Like [`Meter`] it works on the `nosound` outputs. The DSP stays on the
+/// group until [`remove`](Self::remove) is called.
+#[derive(Debug, Clone, Copy)]
+pub struct Spectrum {
+    dsp: Dsp,
+    group: ChannelGroup,
+    sample_rate: f32,
+}
+
+impl Spectrum {
+    /// Analyze the final mix on the core system's master channel group
+    pub fn master(core: &System) -> Result<Self, libfmod::Error> {
+        let (sample_rate, _, _) = core.get_software_format()?;
+        let dsp = core.create_dsp_by_type(DspType::Fft)?;
+        let group = core.get_master_channel_group()?;
+        group.add_dsp(FMOD_CHANNELCONTROL_DSP_HEAD, dsp)?;
+        Ok(Spectrum {
+            dsp,
+            group,
+            sample_rate: sample_rate as f32,
+        })
+    }
+
+    pub fn dsp(&self) -> Dsp {
+        self.dsp
+    }
+
+    /// Nyquist frequency: the top of the spectrum
+    pub fn max_frequency(&self) -> f32 {
+        self.sample_rate / 2.0
+    }
+
+    /// Magnitude spectrum of the most recent window, one `Vec` per channel
+    ///
+    /// Each has `length` bins of `sample_rate / length` Hz; only the first
+    /// half (up to the Nyquist frequency) is meaningful.
+    pub fn read(&self) -> Result<DspParameterFft, libfmod::Error> {
+        DspParameterFft::try_from(self.dsp)
+    }
+
+    /// The spectrum in `count` log-spaced bands from 20 Hz to Nyquist,
+    /// each the loudest bin in it (linear, channels averaged)
+    pub fn bands(&self, count: usize) -> Result<Vec<f32>, libfmod::Error> {
+        Ok(log_bands(&self.read()?, self.sample_rate, count))
+    }
+
+    /// Take the DSP off the channel group and release it
+    pub fn remove(self) -> Result<(), libfmod::Error> {
+        self.group.remove_dsp(self.dsp)?;
+        self.dsp.release()
+    }
+}
+
+/// Group FFT bins into `count` bands spaced evenly on a log frequency scale
+fn log_bands(fft: &DspParameterFft, sample_rate: f32, count: usize) -> Vec<f32> {
+    let length = fft.length.max(0) as usize;
+    let channels: Vec<&Vec<f32>> = fft.spectrum.iter().filter(|bins| !bins.is_empty()).collect();
+    if length == 0 || channels.is_empty() || count == 0 {
+        return vec![0.0; count];
+    }
+
+    // Mean magnitude across channels, up to Nyquist
+    let usable = (length / 2).min(channels.iter().map(|bins| bins.len()).min().unwrap_or(0));
+    let mono: Vec<f32> = (0..usable)
+        .map(|bin| channels.iter().map(|bins| bins[bin]).sum::<f32>() / channels.len() as f32)
+        .collect();
+
+    let hz_per_bin = sample_rate / length as f32;
+    let nyquist = sample_rate / 2.0;
+    let edge = |band: usize| SPECTRUM_FLOOR_HZ * (nyquist / SPECTRUM_FLOOR_HZ).powf(band as f32 / count as f32);
+    (0..count)
+        .map(|band| {
+            let low = ((edge(band) / hz_per_bin).floor() as usize).min(usable.saturating_sub(1));
+            // Low bands can be narrower than a bin; they still get the bin they fall in
+            let high = ((edge(band + 1) / hz_per_bin).ceil() as usize).clamp(low + 1, usable.max(low + 1));
+            mono.get(low..high.min(mono.len()))
+                .map(|bins| bins.iter().fold(0.0f32, |a, b| a.max(*b)))
+                .unwrap_or(0.0)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fft(length: i32, spectrum: Vec<Vec<f32>>) -> DspParameterFft {
+        DspParameterFft { length, spectrum }
+    }
+
+    #[test]
+    fn empty_spectrum_gives_silent_bands() {
+        assert_eq!(log_bands(&fft(0, Vec::new()), 48000.0, 4), vec![0.0; 4]);
+        assert_eq!(log_bands(&fft(1024, vec![Vec::new(), Vec::new()]), 48000.0, 4), vec![0.0; 4]);
+        assert!(log_bands(&fft(1024, vec![vec![1.0; 1024]]), 48000.0, 0).is_empty());
+    }
+
+    #[test]
+    fn more_bands_than_bins() {
+        // 4 usable bins of 1 kHz each
+        let mut bins = vec![0.0; 8];
+        bins[3] = 1.0;
+        let bands = log_bands(&fft(8, vec![bins]), 8000.0, 16);
+        assert_eq!(bands.len(), 16);
+        assert!(bands.iter().all(|band| (0.0..=1.0).contains(band)));
+        assert_eq!(bands[15], 1.0);
+    }
+
+    #[test]
+    fn mismatched_channels_use_the_bins_they_share() {
+        // The second channel stops at bin 3, so the peak in bin 3 of the first is ignored
+        let mut long = vec![0.0; 8];
+        long[3] = 1.0;
+        long[2] = 0.5;
+        let short = vec![0.0, 0.0, 0.5];
+        let bands = log_bands(&fft(8, vec![long, short]), 8000.0, 4);
+        assert_eq!(bands.len(), 4);
+        assert_eq!(bands.iter().fold(0.0f32, |a, b| a.max(*b)), 0.5);
+    }
+
+    #[test]
+    fn peak_lands_in_its_band() {
+        // 46.875 Hz bins; 1 kHz is bin 21, in band 5 of 10 (693 Hz to 1.4 kHz)
+        let mut left = vec![0.0; 1024];
+        let mut right = vec![0.0; 1024];
+        left[21] = 1.0;
+        right[21] = 0.5;
+        let bands = log_bands(&fft(1024, vec![left, right]), 48000.0, 10);
+        let mut expected = vec![0.0; 10];
+        expected[5] = 0.75;
+        assert_eq!(bands, expected);
+    }
+}
@@ -0,0 +1,210 @@
+use libfmod::{Bus, CpuUsage, StopMode, Studio, System, Vca};
+
+use crate::meter::{Levels, Meter};
+
+/// A bus from the loaded banks, with its state at the last refresh
+#[derive(Debug, Clone)]
+pub struct BusStrip {
+    pub bus: Bus,
+    /// `bus:/` path; the master bus is `bus:/`
+    pub path: String,
+    pub volume: f32,
+    /// Volume after VCAs and snapshots
+    pub final_volume: f32,
+    pub mute: bool,
+    pub paused: bool,
+    /// Exclusive and inclusive DSP time of the last mix, in microseconds
+    ///
+    /// `None` unless the core system was initialized with `Init::PROFILE_ENABLE`
+    /// and the bus has a channel group.
+    pub cpu: Option<(u32, u32)>,
+    /// Output levels; `None` until the bus has a channel group
+    pub levels: Option<Levels>,
+    /// Channel group locked so the bus meters even while idle
+    pub locked: bool,
+    meter: Option<Meter>,
+}
+
+impl BusStrip {
+    fn new(bus: Bus) -> Result<Self, libfmod::Error> {
+        let mut strip = BusStrip {
+            bus,
+            path: bus.get_path()?,
+            volume: 1.0,
+            final_volume: 1.0,
+            mute: false,
+            paused: false,
+            cpu: None,
+            levels: None,
+            locked: false,
+            meter: None,
+        };
+        strip.refresh()?;
+        Ok(strip)
+    }
+
+    /// Read volume, mute, pause, CPU and levels
+    ///
+    /// A bus only has a channel group while something plays through it (or
+    /// it is locked), so metering starts the first time one is there.
+    pub fn refresh(&mut self) -> Result<(), libfmod::Error> {
+        (self.volume, self.final_volume) = self.bus.get_volume()?;
+        self.mute = self.bus.get_mute()?;
+        self.paused = self.bus.get_paused()?;
+        self.cpu = self.bus.get_cpu_usage().ok();
+        if self.meter.is_none()
+            && let Ok(group) = self.bus.get_channel_group()
+        {
+            self.meter = Meter::on_channel_group(&group).ok();
+        }
+        // The channel group (and its DSP) goes away when the bus falls idle
+        self.levels = match self.meter.map(|meter| meter.read()) {
+            Some(Ok(levels)) => Some(levels),
+            Some(Err(_)) => {
+                self.meter = None;
+                None
+            }
+            None => None,
+        };
+        Ok(())
+    }
+
+    pub fn set_volume(&mut self, volume: f32) -> Result<(), libfmod::Error> {
+        self.bus.set_volume(volume.max(0.0))?;
+        self.volume = volume.max(0.0);
+        Ok(())
+    }
+
+    pub fn toggle_mute(&mut self) -> Result<(), libfmod::Error> {
+        self.bus.set_mute(!self.mute)?;
+        self.mute = !self.mute;
+        Ok(())
+    }
+
+    pub fn toggle_paused(&mut self) -> Result<(), libfmod::Error> {
+        self.bus.set_paused(!self.paused)?;
+        self.paused = !self.paused;
+        Ok(())
+    }
+
+    /// Keep the bus's channel group alive (and metered) while nothing plays
+    ///
+    /// The channel group is created at the next `Studio::update`.
+    pub fn set_locked(&mut self, locked: bool) -> Result<(), libfmod::Error> {
+        if locked != self.locked {
+            if locked {
+                self.bus.lock_channel_group()?;
+            } else {
+                self.bus.unlock_channel_group()?;
+            }
+            self.locked = locked;
+        }
+        Ok(())
+    }
+
+    /// Stop every event instance routed into this bus
+    pub fn stop_all_events(&self, mode: StopMode) -> Result<(), libfmod::Error> {
+        self.bus.stop_all_events(mode)
+    }
+}
+
+/// A VCA from the loaded banks, with its volume at the last refresh
+#[derive(Debug, Clone)]
+pub struct VcaStrip {
+    pub vca: Vca,
+    pub path: String,
+    pub volume: f32,
+    pub final_volume: f32,
+}
+
+impl VcaStrip {
+    fn new(vca: Vca) -> Result<Self, libfmod::Error> {
+        let (volume, final_volume) = vca.get_volume()?;
+        Ok(VcaStrip {
+            vca,
+            path: vca.get_path()?,
+            volume,
+            final_volume,
+        })
+    }
+
+    pub fn refresh(&mut self) -> Result<(), libfmod::Error> {
+        (self.volume, self.final_volume) = self.vca.get_volume()?;
+        Ok(())
+    }
+
+    pub fn set_volume(&mut self, volume: f32) -> Result<(), libfmod::Error> {
+        self.vca.set_volume(volume.max(0.0))?;
+        self.volume = volume.max(0.0);
+        Ok(())
+    }
+}
+
+/// Every bus and VCA in the loaded banks, like the Studio mixer window
+///
+/// Call [`refresh`](Self::refresh) after each `Studio::update` to pick up
+/// final volumes, CPU and meter levels.
+#[derive(Debug, Clone)]
+pub struct Mixer {
+    /// Sorted by path, so `bus:/` (the master bus) comes first
+    pub buses: Vec<BusStrip>,
+    pub vcas: Vec<VcaStrip>,
+    /// Core system CPU usage at the last refresh, in percent
+    pub cpu: Option<CpuUsage>,
+    core: System,
+}
+
+impl Mixer {
+    pub fn load(studio: &Studio) -> Result<Self, libfmod::Error> {
+        let mut buses = Vec::new();
+        let mut vcas = Vec::new();
+        for bank in studio.get_bank_list(studio.get_bank_count()?)? {
+            for bus in bank.get_bus_list(bank.get_bus_count()?)? {
+                buses.push(BusStrip::new(bus)?);
+            }
+            for vca in bank.get_vca_list(bank.get_vca_count()?)? {
+                vcas.push(VcaStrip::new(vca)?);
+            }
+        }
+        // Banks can share buses; keep one strip per path
+        buses.sort_by(|a, b| a.path.cmp(&b.path));
+        buses.dedup_by(|a, b| a.path == b.path);
+        vcas.sort_by(|a, b| a.path.cmp(&b.path));
+        vcas.dedup_by(|a, b| a.path == b.path);
+        let core = studio.get_core_system()?;
+        Ok(Mixer {
+            buses,
+            vcas,
+            cpu: core.get_cpu_usage().ok(),
+            core,
+        })
+    }
+
+    pub fn refresh(&mut self) -> Result<(), libfmod::Error> {
+        for strip in &mut self.buses {
+            strip.refresh()?;
+        }
+        for strip in &mut self.vcas {
+            strip.refresh()?;
+        }
+        self.cpu = self.core.get_cpu_usage().ok();
+        Ok(())
+    }
+
+    pub fn bus(&self, path: &str) -> Option<&BusStrip> {
+        self.buses.iter().find(|strip| strip.path == path)
+    }
+
+    pub fn vca(&self, path: &str) -> Option<&VcaStrip> {
+        self.vcas.iter().find(|strip| strip.path == path)
+    }
+
+    /// Number of strips: buses first, then VCAs
+    pub fn len(&self) -> usize {
+        self.buses.len() + self.vcas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
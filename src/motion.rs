@@ -0,0 +1,367 @@
+use libfmod::Vector;
+use serde::{Deserialize, Serialize};
+use std::{f32::consts::TAU, fmt, time::Duration};
+
+use crate::spatial::distance;
+
+/// Speed of a new path, in units per second
+pub const DEFAULT_SPEED: f32 = 2.0;
+
+/// Points sampled along a shape to measure its length
+const LENGTH_SAMPLES: usize = 256;
+
+/// The trajectory a [`MotionPath`] follows
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// Straight line from `from` to `to`
+    Linear { from: Vector, to: Vector },
+    /// Circle in the horizontal plane, e.g. around the listener
+    Orbit { center: Vector, radius: f32 },
+    /// Horizontal figure-eight crossing at `center`, `radius` wide each side
+    FigureEight { center: Vector, radius: f32 },
+    /// Catmull-Rom spline through every waypoint
+    Spline { waypoints: Vec<Vector> },
+    /// Straight pass `height` above `center`, from `distance` in front (-Z) to `distance` behind
+    FlyOver { center: Vector, height: f32, distance: f32 },
+}
+
+impl Shape {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Shape::Linear { .. } => "linear",
+            Shape::Orbit { .. } => "orbit",
+            Shape::FigureEight { .. } => "figure-eight",
+            Shape::Spline { .. } => "spline",
+            Shape::FlyOver { .. } => "fly-over",
+        }
+    }
+
+    /// Point at `u` (0.0 to 1.0) along the shape; not evenly spaced in distance
+    pub fn point(&self, u: f32) -> Vector {
+        let u = u.clamp(0.0, 1.0);
+        match self {
+            Shape::Linear { from, to } => lerp(from, to, u),
+            Shape::Orbit { center, radius } => {
+                let (sin, cos) = (TAU * u).sin_cos();
+                offset(center, radius * sin, 0.0, -radius * cos)
+            }
+            Shape::FigureEight { center, radius } => {
+                // Lemniscate of Gerono, crossing the centre twice per lap
+                let (sin, cos) = (TAU * u).sin_cos();
+                offset(center, radius * sin, 0.0, -radius * sin * cos)
+            }
+            Shape::Spline { waypoints } => catmull_rom(waypoints, u),
+            Shape::FlyOver { center, height, distance } => {
+                offset(center, 0.0, *height, -distance + 2.0 * distance * u)
+            }
+        }
+    }
+}
+
+/// What happens when a path reaches its end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Looping {
+    /// Stop at the end
+    Once,
+    /// Jump back to the start (seamless for closed shapes like orbits)
+    Loop,
+    /// Reverse direction at either end
+    PingPong,
+}
+
+impl fmt::Display for Looping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Looping::Once => "once",
+            Looping::Loop => "loop",
+            Looping::PingPong => "ping-pong",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A trajectory travelled at constant speed
+///
+/// Positions are spaced evenly by distance, not by the shape's parameter,
+/// so a spline with uneven waypoints still moves at `speed`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MotionPath {
+    shape: Shape,
+    speed: f32,
+    looping: Looping,
+    /// Distance travelled at each of `LENGTH_SAMPLES + 1` evenly spaced `u`
+    distances: Vec<f32>,
+}
+
+impl MotionPath {
+    pub fn new(shape: Shape) -> Self {
+        let mut distances = Vec::with_capacity(LENGTH_SAMPLES + 1);
+        let mut total = 0.0;
+        let mut previous = shape.point(0.0);
+        distances.push(0.0);
+        for i in 1..=LENGTH_SAMPLES {
+            let point = shape.point(i as f32 / LENGTH_SAMPLES as f32);
+            total += distance(&previous, &point);
+            distances.push(total);
+            previous = point;
+        }
+
+        MotionPath {
+            shape,
+            speed: DEFAULT_SPEED,
+            looping: Looping::Loop,
+            distances,
+        }
+    }
+
+    pub fn linear(from: Vector, to: Vector) -> Self {
+        Self::new(Shape::Linear { from, to })
+    }
+
+    pub fn orbit(center: Vector, radius: f32) -> Self {
+        Self::new(Shape::Orbit { center, radius })
+    }
+
+    pub fn figure_eight(center: Vector, radius: f32) -> Self {
+        Self::new(Shape::FigureEight { center, radius })
+    }
+
+    pub fn spline(waypoints: Vec<Vector>) -> Self {
+        Self::new(Shape::Spline { waypoints })
+    }
+
+    pub fn fly_over(center: Vector, height: f32, distance: f32) -> Self {
+        Self::new(Shape::FlyOver { center, height, distance })
+    }
+
+    /// Set the speed in units per second (default 2.0)
+    pub fn speed(mut self, units_per_second: f32) -> Self {
+        self.speed = units_per_second.max(0.0);
+        self
+    }
+
+    /// Set what happens at the end (default [`Looping::Loop`])
+    pub fn looping(mut self, looping: Looping) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    pub fn units_per_second(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn looping_mode(&self) -> Looping {
+        self.looping
+    }
+
+    /// Length of one pass, in units
+    pub fn length(&self) -> f32 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    /// Time one pass takes; `None` for a stationary path or one too slow to measure
+    pub fn duration(&self) -> Option<Duration> {
+        Duration::try_from_secs_f32(self.length() / self.speed).ok()
+    }
+
+    /// Whether a [`Looping::Once`] path has reached its end by `time`
+    ///
+    /// A stationary path is finished from the start; one moving too slowly
+    /// to have a duration never finishes.
+    pub fn is_finished(&self, time: Duration) -> bool {
+        if self.looping != Looping::Once {
+            return false;
+        }
+        match self.duration() {
+            Some(duration) => time >= duration,
+            None => self.speed <= 0.0,
+        }
+    }
+
+    /// Position `time` after starting
+    pub fn position_at(&self, time: Duration) -> Vector {
+        let length = self.length();
+        if length <= 0.0 || self.speed <= 0.0 {
+            return self.shape.point(0.0);
+        }
+        let travelled = time.as_secs_f32() * self.speed;
+        let along = match self.looping {
+            Looping::Once => travelled.min(length),
+            Looping::Loop => travelled.rem_euclid(length),
+            Looping::PingPong => {
+                let phase = travelled.rem_euclid(2.0 * length);
+                if phase > length { 2.0 * length - phase } else { phase }
+            }
+        };
+        self.shape.point(self.parameter_at(along))
+    }
+
+    /// Shape parameter `u` at `along` units from the start
+    fn parameter_at(&self, along: f32) -> f32 {
+        let index = self.distances.partition_point(|d| *d < along).clamp(1, LENGTH_SAMPLES);
+        let (before, after) = (self.distances[index - 1], self.distances[index]);
+        let within = if after > before { (along - before) / (after - before) } else { 0.0 };
+        (index as f32 - 1.0 + within) / LENGTH_SAMPLES as f32
+    }
+}
+
+/// A path being travelled: advance it by the frame time to get positions
+#[derive(Debug, Clone, PartialEq)]
+pub struct Follower {
+    pub path: MotionPath,
+    pub time: Duration,
+}
+
+impl Follower {
+    pub fn new(path: MotionPath) -> Self {
+        Follower {
+            path,
+            time: Duration::ZERO,
+        }
+    }
+
+    /// Move on by `elapsed` and return the new position
+    pub fn advance(&mut self, elapsed: Duration) -> Vector {
+        self.time += elapsed;
+        self.position()
+    }
+
+    pub fn position(&self) -> Vector {
+        self.path.position_at(self.time)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.path.is_finished(self.time)
+    }
+}
+
+fn offset(center: &Vector, x: f32, y: f32, z: f32) -> Vector {
+    Vector {
+        x: center.x + x,
+        y: center.y + y,
+        z: center.z + z,
+    }
+}
+
+fn lerp(a: &Vector, b: &Vector, t: f32) -> Vector {
+    offset(a, (b.x - a.x) * t, (b.y - a.y) * t, (b.z - a.z) * t)
+}
+
+/// Uniform Catmull-Rom through `points`, with the end points repeated so the
+/// curve starts and ends on them
+fn catmull_rom(points: &[Vector], u: f32) -> Vector {
+    match points {
+        [] => Vector { x: 0.0, y: 0.0, z: 0.0 },
+        [only] => only.clone(),
+        _ => {
+            let segments = points.len() - 1;
+            let scaled = u * segments as f32;
+            let segment = (scaled.floor() as usize).min(segments - 1);
+            let t = scaled - segment as f32;
+
+            let p0 = &points[segment.saturating_sub(1)];
+            let p1 = &points[segment];
+            let p2 = &points[segment + 1];
+            let p3 = &points[(segment + 2).min(segments)];
+
+            let (t2, t3) = (t * t, t * t * t);
+            let blend = |a: f32, b: f32, c: f32, d: f32| {
+                0.5 * (2.0 * b + (c - a) * t + (2.0 * a - 5.0 * b + 4.0 * c - d) * t2 + (3.0 * b - a - 3.0 * c + d) * t3)
+            };
+            Vector {
+                x: blend(p0.x, p1.x, p2.x, p3.x),
+                y: blend(p0.y, p1.y, p2.y, p3.y),
+                z: blend(p0.z, p1.z, p2.z, p3.z),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spatial::vector;
+
+    fn assert_near(actual: &Vector, expected: &Vector) {
+        assert!(distance(actual, expected) < 0.01, "{:?} is not near {:?}", actual, expected);
+    }
+
+    fn secs(seconds: f32) -> Duration {
+        Duration::from_secs_f32(seconds)
+    }
+
+    #[test]
+    fn linear_ends_and_middle() {
+        // 10 units at 2 units/s: 5s per pass
+        let path = MotionPath::linear(vector(0.0, 0.0, 0.0), vector(10.0, 0.0, 0.0)).looping(Looping::Once);
+        assert!((path.length() - 10.0).abs() < 1e-3);
+        assert_eq!(path.duration(), Some(secs(5.0)));
+        assert_near(&path.position_at(Duration::ZERO), &vector(0.0, 0.0, 0.0));
+        assert_near(&path.position_at(secs(2.5)), &vector(5.0, 0.0, 0.0));
+        assert_near(&path.position_at(secs(60.0)), &vector(10.0, 0.0, 0.0));
+        assert!(!path.is_finished(secs(4.9)));
+        assert!(path.is_finished(secs(5.0)));
+    }
+
+    #[test]
+    fn loop_point_wraps_to_the_start() {
+        let path = MotionPath::linear(vector(0.0, 0.0, 0.0), vector(10.0, 0.0, 0.0));
+        assert_near(&path.position_at(secs(5.0)), &vector(0.0, 0.0, 0.0));
+        assert_near(&path.position_at(secs(6.0)), &vector(2.0, 0.0, 0.0));
+        assert!(!path.is_finished(secs(60.0)));
+    }
+
+    #[test]
+    fn ping_pong_turns_back_at_the_end() {
+        let path = MotionPath::linear(vector(0.0, 0.0, 0.0), vector(10.0, 0.0, 0.0)).looping(Looping::PingPong);
+        assert_near(&path.position_at(secs(5.0)), &vector(10.0, 0.0, 0.0));
+        assert_near(&path.position_at(secs(6.0)), &vector(8.0, 0.0, 0.0));
+        assert_near(&path.position_at(secs(10.0)), &vector(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn orbit_starts_in_front_and_closes() {
+        let center = vector(1.0, 2.0, 3.0);
+        let path = MotionPath::orbit(center.clone(), 4.0);
+        let start = vector(1.0, 2.0, -1.0);
+        assert_near(&path.position_at(Duration::ZERO), &start);
+        assert!((path.length() - TAU * 4.0).abs() < 0.01);
+        assert_near(&path.position_at(path.duration().unwrap()), &start);
+    }
+
+    #[test]
+    fn spline_passes_through_its_waypoints() {
+        let waypoints = vec![vector(0.0, 0.0, 0.0), vector(5.0, 0.0, 5.0), vector(10.0, 0.0, 0.0)];
+        let shape = Shape::Spline { waypoints: waypoints.clone() };
+        assert_near(&shape.point(0.0), &waypoints[0]);
+        assert_near(&shape.point(0.5), &waypoints[1]);
+        assert_near(&shape.point(1.0), &waypoints[2]);
+    }
+
+    #[test]
+    fn stationary_path_stays_put() {
+        let point = vector(3.0, 0.0, 3.0);
+        let path = MotionPath::linear(point.clone(), point.clone()).looping(Looping::Once);
+        assert_eq!(path.duration(), Some(Duration::ZERO));
+        assert_near(&path.position_at(secs(3.0)), &point);
+
+        let mut follower = Follower::new(MotionPath::orbit(point, 1.0).speed(0.0));
+        follower.advance(secs(1.0));
+        assert_eq!(follower.path.duration(), None);
+        assert!(!follower.is_finished());
+    }
+
+    #[test]
+    fn very_slow_path_has_no_duration() {
+        let path = MotionPath::orbit(vector(0.0, 0.0, 0.0), 4.0).speed(1e-20).looping(Looping::Once);
+        assert_eq!(path.duration(), None);
+        assert!(!path.is_finished(secs(1.0)));
+        assert!(!path.is_finished(Duration::MAX));
+        assert!(path.clone().speed(0.0).is_finished(Duration::ZERO));
+    }
+}
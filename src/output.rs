@@ -0,0 +1,273 @@
+use libfmod::{Init, OutputType, System};
+use std::{
+    env,
+    ffi::{CString, c_void},
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Environment variable selecting the output mode (same values as `--output`)
+pub const OUTPUT_ENV: &str = "FMOD_OUTPUT";
+
+/// Environment variable naming the WAV file written by the wavwriter modes
+pub const OUTPUT_FILE_ENV: &str = "FMOD_OUTPUT_FILE";
+
+/// Which FMOD output driver the demos should use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputMode {
+    /// Let FMOD pick the platform's sound card driver
+    #[default]
+    Default,
+    /// Mix in real time but discard the output
+    NoSound,
+    /// Discard the output and only mix when `update` is called
+    NoSoundNrt,
+    /// Mix in real time and write the output to a WAV file
+    WavWriter,
+    /// Write to a WAV file and only mix when `update` is called
+    WavWriterNrt,
+}
+
+impl OutputMode {
+    pub const NAMES: [&'static str; 5] =
+        ["default", "nosound", "nosound-nrt", "wavwriter", "wavwriter-nrt"];
+
+    /// The output type passed to `set_output`, or `None` to keep FMOD's choice
+    pub fn output_type(&self) -> Option<OutputType> {
+        match self {
+            OutputMode::Default => None,
+            OutputMode::NoSound => Some(OutputType::Nosound),
+            OutputMode::NoSoundNrt => Some(OutputType::NosoundNrt),
+            OutputMode::WavWriter => Some(OutputType::Wavwriter),
+            OutputMode::WavWriterNrt => Some(OutputType::WavwriterNrt),
+        }
+    }
+
+    /// The mode matching an output type chosen directly, `Default` for sound card drivers
+    pub fn from_output_type(output: OutputType) -> Self {
+        match output {
+            OutputType::Nosound => OutputMode::NoSound,
+            OutputType::NosoundNrt => OutputMode::NoSoundNrt,
+            OutputType::Wavwriter => OutputMode::WavWriter,
+            OutputType::WavwriterNrt => OutputMode::WavWriterNrt,
+            _ => OutputMode::Default,
+        }
+    }
+
+    /// True when no sound card is needed
+    pub fn is_headless(&self) -> bool {
+        *self != OutputMode::Default
+    }
+
+    /// True when the mixer runs on its own clock rather than on `update`
+    pub fn is_realtime(&self) -> bool {
+        !matches!(self, OutputMode::NoSoundNrt | OutputMode::WavWriterNrt)
+    }
+
+    pub fn writes_wav(&self) -> bool {
+        matches!(self, OutputMode::WavWriter | OutputMode::WavWriterNrt)
+    }
+}
+
+impl FromStr for OutputMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('_', "-").as_str() {
+            "default" | "auto" | "" => Ok(OutputMode::Default),
+            "nosound" | "none" => Ok(OutputMode::NoSound),
+            "nosound-nrt" | "nosoundnrt" => Ok(OutputMode::NoSoundNrt),
+            "wavwriter" | "wav" => Ok(OutputMode::WavWriter),
+            "wavwriter-nrt" | "wavwriternrt" | "wav-nrt" => Ok(OutputMode::WavWriterNrt),
+            other => Err(format!(
+                "unknown output mode '{}' (expected one of: {})",
+                other,
+                OutputMode::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OutputMode::Default => "default",
+            OutputMode::NoSound => "nosound",
+            OutputMode::NoSoundNrt => "nosound-nrt",
+            OutputMode::WavWriter => "wavwriter",
+            OutputMode::WavWriterNrt => "wavwriter-nrt",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Output settings shared by every example
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutputOptions {
+    pub mode: OutputMode,
+    /// WAV file for the wavwriter modes (FMOD writes `fmodoutput.wav` if unset)
+    pub file: Option<PathBuf>,
+}
+
+impl OutputOptions {
+    /// Usage line for the shared flags, for examples that print help
+    pub const USAGE: &'static str =
+        "[--output default|nosound|nosound-nrt|wavwriter|wavwriter-nrt] [--output-file <path.wav>]";
+
+    /// Read the output options from `FMOD_OUTPUT` / `FMOD_OUTPUT_FILE` only
+    pub fn from_env() -> Result<Self, String> {
+        let mode = match env::var(OUTPUT_ENV) {
+            Ok(value) => value.parse()?,
+            Err(_) => OutputMode::Default,
+        };
+        let file = env::var_os(OUTPUT_FILE_ENV).map(PathBuf::from);
+        Ok(OutputOptions { mode, file })
+    }
+
+    /// Take `--output` and `--output-file` out of `args`, falling back to the environment
+    ///
+    /// The flags are removed so the example's own positional arguments keep
+    /// their indices. `args[0]` (the program name) is never touched.
+    pub fn from_args(args: &mut Vec<String>) -> Result<Self, String> {
+        let mut options = Self::from_env()?;
+
+        let mut i = 1;
+        while i < args.len() {
+            let (flag, inline) = match args[i].split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (args[i].clone(), None),
+            };
+            if flag != "--output" && flag != "--output-file" {
+                i += 1;
+                continue;
+            }
+
+            args.remove(i);
+            let value = match inline {
+                Some(value) => value,
+                None if i < args.len() => args.remove(i),
+                None => return Err(format!("{} needs a value", flag)),
+            };
+
+            if flag == "--output" {
+                options.mode = value.parse()?;
+            } else {
+                options.file = Some(PathBuf::from(value));
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// Parse the process arguments, returning the options and the remaining arguments
+    pub fn from_process_args() -> Result<(Self, Vec<String>), String> {
+        let mut args: Vec<String> = env::args().collect();
+        let options = Self::from_args(&mut args)?;
+        Ok((options, args))
+    }
+
+    pub fn is_headless(&self) -> bool {
+        self.mode.is_headless()
+    }
+
+    pub fn is_realtime(&self) -> bool {
+        self.mode.is_realtime()
+    }
+
+    /// The WAV file argument in the form FMOD expects as `extradriverdata`
+    pub fn driver_data(&self) -> Result<Option<CString>, String> {
+        match (&self.file, self.mode.writes_wav()) {
+            (Some(path), true) => path_to_cstring(path).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Select the output on a core system and initialize it
+    pub fn init_system(&self, system: &System, max_channels: i32, flags: Init) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(output) = self.mode.output_type() {
+            system.set_output(output)?;
+        }
+        let driver_data = self.driver_data()?;
+        system.init(max_channels, flags, driver_data.as_ref().map(|s| s.as_ptr() as *mut c_void))?;
+        Ok(())
+    }
+}
+
+fn path_to_cstring(path: &Path) -> Result<CString, String> {
+    CString::new(path.to_string_lossy().into_owned())
+        .map_err(|_| format!("output file path contains a NUL byte: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn takes_the_flags_out_of_the_arguments() {
+        let mut list = args(&["demo", "explosion", "--output", "wavwriter-nrt", "--output-file", "out.wav", "3"]);
+        let options = OutputOptions::from_args(&mut list).unwrap();
+        assert_eq!(options.mode, OutputMode::WavWriterNrt);
+        assert_eq!(options.file, Some(PathBuf::from("out.wav")));
+        assert_eq!(list, args(&["demo", "explosion", "3"]));
+    }
+
+    #[test]
+    fn accepts_inline_values() {
+        let mut list = args(&["demo", "--output=nosound", "--output-file=a=b.wav"]);
+        let options = OutputOptions::from_args(&mut list).unwrap();
+        assert_eq!(options.mode, OutputMode::NoSound);
+        assert_eq!(options.file, Some(PathBuf::from("a=b.wav")));
+        assert_eq!(list, args(&["demo"]));
+    }
+
+    #[test]
+    fn leaves_other_arguments_alone() {
+        let mut list = args(&["--output", "demo", "--outputs", "x=1"]);
+        let options = OutputOptions::from_args(&mut list).unwrap();
+        // Only the environment applies, and the program name is never a flag
+        assert_eq!(options, OutputOptions::from_env().unwrap());
+        assert_eq!(list, args(&["--output", "demo", "--outputs", "x=1"]));
+    }
+
+    #[test]
+    fn rejects_missing_and_unknown_values() {
+        let error = OutputOptions::from_args(&mut args(&["demo", "--output"])).unwrap_err();
+        assert_eq!(error, "--output needs a value");
+        let error = OutputOptions::from_args(&mut args(&["demo", "--output", "speakers"])).unwrap_err();
+        assert!(error.starts_with("unknown output mode 'speakers'"));
+    }
+
+    #[test]
+    fn mode_names_round_trip() {
+        for name in OutputMode::NAMES {
+            assert_eq!(name.parse::<OutputMode>().unwrap().to_string(), name);
+        }
+        assert_eq!("WAV_NRT".parse::<OutputMode>().unwrap(), OutputMode::WavWriterNrt);
+    }
+
+    #[test]
+    fn output_types_map_back_to_modes() {
+        for mode in [OutputMode::NoSound, OutputMode::NoSoundNrt, OutputMode::WavWriter, OutputMode::WavWriterNrt] {
+            assert_eq!(OutputMode::from_output_type(mode.output_type().unwrap()), mode);
+        }
+        assert_eq!(OutputMode::from_output_type(OutputType::Autodetect), OutputMode::Default);
+    }
+
+    #[test]
+    fn driver_data_only_for_wav_modes() {
+        let mut options = OutputOptions {
+            mode: OutputMode::NoSoundNrt,
+            file: Some(PathBuf::from("out.wav")),
+        };
+        assert_eq!(options.driver_data().unwrap(), None);
+        options.mode = OutputMode::WavWriter;
+        assert_eq!(options.driver_data().unwrap(), Some(CString::new("out.wav").unwrap()));
+        options.file = Some(PathBuf::from("bad\0.wav"));
+        assert!(options.driver_data().is_err());
+    }
+}
@@ -0,0 +1,502 @@
+use libfmod::{EventDescription, EventInstance, ParameterId, Studio};
+use std::fmt;
+
+use crate::error::{FmodError, write_suggestions};
+use crate::inspect::{self, ParameterInfo};
+
+/// Most suggestions listed for an unknown parameter name
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Errors raised by the checked parameter setters
+#[derive(Debug)]
+pub enum ParameterError {
+    /// No parameter by that name; `owner` is the event path or "global"
+    Unknown {
+        owner: String,
+        name: String,
+        available: Vec<String>,
+        suggestions: Vec<String>,
+    },
+    /// The parameter exists but FMOD drives it (read-only or automatic)
+    NotSettable { owner: String, name: String, kind: String },
+    /// A label the parameter does not have (or the parameter is not labeled)
+    UnknownLabel {
+        owner: String,
+        name: String,
+        label: String,
+        labels: Vec<String>,
+        suggestions: Vec<String>,
+    },
+    Fmod { context: String, error: libfmod::Error },
+}
+
+impl fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterError::Unknown {
+                owner,
+                name,
+                available,
+                suggestions,
+            } => {
+                write!(f, "{} has no parameter \"{}\"", owner, name)?;
+                write_suggestions(f, suggestions)?;
+                if available.is_empty() {
+                    write!(f, " (it has no parameters)")
+                } else {
+                    write!(f, " (available: {})", available.join(", "))
+                }
+            }
+            ParameterError::NotSettable { owner, name, kind } => {
+                write!(f, "{}: parameter \"{}\" is {} and cannot be set", owner, name, kind)
+            }
+            ParameterError::UnknownLabel {
+                owner,
+                name,
+                label,
+                labels,
+                suggestions,
+            } => {
+                if labels.is_empty() {
+                    return write!(f, "{}: parameter \"{}\" has no labels; give a number instead of \"{}\"", owner, name, label);
+                }
+                write!(f, "{}: parameter \"{}\" has no label \"{}\"", owner, name, label)?;
+                write_suggestions(f, suggestions)?;
+                write!(f, " (labels: {})", labels.join(", "))
+            }
+            ParameterError::Fmod { context, error } => write!(f, "{} failed: {:?}", context, error),
+        }
+    }
+}
+
+impl std::error::Error for ParameterError {}
+
+impl FmodError for ParameterError {
+    fn from_fmod(context: String, error: libfmod::Error) -> Self {
+        ParameterError::Fmod { context, error }
+    }
+}
+
+/// The parameters of one event, or the global ones, for checking names
+/// before they reach FMOD
+///
+/// `set_parameter_by_name` only reports `ERR_EVENT_NOTFOUND` for a bad
+/// name, and callers tend to drop that with `.ok()`. Lookups here are
+/// case-insensitive, as FMOD's are, and a miss lists the closest names.
+#[derive(Debug, Clone)]
+pub struct Parameters {
+    owner: String,
+    parameters: Vec<ParameterInfo>,
+}
+
+impl Parameters {
+    pub fn of_event(event: &EventDescription) -> Result<Self, ParameterError> {
+        let owner = event
+            .get_path()
+            .or_else(|_| event.get_id().map(|id| inspect::format_guid(&id)))
+            .map_err(ParameterError::fmod("EventDescription::get_path"))?;
+        let parameters = inspect::event_parameters(event).map_err(ParameterError::fmod(format!("{}: parameters", owner)))?;
+        Ok(Parameters { owner, parameters })
+    }
+
+    pub fn of_instance(instance: &EventInstance) -> Result<Self, ParameterError> {
+        let event = instance
+            .get_description()
+            .map_err(ParameterError::fmod("EventInstance::get_description"))?;
+        Self::of_event(&event)
+    }
+
+    /// Studio-level (global) parameters
+    pub fn global(studio: &Studio) -> Result<Self, ParameterError> {
+        Ok(Parameters {
+            owner: "global".to_string(),
+            parameters: inspect::global_parameters(studio).map_err(ParameterError::fmod("global parameters"))?,
+        })
+    }
+
+    /// Event path (or GUID), or "global"
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    pub fn all(&self) -> &[ParameterInfo] {
+        &self.parameters
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ParameterInfo> {
+        self.parameters.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Look up `name`, failing with the available names and closest matches
+    pub fn find(&self, name: &str) -> Result<&ParameterInfo, ParameterError> {
+        self.get(name).ok_or_else(|| {
+            let available: Vec<String> = self.parameters.iter().map(|p| p.name.clone()).collect();
+            ParameterError::Unknown {
+                owner: self.owner.clone(),
+                name: name.to_string(),
+                suggestions: closest_matches(name, available.iter().map(String::as_str))
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect(),
+                available,
+            }
+        })
+    }
+
+    /// Like [`find`](Self::find), but also rejects read-only and automatic parameters
+    pub fn find_settable(&self, name: &str) -> Result<&ParameterInfo, ParameterError> {
+        let parameter = self.find(name)?;
+        if parameter.is_settable() {
+            Ok(parameter)
+        } else {
+            Err(not_settable(&self.owner, parameter))
+        }
+    }
+}
+
+fn not_settable(owner: &str, parameter: &ParameterInfo) -> ParameterError {
+    let kind = if parameter.has_flag("automatic") {
+        format!("automatic ({})", parameter.kind)
+    } else {
+        "read-only".to_string()
+    };
+    ParameterError::NotSettable {
+        owner: owner.to_string(),
+        name: parameter.name.clone(),
+        kind,
+    }
+}
+
+/// Set an event instance's parameter, checking the name first
+///
+/// Returns the parameter's description, e.g. to compare the value against
+/// its range.
+pub fn set_parameter(instance: &EventInstance, name: &str, value: f32) -> Result<ParameterInfo, ParameterError> {
+    let parameters = Parameters::of_instance(instance)?;
+    let parameter = parameters.find_settable(name)?;
+    instance
+        .set_parameter_by_name(&parameter.name, value, false)
+        .map_err(ParameterError::fmod(format!("{}: set_parameter_by_name(\"{}\")", parameters.owner, parameter.name)))?;
+    Ok(parameter.clone())
+}
+
+/// Set a global parameter, checking the name first
+pub fn set_global_parameter(studio: &Studio, name: &str, value: f32) -> Result<ParameterInfo, ParameterError> {
+    let parameters = Parameters::global(studio)?;
+    let parameter = parameters.find_settable(name)?;
+    studio
+        .set_parameter_by_name(&parameter.name, value, false)
+        .map_err(ParameterError::fmod(format!("Studio::set_parameter_by_name(\"{}\")", parameter.name)))?;
+    Ok(parameter.clone())
+}
+
+/// A value to set: a number, or one of a labeled parameter's labels
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParameterValue {
+    Number(f32),
+    Label(String),
+}
+
+impl ParameterValue {
+    /// A number if `text` parses as one, a label otherwise
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        match text.parse::<f32>() {
+            Ok(number) => ParameterValue::Number(number),
+            Err(_) => ParameterValue::Label(text.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for ParameterValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterValue::Number(number) => write!(f, "{}", number),
+            ParameterValue::Label(label) => write!(f, "{}", label),
+        }
+    }
+}
+
+/// A global parameter with the values the Studio system currently holds
+#[derive(Debug, Clone)]
+pub struct GlobalParameter {
+    pub info: ParameterInfo,
+    pub id: ParameterId,
+    /// Value last set by game code
+    pub value: f32,
+    /// Value in use after seek speed and modulation
+    pub final_value: f32,
+}
+
+impl GlobalParameter {
+    /// Every global parameter in the loaded banks, sorted by name
+    pub fn list(studio: &Studio) -> Result<Vec<GlobalParameter>, ParameterError> {
+        let count = studio
+            .get_parameter_description_count()
+            .map_err(ParameterError::fmod("Studio::get_parameter_description_count"))?;
+        let descriptions = studio
+            .get_parameter_description_list(count)
+            .map_err(ParameterError::fmod("Studio::get_parameter_description_list"))?;
+        let mut parameters = Vec::with_capacity(descriptions.len());
+        for description in &descriptions {
+            let info = inspect::parameter_info(description, |label| {
+                studio.get_parameter_label_by_id(description.id, label)
+            });
+            let mut parameter = GlobalParameter {
+                info,
+                id: description.id,
+                value: description.defaultvalue,
+                final_value: description.defaultvalue,
+            };
+            parameter.refresh(studio)?;
+            parameters.push(parameter);
+        }
+        parameters.sort_by(|a, b| a.info.name.cmp(&b.info.name));
+        Ok(parameters)
+    }
+
+    /// Find a global parameter by name (ignoring case) or by ID as shown by
+    /// [`id_string`](Self::id_string)
+    pub fn find(studio: &Studio, key: &str) -> Result<GlobalParameter, ParameterError> {
+        let mut parameters = Self::list(studio)?;
+        let wanted = parse_parameter_id(key);
+        let position = parameters.iter().position(|p| {
+            p.info.name.eq_ignore_ascii_case(key.trim())
+                || wanted.is_some_and(|id| id.data_1 == p.id.data_1 && id.data_2 == p.id.data_2)
+        });
+        match position {
+            Some(index) => Ok(parameters.swap_remove(index)),
+            None => {
+                let available: Vec<String> = parameters.into_iter().map(|p| p.info.name).collect();
+                Err(ParameterError::Unknown {
+                    owner: "global".to_string(),
+                    name: key.to_string(),
+                    suggestions: closest_matches(key, available.iter().map(String::as_str))
+                        .into_iter()
+                        .map(str::to_string)
+                        .collect(),
+                    available,
+                })
+            }
+        }
+    }
+
+    pub fn id_string(&self) -> String {
+        format_parameter_id(&self.id)
+    }
+
+    /// Label of the current value, if the parameter is labeled
+    pub fn label(&self) -> Option<&str> {
+        self.info.label(self.value)
+    }
+
+    /// Re-read the current and final values
+    pub fn refresh(&mut self, studio: &Studio) -> Result<(), ParameterError> {
+        (self.value, self.final_value) = studio
+            .get_parameter_by_id(self.id)
+            .map_err(ParameterError::fmod(format!("Studio::get_parameter_by_id(\"{}\")", self.info.name)))?;
+        Ok(())
+    }
+
+    /// Set a number or a label, then re-read the values
+    ///
+    /// Read-only and automatic parameters are rejected, as are labels the
+    /// parameter does not have.
+    pub fn set(&mut self, studio: &Studio, value: &ParameterValue) -> Result<(), ParameterError> {
+        if !self.info.is_settable() {
+            return Err(not_settable("global", &self.info));
+        }
+        let context = format!("Studio::set_parameter_by_id(\"{}\")", self.info.name);
+        match value {
+            ParameterValue::Number(number) => studio
+                .set_parameter_by_id(self.id, *number, false)
+                .map_err(ParameterError::fmod(context))?,
+            ParameterValue::Label(label) => {
+                let Some(known) = self.info.labels.iter().find(|l| l.eq_ignore_ascii_case(label)) else {
+                    return Err(ParameterError::UnknownLabel {
+                        owner: "global".to_string(),
+                        name: self.info.name.clone(),
+                        label: label.clone(),
+                        labels: self.info.labels.clone(),
+                        suggestions: closest_matches(label, self.info.labels.iter().map(String::as_str))
+                            .into_iter()
+                            .map(str::to_string)
+                            .collect(),
+                    });
+                };
+                studio
+                    .set_parameter_by_id_with_label(self.id, known, false)
+                    .map_err(ParameterError::fmod(context))?
+            }
+        }
+        self.refresh(studio)
+    }
+}
+
+/// Set a global parameter given by name or ID to a number or label, as typed
+///
+/// `edit_global_parameter(studio, "Weather", "Rainy")` or
+/// `edit_global_parameter(studio, "0badf00d:12345678", "0.5")`.
+pub fn edit_global_parameter(studio: &Studio, key: &str, value: &str) -> Result<GlobalParameter, ParameterError> {
+    let mut parameter = GlobalParameter::find(studio, key)?;
+    parameter.set(studio, &ParameterValue::parse(value))?;
+    Ok(parameter)
+}
+
+/// A parameter ID as two 8-digit hex numbers, `0badf00d:12345678`
+pub fn format_parameter_id(id: &ParameterId) -> String {
+    format!("{:08x}:{:08x}", id.data_1, id.data_2)
+}
+
+/// Parse an ID written by [`format_parameter_id`], with or without braces or `0x`
+pub fn parse_parameter_id(text: &str) -> Option<ParameterId> {
+    let text = text.trim().trim_start_matches('{').trim_end_matches('}');
+    let (first, second) = text.split_once([':', '-', ','])?;
+    let hex = |part: &str| {
+        let part = part.trim();
+        let part = part.strip_prefix("0x").unwrap_or(part);
+        u32::from_str_radix(part, 16).ok()
+    };
+    Some(ParameterId {
+        data_1: hex(first)?,
+        data_2: hex(second)?,
+    })
+}
+
+/// Candidates that look like a misspelling of `name`, closest first
+///
+/// Matches ignore case and allow roughly one edit per three characters;
+/// a candidate containing `name` (or contained in it) also counts, so
+/// "EngineRPM" suggests "RPM".
+pub fn closest_matches<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let wanted = name.to_lowercase();
+    let allowed = (wanted.chars().count() / 3).max(1);
+    let mut matches: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let lower = candidate.to_lowercase();
+            let distance = edit_distance(&wanted, &lower);
+            let contains = !lower.is_empty() && (lower.contains(&wanted) || wanted.contains(&lower));
+            (distance <= allowed || contains).then_some((distance, candidate))
+        })
+        .collect();
+    matches.sort();
+    matches.dedup_by(|a, b| a.1 == b.1);
+    matches.into_iter().take(MAX_SUGGESTIONS).map(|(_, candidate)| candidate).collect()
+}
+
+/// Edit distance by characters, counting a swap of neighbours as one edit
+/// ("RMP" is one edit from "RPM")
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut before: Vec<usize> = Vec::new();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (previous[j] + 1).min(row[j - 1] + 1).min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(before[j - 2] + 1);
+            }
+        }
+        before = std::mem::replace(&mut previous, row);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameter(name: &str, flags: Vec<&'static str>) -> ParameterInfo {
+        ParameterInfo {
+            name: name.to_string(),
+            minimum: 0.0,
+            maximum: 1.0,
+            default: 0.0,
+            kind: "GameControlled".to_string(),
+            flags,
+            labels: Vec::new(),
+        }
+    }
+
+    fn engine() -> Parameters {
+        Parameters {
+            owner: "event:/Vehicles/Car Engine".to_string(),
+            parameters: vec![
+                parameter("RPM", Vec::new()),
+                parameter("Load", Vec::new()),
+                ParameterInfo {
+                    kind: "AutomaticDistance".to_string(),
+                    ..parameter("Distance", vec!["readonly", "automatic"])
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn edit_distance_counts_swaps_as_one() {
+        assert_eq!(edit_distance("rpm", "rpm"), 0);
+        assert_eq!(edit_distance("rmp", "rpm"), 1);
+        assert_eq!(edit_distance("rp", "rpm"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn closest_matches_finds_typos_and_substrings() {
+        let names = ["RPM", "Load", "Surface"];
+        assert_eq!(closest_matches("RMP", names), vec!["RPM"]);
+        assert_eq!(closest_matches("rpm", names), vec!["RPM"]);
+        assert_eq!(closest_matches("EngineRPM", names), vec!["RPM"]);
+        assert_eq!(closest_matches("Surfce", names), vec!["Surface"]);
+        assert!(closest_matches("Weather", names).is_empty());
+    }
+
+    #[test]
+    fn closest_matches_stops_at_the_limit() {
+        let names = ["ab1", "ab2", "ab3", "ab4"];
+        assert_eq!(closest_matches("ab", names).len(), MAX_SUGGESTIONS);
+    }
+
+    #[test]
+    fn parameter_ids_round_trip() {
+        let id = ParameterId {
+            data_1: 0x0bad_f00d,
+            data_2: 0x1234_5678,
+        };
+        let text = format_parameter_id(&id);
+        assert_eq!(text, "0badf00d:12345678");
+        for written in [text.as_str(), "{0badf00d-12345678}", "0x0badf00d, 0x12345678"] {
+            let parsed = parse_parameter_id(written).unwrap();
+            assert_eq!((parsed.data_1, parsed.data_2), (id.data_1, id.data_2), "{}", written);
+        }
+        assert!(parse_parameter_id("0badf00d").is_none());
+        assert!(parse_parameter_id("xyz:123").is_none());
+    }
+
+    #[test]
+    fn find_is_case_insensitive_and_suggests() {
+        let parameters = engine();
+        assert_eq!(parameters.find("rpm").unwrap().name, "RPM");
+        let error = parameters.find("RMP").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "event:/Vehicles/Car Engine has no parameter \"RMP\"; did you mean \"RPM\"? (available: RPM, Load, Distance)"
+        );
+    }
+
+    #[test]
+    fn find_settable_rejects_automatic_parameters() {
+        let error = engine().find_settable("distance").unwrap_err();
+        assert!(matches!(error, ParameterError::NotSettable { .. }));
+        assert!(error.to_string().contains("automatic (AutomaticDistance)"));
+    }
+
+    #[test]
+    fn values_parse_as_numbers_or_labels() {
+        assert_eq!(ParameterValue::parse(" 0.5 "), ParameterValue::Number(0.5));
+        assert_eq!(ParameterValue::parse("Rainy"), ParameterValue::Label("Rainy".to_string()));
+    }
+}
@@ -0,0 +1,279 @@
+use libfmod::Vector;
+use std::f32::consts::TAU;
+
+use crate::spatial::Listener;
+
+/// Metres per column at the default scale
+pub const DEFAULT_SCALE: f32 = 1.0;
+
+/// Metres between grid dots
+pub const GRID_SPACING: f32 = 10.0;
+
+/// Facing arrows for yaw 0, 45, 90, ... with their cell offsets
+const ARROWS: [(char, isize, isize); 8] = [
+    ('↑', 0, -1),
+    ('↗', 1, -1),
+    ('→', 1, 0),
+    ('↘', 1, 1),
+    ('↓', 0, 1),
+    ('↙', -1, 1),
+    ('←', -1, 0),
+    ('↖', -1, -1),
+];
+
+/// What a radar cell shows, lowest priority first
+///
+/// When several things fall on the same cell the highest one is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Mark {
+    Empty,
+    Grid,
+    MaxDistance,
+    MinDistance,
+    Facing,
+    /// A source outside the map, pinned to its edge
+    OffRadar,
+    Source,
+    Listener,
+    SelectedListener,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub mark: Mark,
+}
+
+const EMPTY: Cell = Cell { glyph: ' ', mark: Mark::Empty };
+
+/// A 3D source to plot, with the attenuation range of its event
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blip {
+    pub position: Vector,
+    pub min_distance: f32,
+    pub max_distance: f32,
+    pub glyph: char,
+}
+
+/// Fixed-scale top-down map of listeners and sources
+///
+/// Seen from above with yaw 0 (-Z) pointing up the screen. FMOD's axes are
+/// left-handed, so -X is to the right of a listener facing -Z and is drawn
+/// on the right. Rows cover twice the distance of columns, since terminal
+/// cells are about twice as tall as they are wide.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Radar {
+    pub width: usize,
+    pub height: usize,
+    /// Metres per column
+    pub scale: f32,
+}
+
+impl Radar {
+    pub fn new(width: usize, height: usize) -> Self {
+        Radar {
+            width: width.max(1),
+            height: height.max(1),
+            scale: DEFAULT_SCALE,
+        }
+    }
+
+    pub fn scale(mut self, metres_per_column: f32) -> Self {
+        self.scale = metres_per_column.max(0.01);
+        self
+    }
+
+    /// Cell of a world position, which may lie outside the map
+    fn cell(&self, center: &Vector, position: &Vector) -> (isize, isize) {
+        let column = (center.x - position.x) / self.scale;
+        let row = (position.z - center.z) / (2.0 * self.scale);
+        (
+            (self.width / 2) as isize + column.round() as isize,
+            (self.height / 2) as isize + row.round() as isize,
+        )
+    }
+
+    fn contains(&self, (column, row): (isize, isize)) -> bool {
+        (0..self.width as isize).contains(&column) && (0..self.height as isize).contains(&row)
+    }
+
+    /// Draw a map centred on `center`
+    ///
+    /// Listeners are drawn as their number (1-based) with an arrow for the
+    /// direction they face; each blip as its glyph inside rings at its min
+    /// and max distance. Returns `height` rows of `width` cells.
+    pub fn render(&self, center: &Vector, listeners: &[Listener], selected: usize, blips: &[Blip]) -> Vec<Vec<Cell>> {
+        let mut cells = vec![vec![EMPTY; self.width]; self.height];
+        let mut put = |(column, row): (isize, isize), glyph: char, mark: Mark| {
+            if self.contains((column, row)) {
+                let cell = &mut cells[row as usize][column as usize];
+                if mark >= cell.mark {
+                    *cell = Cell { glyph, mark };
+                }
+            }
+        };
+
+        // Grid dots at fixed world coordinates, so the grid scrolls with the centre
+        let origin = self.cell(center, &Vector { x: 0.0, y: 0.0, z: 0.0 });
+        let columns = (GRID_SPACING / self.scale).round().max(1.0) as isize;
+        let rows = (GRID_SPACING / (2.0 * self.scale)).round().max(1.0) as isize;
+        for row in 0..self.height as isize {
+            for column in 0..self.width as isize {
+                if (column - origin.0).rem_euclid(columns) == 0 && (row - origin.1).rem_euclid(rows) == 0 {
+                    put((column, row), '·', Mark::Grid);
+                }
+            }
+        }
+
+        for blip in blips {
+            for (radius, glyph, mark) in [
+                (blip.max_distance, '∙', Mark::MaxDistance),
+                (blip.min_distance, '∘', Mark::MinDistance),
+            ] {
+                if radius <= 0.0 {
+                    continue;
+                }
+                // Enough samples that neighbouring points land in adjacent cells
+                let samples = ((TAU * radius / self.scale) * 2.0).ceil().clamp(8.0, 4096.0) as usize;
+                for i in 0..samples {
+                    let (sin, cos) = (TAU * i as f32 / samples as f32).sin_cos();
+                    let point = Vector {
+                        x: blip.position.x + radius * cos,
+                        y: blip.position.y,
+                        z: blip.position.z + radius * sin,
+                    };
+                    put(self.cell(center, &point), glyph, mark);
+                }
+            }
+
+            let (column, row) = self.cell(center, &blip.position);
+            if self.contains((column, row)) {
+                put((column, row), blip.glyph, Mark::Source);
+            } else {
+                let pinned = (
+                    column.clamp(0, self.width as isize - 1),
+                    row.clamp(0, self.height as isize - 1),
+                );
+                put(pinned, blip.glyph, Mark::OffRadar);
+            }
+        }
+
+        for (index, listener) in listeners.iter().enumerate() {
+            let (column, row) = self.cell(center, &listener.position);
+            let (arrow, dx, dy) = facing_arrow(listener.yaw);
+            put((column + dx, row + dy), arrow, Mark::Facing);
+
+            let glyph = char::from_digit(index as u32 + 1, 36).unwrap_or('L');
+            let mark = if index == selected { Mark::SelectedListener } else { Mark::Listener };
+            put((column, row), glyph, mark);
+        }
+
+        cells
+    }
+}
+
+/// Arrow glyph and cell offset for a heading in degrees
+pub fn facing_arrow(yaw_degrees: f32) -> (char, isize, isize) {
+    let octant = (yaw_degrees.rem_euclid(360.0) / 45.0).round() as usize % ARROWS.len();
+    ARROWS[octant]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spatial::{ZERO, vector};
+
+    /// 11 x 7 cells, so the centre is column 5, row 3
+    const RADAR: Radar = Radar { width: 11, height: 7, scale: DEFAULT_SCALE };
+
+    fn blip(x: f32, z: f32, glyph: char) -> Blip {
+        Blip {
+            position: vector(x, 0.0, z),
+            min_distance: 0.0,
+            max_distance: 0.0,
+            glyph,
+        }
+    }
+
+    fn listener(x: f32, z: f32, yaw: f32) -> Listener {
+        Listener { yaw, ..Listener::new(vector(x, 0.0, z)) }
+    }
+
+    fn at(cells: &[Vec<Cell>], column: usize, row: usize) -> Cell {
+        cells[row][column]
+    }
+
+    #[test]
+    fn negative_x_is_drawn_on_the_right() {
+        let cells = RADAR.render(&ZERO, &[], 0, &[blip(-3.0, 0.0, 'a'), blip(3.0, 0.0, 'b')]);
+        assert_eq!(cells.len(), 7);
+        assert!(cells.iter().all(|row| row.len() == 11));
+        assert_eq!(at(&cells, 8, 3), Cell { glyph: 'a', mark: Mark::Source });
+        assert_eq!(at(&cells, 2, 3), Cell { glyph: 'b', mark: Mark::Source });
+    }
+
+    #[test]
+    fn rows_cover_twice_the_column_scale() {
+        // -Z is up the screen
+        let cells = RADAR.render(&ZERO, &[], 0, &[blip(0.0, -4.0, 'a'), blip(0.0, 4.0, 'b')]);
+        assert_eq!(at(&cells, 5, 1).glyph, 'a');
+        assert_eq!(at(&cells, 5, 5).glyph, 'b');
+
+        let zoomed = RADAR.scale(2.0).render(&ZERO, &[], 0, &[blip(-4.0, 4.0, 'a')]);
+        assert_eq!(at(&zoomed, 7, 4).glyph, 'a');
+
+        // Centred elsewhere, everything shifts with the centre
+        let moved = RADAR.render(&vector(-4.0, 0.0, 4.0), &[], 0, &[blip(-4.0, 4.0, 'a')]);
+        assert_eq!(at(&moved, 5, 3).glyph, 'a');
+    }
+
+    #[test]
+    fn arrows_point_to_the_nearest_octant() {
+        assert_eq!(facing_arrow(0.0), ('↑', 0, -1));
+        assert_eq!(facing_arrow(45.0), ('↗', 1, -1));
+        assert_eq!(facing_arrow(90.0), ('→', 1, 0));
+        assert_eq!(facing_arrow(180.0), ('↓', 0, 1));
+        assert_eq!(facing_arrow(270.0), ('←', -1, 0));
+        assert_eq!(facing_arrow(-90.0), facing_arrow(270.0));
+        assert_eq!(facing_arrow(22.0).0, '↑');
+        assert_eq!(facing_arrow(23.0).0, '↗');
+        assert_eq!(facing_arrow(350.0).0, '↑');
+        assert_eq!(facing_arrow(720.0 + 135.0).0, '↘');
+
+        // Yaw 90 faces -X, which is drawn on the right
+        let cells = RADAR.render(&ZERO, &[listener(0.0, 0.0, 90.0)], 0, &[]);
+        assert_eq!(at(&cells, 5, 3), Cell { glyph: '1', mark: Mark::SelectedListener });
+        assert_eq!(at(&cells, 6, 3), Cell { glyph: '→', mark: Mark::Facing });
+    }
+
+    #[test]
+    fn off_radar_sources_are_pinned_to_the_edge() {
+        let cells = RADAR.render(&ZERO, &[], 0, &[blip(-100.0, 0.0, 'a'), blip(100.0, -100.0, 'b')]);
+        assert_eq!(at(&cells, 10, 3), Cell { glyph: 'a', mark: Mark::OffRadar });
+        assert_eq!(at(&cells, 0, 0), Cell { glyph: 'b', mark: Mark::OffRadar });
+
+        // A source on the map wins over one pinned to the same cell
+        let cells = RADAR.render(&ZERO, &[], 0, &[blip(-5.0, 0.0, 'a'), blip(-100.0, 0.0, 'b')]);
+        assert_eq!(at(&cells, 10, 3), Cell { glyph: 'a', mark: Mark::Source });
+    }
+
+    #[test]
+    fn higher_marks_are_drawn_over_lower_ones() {
+        // The grid has a dot at the origin, under the source
+        assert_eq!(at(&RADAR.render(&ZERO, &[], 0, &[]), 5, 3), Cell { glyph: '·', mark: Mark::Grid });
+        let ringed = [Blip { min_distance: 2.0, max_distance: 4.0, ..blip(0.0, 0.0, 'a') }];
+        let cells = RADAR.render(&ZERO, &[], 0, &ringed);
+        assert_eq!(at(&cells, 5, 3), Cell { glyph: 'a', mark: Mark::Source });
+        assert_eq!(at(&cells, 7, 3), Cell { glyph: '∘', mark: Mark::MinDistance });
+        assert_eq!(at(&cells, 9, 3), Cell { glyph: '∙', mark: Mark::MaxDistance });
+
+        // Listeners cover sources and the selected listener covers the others, in either order
+        let listeners = [listener(0.0, 0.0, 0.0), listener(0.0, 0.0, 0.0)];
+        assert_eq!(at(&RADAR.render(&ZERO, &listeners, 0, &ringed), 5, 3).glyph, '1');
+        assert_eq!(at(&RADAR.render(&ZERO, &listeners, 1, &ringed), 5, 3).glyph, '2');
+        let unselected = RADAR.render(&ZERO, &listeners[..1], 5, &ringed);
+        assert_eq!(at(&unselected, 5, 3), Cell { glyph: '1', mark: Mark::Listener });
+        // The facing arrow covers the ring it points at
+        assert_eq!(at(&unselected, 5, 2), Cell { glyph: '↑', mark: Mark::Facing });
+    }
+}
@@ -0,0 +1,272 @@
+use libfmod::{EventDescription, EventInstance, PlaybackState, StopMode};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Sustaining for longer than this counts as stuck
+pub const DEFAULT_SUSTAIN_LIMIT: Duration = Duration::from_secs(10);
+
+/// What an instance is doing right now
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackInfo {
+    pub state: PlaybackState,
+    /// Timeline position
+    pub position: Duration,
+    /// Length of the event's timeline; `None` when it has none
+    pub length: Option<Duration>,
+    pub volume: f32,
+    pub pitch: f32,
+    pub paused: bool,
+}
+
+impl PlaybackInfo {
+    pub fn read(instance: &EventInstance) -> Result<Self, libfmod::Error> {
+        let length_ms = instance.get_description()?.get_length()?;
+        let (volume, _final_volume) = instance.get_volume()?;
+        let (pitch, _final_pitch) = instance.get_pitch()?;
+        Ok(PlaybackInfo {
+            state: instance.get_playback_state()?,
+            position: Duration::from_millis(instance.get_timeline_position()?.max(0) as u64),
+            length: (length_ms > 0).then(|| Duration::from_millis(length_ms as u64)),
+            volume,
+            pitch,
+            paused: instance.get_paused()?,
+        })
+    }
+
+    /// Position through the timeline, 0.0 to 1.0, when it has a length
+    pub fn progress(&self) -> Option<f32> {
+        self.length
+            .map(|length| (self.position.as_secs_f32() / length.as_secs_f32()).clamp(0.0, 1.0))
+    }
+}
+
+/// An instance the registry knows about
+#[derive(Debug, Clone)]
+pub struct TrackedInstance {
+    pub instance: EventInstance,
+    /// `event:/` path of its description (or the GUID without a strings bank)
+    pub path: String,
+    /// Who created it, e.g. the harness panel or test that started it
+    pub owner: String,
+    pub created: Instant,
+    /// When it was released through the registry
+    pub released: Option<Instant>,
+    /// Playback state at the last update; `None` once FMOD has destroyed it
+    pub state: Option<PlaybackState>,
+    /// When it entered `Sustaining`, if it still is
+    pub sustaining_since: Option<Instant>,
+}
+
+impl TrackedInstance {
+    pub fn age(&self) -> Duration {
+        self.created.elapsed()
+    }
+
+    /// How long it has been sitting on a sustain point
+    pub fn sustaining_for(&self) -> Option<Duration> {
+        self.sustaining_since.map(|since| since.elapsed())
+    }
+}
+
+/// Live instances of one description, by state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InstanceCounts {
+    pub live: usize,
+    pub starting: usize,
+    pub playing: usize,
+    pub sustaining: usize,
+    pub stopping: usize,
+    pub stopped: usize,
+    /// Released but still playing out (FMOD destroys them once stopped)
+    pub released: usize,
+}
+
+/// Why an instance is reported by [`InstanceRegistry::leaks`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LeakKind {
+    /// Still alive and never released
+    NeverReleased,
+    /// Held on a sustain point for this long; it will not stop on its own
+    Sustaining(Duration),
+}
+
+#[derive(Debug, Clone)]
+pub struct Leak {
+    pub path: String,
+    pub owner: String,
+    pub age: Duration,
+    pub state: Option<PlaybackState>,
+    pub kind: LeakKind,
+}
+
+impl fmt::Display for Leak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            LeakKind::NeverReleased => write!(f, "{} (owner {}) never released", self.path, self.owner)?,
+            LeakKind::Sustaining(held) => write!(
+                f,
+                "{} (owner {}) stuck in Sustaining for {:.1}s",
+                self.path,
+                self.owner,
+                held.as_secs_f32()
+            )?,
+        }
+        let state = self.state.map_or("destroyed".to_string(), |state| format!("{:?}", state));
+        write!(f, ", {} after {:.1}s", state, self.age.as_secs_f32())
+    }
+}
+
+/// Tracks every event instance created through it, from creation until
+/// FMOD destroys it
+///
+/// Create and release instances through the registry and call
+/// [`update`](Self::update) once a frame; [`leaks`](Self::leaks) then lists
+/// instances that were never released or are stuck on a sustain point.
+#[derive(Debug)]
+pub struct InstanceRegistry {
+    instances: Vec<TrackedInstance>,
+    sustain_limit: Duration,
+}
+
+impl Default for InstanceRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InstanceRegistry {
+    pub fn new() -> Self {
+        InstanceRegistry {
+            instances: Vec::new(),
+            sustain_limit: DEFAULT_SUSTAIN_LIMIT,
+        }
+    }
+
+    /// Set how long an instance may sustain before it is reported (default 10s)
+    pub fn sustain_limit(mut self, limit: Duration) -> Self {
+        self.sustain_limit = limit;
+        self
+    }
+
+    /// Create an instance of `description` and track it
+    pub fn create(
+        &mut self,
+        description: &EventDescription,
+        owner: impl Into<String>,
+    ) -> Result<EventInstance, libfmod::Error> {
+        let instance = description.create_instance()?;
+        self.insert(instance, description, owner.into());
+        Ok(instance)
+    }
+
+    /// Track an instance created elsewhere
+    pub fn track(&mut self, instance: EventInstance, owner: impl Into<String>) -> Result<(), libfmod::Error> {
+        let description = instance.get_description()?;
+        self.insert(instance, &description, owner.into());
+        Ok(())
+    }
+
+    fn insert(&mut self, instance: EventInstance, description: &EventDescription, owner: String) {
+        let path = description
+            .get_path()
+            .or_else(|_| description.get_id().map(|id| crate::inspect::format_guid(&id)))
+            .unwrap_or_else(|_| "<unknown event>".to_string());
+        self.instances.push(TrackedInstance {
+            instance,
+            path,
+            owner,
+            created: Instant::now(),
+            released: None,
+            state: instance.get_playback_state().ok(),
+            sustaining_since: None,
+        });
+    }
+
+    /// Release `instance`, noting it so it is no longer reported as a leak
+    pub fn release(&mut self, instance: &EventInstance) -> Result<(), libfmod::Error> {
+        instance.release()?;
+        if let Some(tracked) = self.instances.iter_mut().find(|t| t.instance == *instance && t.released.is_none()) {
+            tracked.released = Some(Instant::now());
+        }
+        Ok(())
+    }
+
+    pub fn stop_and_release(&mut self, instance: &EventInstance, mode: StopMode) -> Result<(), libfmod::Error> {
+        instance.stop(mode)?;
+        self.release(instance)
+    }
+
+    /// Refresh playback states and forget instances FMOD has destroyed
+    pub fn update(&mut self) {
+        let now = Instant::now();
+        for tracked in &mut self.instances {
+            tracked.state = if tracked.instance.is_valid() {
+                tracked.instance.get_playback_state().ok()
+            } else {
+                None
+            };
+            tracked.sustaining_since = match tracked.state {
+                Some(PlaybackState::Sustaining) => Some(tracked.sustaining_since.unwrap_or(now)),
+                _ => None,
+            };
+        }
+        // A destroyed instance that was never released was still released by
+        // someone (e.g. Studio shutdown or a bank unload), so it is no leak
+        self.instances.retain(|tracked| tracked.state.is_some());
+    }
+
+    /// Every instance still alive, oldest first
+    pub fn instances(&self) -> &[TrackedInstance] {
+        &self.instances
+    }
+
+    /// Live instance counts per event path
+    pub fn counts(&self) -> BTreeMap<String, InstanceCounts> {
+        let mut counts: BTreeMap<String, InstanceCounts> = BTreeMap::new();
+        for tracked in &self.instances {
+            let Some(state) = tracked.state else {
+                continue;
+            };
+            let entry = counts.entry(tracked.path.clone()).or_default();
+            entry.live += 1;
+            match state {
+                PlaybackState::Starting => entry.starting += 1,
+                PlaybackState::Playing => entry.playing += 1,
+                PlaybackState::Sustaining => entry.sustaining += 1,
+                PlaybackState::Stopping => entry.stopping += 1,
+                PlaybackState::Stopped => entry.stopped += 1,
+            }
+            if tracked.released.is_some() {
+                entry.released += 1;
+            }
+        }
+        counts
+    }
+
+    /// Instances never released, and instances sustaining for longer than the limit
+    ///
+    /// At shutdown every unreleased instance is a leak; while running, these
+    /// are the ones to look at.
+    pub fn leaks(&self) -> Vec<Leak> {
+        let mut leaks = Vec::new();
+        for tracked in &self.instances {
+            let stuck = tracked.sustaining_for().filter(|held| *held >= self.sustain_limit);
+            let kind = match (stuck, tracked.released) {
+                (Some(held), _) => LeakKind::Sustaining(held),
+                (None, None) => LeakKind::NeverReleased,
+                (None, Some(_)) => continue,
+            };
+            leaks.push(Leak {
+                path: tracked.path.clone(),
+                owner: tracked.owner.clone(),
+                age: tracked.age(),
+                state: tracked.state,
+                kind,
+            });
+        }
+        leaks
+    }
+}
@@ -0,0 +1,353 @@
+use libfmod::{Attributes3d, EventInstance, SpeakerMode, StopMode, Vector};
+use std::{
+    fmt, io,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use crate::error::FmodError;
+use crate::output::{OutputMode, OutputOptions};
+use crate::parameter::{ParameterError, Parameters};
+use crate::session::{SessionError, StudioSession};
+use crate::wav::WavFile;
+
+/// Errors raised while rendering events offline
+#[derive(Debug)]
+pub enum RenderError {
+    Session(SessionError),
+    /// An FMOD call failed; the context names the event it was for, if any
+    Fmod { context: String, error: libfmod::Error },
+    /// A `--param` name the event does not have
+    Parameter(ParameterError),
+    Wav(io::Error),
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderError::Session(e) => write!(f, "{}", e),
+            RenderError::Fmod { context, error } => write!(f, "{} failed: {:?}", context, error),
+            RenderError::Parameter(e) => write!(f, "{}", e),
+            RenderError::Wav(e) => write!(f, "WAV post-processing failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+impl FmodError for RenderError {
+    fn from_fmod(context: String, error: libfmod::Error) -> Self {
+        RenderError::Fmod { context, error }
+    }
+}
+
+impl From<SessionError> for RenderError {
+    fn from(e: SessionError) -> Self {
+        RenderError::Session(e)
+    }
+}
+
+impl From<ParameterError> for RenderError {
+    fn from(e: ParameterError) -> Self {
+        RenderError::Parameter(e)
+    }
+}
+
+impl From<io::Error> for RenderError {
+    fn from(e: io::Error) -> Self {
+        RenderError::Wav(e)
+    }
+}
+
+/// A parameter value applied at a point on the render timeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterChange {
+    pub at: Duration,
+    pub name: String,
+    pub value: f32,
+}
+
+/// One event instance on the render timeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderEvent {
+    pub path: String,
+    pub start: Duration,
+    pub stop: Option<Duration>,
+    pub parameters: Vec<ParameterChange>,
+    pub position: Option<Vector>,
+}
+
+impl RenderEvent {
+    /// Start `path` at the beginning of the render and let it run to the end
+    pub fn new(path: impl Into<String>) -> Self {
+        RenderEvent {
+            path: path.into(),
+            start: Duration::ZERO,
+            stop: None,
+            parameters: Vec::new(),
+            position: None,
+        }
+    }
+
+    pub fn start_at(mut self, start: Duration) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Stop with fadeout at `stop`
+    pub fn stop_at(mut self, stop: Duration) -> Self {
+        self.stop = Some(stop);
+        self
+    }
+
+    /// Set `name` to `value` once the timeline reaches `at` (changes before `start` apply at start)
+    pub fn parameter(mut self, name: impl Into<String>, value: f32, at: Duration) -> Self {
+        self.parameters.push(ParameterChange {
+            at,
+            name: name.into(),
+            value,
+        });
+        self
+    }
+
+    /// Place the instance in 3D space (the listener stays at FMOD's default: the origin, facing +Z)
+    pub fn position(mut self, position: Vector) -> Self {
+        self.position = Some(position);
+        self
+    }
+}
+
+/// Everything needed to render a set of events to a WAV file
+#[derive(Debug, Clone)]
+pub struct RenderSpec {
+    pub output: PathBuf,
+    pub length: Duration,
+    pub sample_rate: i32,
+    pub speaker_mode: SpeakerMode,
+    /// Seed for FMOD's random variations, for repeatable renders
+    pub random_seed: Option<u32>,
+    pub banks: Vec<PathBuf>,
+    pub example_banks: Vec<String>,
+    pub events: Vec<RenderEvent>,
+}
+
+impl RenderSpec {
+    pub fn new(output: impl Into<PathBuf>, length: Duration) -> Self {
+        RenderSpec {
+            output: output.into(),
+            length,
+            sample_rate: 48000,
+            speaker_mode: SpeakerMode::Stereo,
+            random_seed: None,
+            banks: Vec::new(),
+            example_banks: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    pub fn sample_rate(mut self, sample_rate: i32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    pub fn speaker_mode(mut self, speaker_mode: SpeakerMode) -> Self {
+        self.speaker_mode = speaker_mode;
+        self
+    }
+
+    pub fn random_seed(mut self, seed: u32) -> Self {
+        self.random_seed = Some(seed);
+        self
+    }
+
+    pub fn bank(mut self, path: impl Into<PathBuf>) -> Self {
+        self.banks.push(path.into());
+        self
+    }
+
+    /// Load banks by name from the SDK's example media directory
+    pub fn example_banks(mut self, names: &[&str]) -> Self {
+        self.example_banks.extend(names.iter().map(|n| n.to_string()));
+        self
+    }
+
+    pub fn event(mut self, event: RenderEvent) -> Self {
+        self.events.push(event);
+        self
+    }
+}
+
+/// What a finished render produced
+#[derive(Debug, Clone)]
+pub struct RenderReport {
+    pub output: PathBuf,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub frames: usize,
+    /// Number of `studio.update()` calls (one mixer block each)
+    pub updates: u32,
+    /// Wall clock time the render took
+    pub elapsed: Duration,
+}
+
+impl RenderReport {
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.frames as f64 / self.sample_rate.max(1) as f64)
+    }
+}
+
+struct ScheduledEvent<'a> {
+    event: &'a RenderEvent,
+    parameters: Vec<&'a ParameterChange>,
+    applied: usize,
+    instance: Option<EventInstance>,
+    stopped: bool,
+}
+
+/// Render the spec as fast as possible through the `WavWriterNrt` output
+///
+/// Each `studio.update()` mixes one DSP block, so the timeline advances by
+/// the block length per update no matter how long the update takes. The
+/// written file is trimmed or padded to exactly `spec.length`.
+pub fn render(spec: &RenderSpec) -> Result<RenderReport, RenderError> {
+    let started = Instant::now();
+
+    let output = OutputOptions {
+        mode: OutputMode::WavWriterNrt,
+        file: Some(spec.output.clone()),
+    };
+    let example_banks: Vec<&str> = spec.example_banks.iter().map(String::as_str).collect();
+    let mut builder = StudioSession::builder()
+        .sample_rate(spec.sample_rate)
+        .speaker_mode(spec.speaker_mode)
+        .output_options(&output)
+        .example_banks(&example_banks)
+        .banks(spec.banks.iter().cloned());
+    if let Some(seed) = spec.random_seed {
+        builder = builder.random_seed(seed);
+    }
+    let session = builder.build()?;
+    let studio = session.studio();
+    let core = session.core().map_err(RenderError::fmod("get_core_system"))?;
+
+    let (block_length, _) = core.get_dsp_buffer_size().map_err(RenderError::fmod("get_dsp_buffer_size"))?;
+    let (sample_rate, _, _) = core.get_software_format().map_err(RenderError::fmod("get_software_format"))?;
+    let total_frames = (spec.length.as_secs_f64() * sample_rate as f64).round() as usize;
+
+    // Resolve every event up front so a typo fails before anything is rendered
+    let mut scheduled = Vec::with_capacity(spec.events.len());
+    for event in &spec.events {
+        let description = studio
+            .get_event(&event.path)
+            .map_err(RenderError::fmod(format!("{}: get_event", event.path)))?;
+        let known = Parameters::of_event(&description)?;
+        for change in &event.parameters {
+            known.find_settable(&change.name)?;
+        }
+        let mut parameters: Vec<&ParameterChange> = event.parameters.iter().collect();
+        parameters.sort_by_key(|change| change.at);
+        scheduled.push((
+            description,
+            ScheduledEvent {
+                event,
+                parameters,
+                applied: 0,
+                instance: None,
+                stopped: false,
+            },
+        ));
+    }
+
+    let mut frames = 0usize;
+    let mut updates = 0u32;
+    let render_result = (|| -> Result<(), RenderError> {
+        while frames < total_frames {
+            let now = Duration::from_secs_f64(frames as f64 / sample_rate as f64);
+
+            for (description, slot) in scheduled.iter_mut() {
+                let event = slot.event;
+                let path = &event.path;
+
+                if slot.instance.is_none() && now >= event.start {
+                    let instance = description
+                        .create_instance()
+                        .map_err(RenderError::fmod(format!("{}: create_instance", path)))?;
+                    slot.instance = Some(instance);
+                    if let Some(position) = &event.position {
+                        instance
+                            .set_3d_attributes(Attributes3d {
+                                position: position.clone(),
+                                velocity: Vector { x: 0.0, y: 0.0, z: 0.0 },
+                                forward: Vector { x: 0.0, y: 0.0, z: 1.0 },
+                                up: Vector { x: 0.0, y: 1.0, z: 0.0 },
+                            })
+                            .map_err(RenderError::fmod(format!("{}: set_3d_attributes", path)))?;
+                    }
+                    apply_parameters(slot, now)?;
+                    instance.start().map_err(RenderError::fmod(format!("{}: start", path)))?;
+                }
+
+                if let Some(instance) = slot.instance {
+                    apply_parameters(slot, now)?;
+                    if let Some(stop) = event.stop
+                        && !slot.stopped
+                        && now >= stop
+                    {
+                        instance
+                            .stop(StopMode::AllowFadeout)
+                            .map_err(RenderError::fmod(format!("{}: stop", path)))?;
+                        slot.stopped = true;
+                    }
+                }
+            }
+
+            studio.update().map_err(RenderError::fmod("Studio::update"))?;
+            frames += block_length as usize;
+            updates += 1;
+        }
+        Ok(())
+    })();
+
+    // Release instances even when the timeline failed part way through
+    for (_, slot) in &scheduled {
+        if let Some(instance) = slot.instance {
+            instance.release().ok();
+        }
+    }
+    render_result?;
+
+    // Releasing the system finalizes the WAV header
+    session.close().map_err(RenderError::fmod("Studio::release"))?;
+
+    let mut wav = WavFile::read(&spec.output)?;
+    wav.set_frames(total_frames);
+    wav.write(&spec.output)?;
+
+    Ok(RenderReport {
+        output: spec.output.clone(),
+        sample_rate: wav.sample_rate,
+        channels: wav.channels,
+        frames: wav.frames(),
+        updates,
+        elapsed: started.elapsed(),
+    })
+}
+
+fn apply_parameters(slot: &mut ScheduledEvent<'_>, now: Duration) -> Result<(), RenderError> {
+    let Some(instance) = slot.instance else {
+        return Ok(());
+    };
+    while let Some(change) = slot.parameters.get(slot.applied) {
+        if change.at > now {
+            break;
+        }
+        instance
+            .set_parameter_by_name(&change.name, change.value, false)
+            .map_err(RenderError::fmod(format!(
+                "{}: set_parameter_by_name(\"{}\")",
+                slot.event.path, change.name
+            )))?;
+        slot.applied += 1;
+    }
+    Ok(())
+}
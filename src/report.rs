@@ -0,0 +1,87 @@
+use std::fmt;
+
+/// Collects pass/fail checks for the test-suite examples
+///
+/// Each check is printed as it is recorded; [`TestReport::finish`] returns an
+/// error when anything failed so `main` exits with a non-zero status.
+#[derive(Debug, Default)]
+pub struct TestReport {
+    passed: usize,
+    failed: Vec<String>,
+}
+
+impl TestReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a check, returning whether it passed
+    pub fn check(&mut self, name: impl Into<String>, passed: bool, detail: impl fmt::Display) -> bool {
+        let name = name.into();
+        if passed {
+            println!("  ✓ {} ({})", name, detail);
+            self.passed += 1;
+        } else {
+            println!("  ✗ {} ({})", name, detail);
+            self.failed.push(name);
+        }
+        passed
+    }
+
+    /// Check that `actual` is within `tolerance` of `expected`
+    pub fn check_close(&mut self, name: impl Into<String>, actual: f32, expected: f32, tolerance: f32) -> bool {
+        let passed = (actual - expected).abs() <= tolerance;
+        self.check(name, passed, format!("got {:.3}, expected {:.3}", actual, expected))
+    }
+
+    /// Check that `result` is `Ok`, returning its value
+    pub fn check_ok<T, E: fmt::Display>(&mut self, name: impl Into<String>, result: Result<T, E>) -> Option<T> {
+        match result {
+            Ok(value) => {
+                self.check(name, true, "ok");
+                Some(value)
+            }
+            Err(e) => {
+                self.check(name, false, e);
+                None
+            }
+        }
+    }
+
+    pub fn passed(&self) -> usize {
+        self.passed
+    }
+
+    pub fn failed(&self) -> &[String] {
+        &self.failed
+    }
+
+    /// Print the summary and fail if any check failed
+    pub fn finish(self) -> Result<(), TestFailure> {
+        let total = self.passed + self.failed.len();
+        if self.failed.is_empty() {
+            println!("\n✅ {}/{} checks passed", self.passed, total);
+            Ok(())
+        } else {
+            println!("\n❌ {}/{} checks failed:", self.failed.len(), total);
+            for name in &self.failed {
+                println!("   - {}", name);
+            }
+            Err(TestFailure { failed: self.failed })
+        }
+    }
+}
+
+/// The checks that failed in a [`TestReport`]
+#[derive(Debug)]
+pub struct TestFailure {
+    pub failed: Vec<String>,
+}
+
+impl fmt::Display for TestFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} check(s) failed: {}", self.failed.len(), self.failed.join(", "))
+    }
+}
+
+impl std::error::Error for TestFailure {}
@@ -0,0 +1,769 @@
+use libfmod::{EventDescription, EventInstance, StopMode, Studio, Vector};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::automation::Curve;
+use crate::clock::{ClockMode, UpdateLoop};
+use crate::error::FmodError;
+use crate::motion::{Looping, MotionPath, Shape};
+use crate::output::OutputOptions;
+use crate::parameter::{ParameterError, Parameters};
+use crate::session::{SessionError, StudioSession};
+use crate::spatial::{self, Emitter, Listener, VelocityTracker};
+
+pub use crate::snapshot::SNAPSHOT_INTENSITY;
+
+/// Errors raised while loading or running a scene
+#[derive(Debug)]
+pub enum SceneError {
+    Io { path: PathBuf, error: io::Error },
+    /// The file is not valid YAML/TOML/JSON or does not match the scene format
+    Parse { path: PathBuf, message: String },
+    /// The scene parsed but makes no sense, e.g. an event stopping before it starts
+    Invalid(String),
+    Session(SessionError),
+    /// A parameter name the event (or the Studio system) does not have
+    Parameter(ParameterError),
+    Fmod { context: String, error: libfmod::Error },
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            SceneError::Parse { path, message } => write!(f, "{}: {}", path.display(), message),
+            SceneError::Invalid(message) => write!(f, "invalid scene: {}", message),
+            SceneError::Session(e) => write!(f, "{}", e),
+            SceneError::Parameter(e) => write!(f, "{}", e),
+            SceneError::Fmod { context, error } => write!(f, "{} failed: {:?}", context, error),
+        }
+    }
+}
+
+impl std::error::Error for SceneError {}
+
+impl From<SessionError> for SceneError {
+    fn from(e: SessionError) -> Self {
+        SceneError::Session(e)
+    }
+}
+
+impl From<ParameterError> for SceneError {
+    fn from(e: ParameterError) -> Self {
+        SceneError::Parameter(e)
+    }
+}
+
+// Only `UpdateLoop` errors arrive without context
+impl From<libfmod::Error> for SceneError {
+    fn from(error: libfmod::Error) -> Self {
+        SceneError::Fmod {
+            context: "Studio::update".to_string(),
+            error,
+        }
+    }
+}
+
+impl FmodError for SceneError {
+    fn from_fmod(context: String, error: libfmod::Error) -> Self {
+        SceneError::Fmod { context, error }
+    }
+}
+
+/// Longest scene accepted, in seconds
+pub const MAX_LENGTH: f32 = 24.0 * 60.0 * 60.0;
+
+fn validate_curve(curve: &Curve, what: &str) -> Result<(), SceneError> {
+    curve.validate().map_err(|message| SceneError::Invalid(format!("{}: {}", what, message)))
+}
+
+/// A listener's placement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListenerSpec {
+    pub position: [f32; 3],
+    #[serde(default)]
+    pub yaw: f32,
+    #[serde(default = "full_weight")]
+    pub weight: f32,
+}
+
+fn full_weight() -> f32 {
+    1.0
+}
+
+/// Trajectory shapes, named by `shape:`; see [`Shape`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "shape", rename_all = "kebab-case")]
+pub enum ShapeSpec {
+    Linear {
+        from: [f32; 3],
+        to: [f32; 3],
+    },
+    Orbit {
+        #[serde(default)]
+        center: [f32; 3],
+        radius: f32,
+    },
+    FigureEight {
+        #[serde(default)]
+        center: [f32; 3],
+        radius: f32,
+    },
+    Spline {
+        waypoints: Vec<[f32; 3]>,
+    },
+    FlyOver {
+        #[serde(default)]
+        center: [f32; 3],
+        height: f32,
+        distance: f32,
+    },
+}
+
+/// A motion path for an event, e.g. `{ shape: orbit, radius: 8, speed: 4 }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PathSpec {
+    #[serde(flatten)]
+    pub shape: ShapeSpec,
+    /// Units per second
+    pub speed: Option<f32>,
+    pub looping: Option<Looping>,
+}
+
+impl PathSpec {
+    /// Check that every coordinate, size and the speed is a finite number
+    pub fn validate(&self) -> Result<(), String> {
+        let finite = |v: &[f32; 3]| v.iter().all(|x| x.is_finite());
+        let shape_ok = match &self.shape {
+            ShapeSpec::Linear { from, to } => finite(from) && finite(to),
+            ShapeSpec::Orbit { center, radius } | ShapeSpec::FigureEight { center, radius } => {
+                finite(center) && radius.is_finite()
+            }
+            ShapeSpec::Spline { waypoints } => waypoints.iter().all(finite),
+            ShapeSpec::FlyOver { center, height, distance } => {
+                finite(center) && height.is_finite() && distance.is_finite()
+            }
+        };
+        if !shape_ok {
+            return Err("motion coordinates and sizes must be finite numbers".to_string());
+        }
+        match self.speed {
+            Some(speed) if !speed.is_finite() || speed < 0.0 => {
+                Err(format!("motion speed ({}) must be a finite number of at least 0", speed))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn to_path(&self) -> MotionPath {
+        let shape = match &self.shape {
+            ShapeSpec::Linear { from, to } => Shape::Linear {
+                from: vector(from),
+                to: vector(to),
+            },
+            ShapeSpec::Orbit { center, radius } => Shape::Orbit {
+                center: vector(center),
+                radius: *radius,
+            },
+            ShapeSpec::FigureEight { center, radius } => Shape::FigureEight {
+                center: vector(center),
+                radius: *radius,
+            },
+            ShapeSpec::Spline { waypoints } => Shape::Spline {
+                waypoints: waypoints.iter().map(vector).collect(),
+            },
+            ShapeSpec::FlyOver { center, height, distance } => Shape::FlyOver {
+                center: vector(center),
+                height: *height,
+                distance: *distance,
+            },
+        };
+        let mut path = MotionPath::new(shape);
+        if let Some(speed) = self.speed {
+            path = path.speed(speed);
+        }
+        if let Some(looping) = self.looping {
+            path = path.looping(looping);
+        }
+        path
+    }
+}
+
+/// An event started (and optionally stopped) at given scene times
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventSpec {
+    pub path: String,
+    /// Seconds from the start of the scene
+    #[serde(default)]
+    pub start: f32,
+    /// Seconds from the start of the scene; runs to the end without it
+    pub stop: Option<f32>,
+    pub volume: Option<f32>,
+    /// Parameter values, keyed by name; keyframe times are scene seconds
+    #[serde(default)]
+    pub parameters: BTreeMap<String, Curve>,
+    /// Fixed 3D position
+    pub position: Option<[f32; 3]>,
+    /// Path to follow from the moment the event starts
+    pub motion: Option<PathSpec>,
+}
+
+/// A snapshot started at given scene times, with its intensity (0-100)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SnapshotSpec {
+    pub path: String,
+    #[serde(default)]
+    pub start: f32,
+    pub stop: Option<f32>,
+    pub intensity: Option<Curve>,
+}
+
+/// A declarative audio test scene, loaded from YAML, TOML or JSON
+///
+/// Lists the banks to load, the listeners, and events and snapshots to
+/// start and stop at given times, with parameter automation and 3D paths.
+/// All times are seconds from the start of the scene.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scene {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Seconds to run; everything still playing is stopped at the end
+    pub length: f32,
+    pub sample_rate: Option<i32>,
+    /// Seed for FMOD's random variations, for repeatable runs
+    pub random_seed: Option<u32>,
+    /// Bank files, relative to the scene file
+    #[serde(default)]
+    pub banks: Vec<PathBuf>,
+    /// Bank names from the SDK's example media directory
+    #[serde(default)]
+    pub example_banks: Vec<String>,
+    /// Listener 0, 1, ...; one listener at the origin facing -Z without any
+    #[serde(default)]
+    pub listeners: Vec<ListenerSpec>,
+    /// Studio-level parameters, keyed by name
+    #[serde(default)]
+    pub global_parameters: BTreeMap<String, Curve>,
+    #[serde(default)]
+    pub snapshots: Vec<SnapshotSpec>,
+    #[serde(default)]
+    pub events: Vec<EventSpec>,
+}
+
+/// Scene file formats, picked by extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SceneFormat {
+    Yaml,
+    Toml,
+    Json,
+}
+
+impl SceneFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "yaml" | "yml" => Some(SceneFormat::Yaml),
+            "toml" => Some(SceneFormat::Toml),
+            "json" => Some(SceneFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+impl Scene {
+    /// Load and validate a `.yaml`/`.yml`, `.toml` or `.json` scene
+    pub fn load(path: &Path) -> Result<Self, SceneError> {
+        let format = SceneFormat::from_path(path).ok_or_else(|| SceneError::Parse {
+            path: path.to_path_buf(),
+            message: "unknown scene format (expected .yaml, .yml, .toml or .json)".to_string(),
+        })?;
+        let text = fs::read_to_string(path).map_err(|error| SceneError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        Self::parse(&text, format).map_err(|e| match e {
+            SceneError::Parse { message, .. } => SceneError::Parse {
+                path: path.to_path_buf(),
+                message,
+            },
+            other => other,
+        })
+    }
+
+    /// Parse and validate scene text
+    pub fn parse(text: &str, format: SceneFormat) -> Result<Self, SceneError> {
+        let parsed = match format {
+            SceneFormat::Yaml => serde_norway::from_str::<Scene>(text).map_err(|e| e.to_string()),
+            SceneFormat::Toml => toml::from_str::<Scene>(text).map_err(|e| e.to_string()),
+            SceneFormat::Json => serde_json::from_str::<Scene>(text).map_err(|e| e.to_string()),
+        };
+        let scene = parsed.map_err(|message| SceneError::Parse {
+            path: PathBuf::new(),
+            message,
+        })?;
+        scene.validate()?;
+        Ok(scene)
+    }
+
+    /// Check times and keyframes without touching FMOD
+    pub fn validate(&self) -> Result<(), SceneError> {
+        if !(self.length > 0.0 && self.length <= MAX_LENGTH) {
+            return Err(SceneError::Invalid(format!(
+                "length must be a number greater than 0 and at most {} seconds",
+                MAX_LENGTH
+            )));
+        }
+        if self.banks.is_empty() && self.example_banks.is_empty() {
+            return Err(SceneError::Invalid("no banks or example_banks to load".to_string()));
+        }
+        for (index, listener) in self.listeners.iter().enumerate() {
+            if listener.position.iter().chain([&listener.yaw, &listener.weight]).any(|x| !x.is_finite()) {
+                return Err(SceneError::Invalid(format!(
+                    "listener {}: position, yaw and weight must be finite numbers",
+                    index
+                )));
+            }
+        }
+        let check_times = |path: &str, start: f32, stop: Option<f32>| {
+            if !start.is_finite() || stop.is_some_and(|stop| !stop.is_finite()) {
+                return Err(SceneError::Invalid(format!("{}: start and stop must be finite numbers", path)));
+            }
+            if start < 0.0 {
+                return Err(SceneError::Invalid(format!("{}: start is negative", path)));
+            }
+            if start >= self.length {
+                return Err(SceneError::Invalid(format!(
+                    "{}: start ({}) is not before the end of the scene ({})",
+                    path, start, self.length
+                )));
+            }
+            match stop {
+                Some(stop) if stop < start => {
+                    Err(SceneError::Invalid(format!("{}: stop ({}) is before start ({})", path, stop, start)))
+                }
+                _ => Ok(()),
+            }
+        };
+        for event in &self.events {
+            check_times(&event.path, event.start, event.stop)?;
+            for (name, automation) in &event.parameters {
+                validate_curve(automation, &format!("{} parameter {}", event.path, name))?;
+            }
+            if event.position.is_some() && event.motion.is_some() {
+                return Err(SceneError::Invalid(format!("{}: give either position or motion, not both", event.path)));
+            }
+            if event.position.is_some_and(|position| position.iter().any(|x| !x.is_finite())) {
+                return Err(SceneError::Invalid(format!("{}: position must be finite numbers", event.path)));
+            }
+            if event.volume.is_some_and(|volume| !volume.is_finite()) {
+                return Err(SceneError::Invalid(format!("{}: volume must be a finite number", event.path)));
+            }
+            if let Some(motion) = &event.motion {
+                motion.validate().map_err(|message| SceneError::Invalid(format!("{}: {}", event.path, message)))?;
+            }
+        }
+        for snapshot in &self.snapshots {
+            check_times(&snapshot.path, snapshot.start, snapshot.stop)?;
+            if let Some(intensity) = &snapshot.intensity {
+                validate_curve(intensity, &format!("{} intensity", snapshot.path))?;
+            }
+        }
+        for (name, automation) in &self.global_parameters {
+            validate_curve(automation, &format!("global parameter {}", name))?;
+        }
+        Ok(())
+    }
+
+    /// Scene length; zero if the length is not a valid duration
+    pub fn duration(&self) -> Duration {
+        Duration::try_from_secs_f32(self.length).unwrap_or_default()
+    }
+}
+
+fn vector(v: &[f32; 3]) -> Vector {
+    spatial::vector(v[0], v[1], v[2])
+}
+
+/// Something that happened during a run, for progress output
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+    pub at: Duration,
+    pub message: String,
+}
+
+/// What a finished run did
+#[derive(Debug, Clone)]
+pub struct SceneReport {
+    pub mode: ClockMode,
+    /// Scene time covered
+    pub length: Duration,
+    pub ticks: u64,
+    /// Wall clock time the run took
+    pub elapsed: Duration,
+    pub cues: Vec<Cue>,
+}
+
+// An event or snapshot on the timeline
+struct Slot<'a> {
+    path: &'a str,
+    description: EventDescription,
+    start: f32,
+    stop: Option<f32>,
+    volume: Option<f32>,
+    parameters: Vec<(&'a str, &'a Curve)>,
+    position: Option<Vector>,
+    motion: Option<MotionPath>,
+    emitter: Emitter,
+    tracker: VelocityTracker,
+    instance: Option<EventInstance>,
+    stopped: bool,
+}
+
+impl<'a> Slot<'a> {
+    fn new(studio: &Studio, path: &'a str, start: f32, stop: Option<f32>) -> Result<Self, SceneError> {
+        let description = studio.get_event(path).map_err(SceneError::fmod(format!("get_event(\"{}\")", path)))?;
+        Ok(Slot {
+            path,
+            description,
+            start,
+            stop,
+            volume: None,
+            parameters: Vec::new(),
+            position: None,
+            motion: None,
+            emitter: Emitter::new(spatial::ZERO),
+            tracker: VelocityTracker::new(),
+            instance: None,
+            stopped: false,
+        })
+    }
+
+    fn is_spatial(&self) -> bool {
+        self.position.is_some() || self.motion.is_some()
+    }
+
+    // Start, automate, move and stop the instance for scene time `now`
+    fn advance(&mut self, now: f32, delta: Duration, cues: &mut Vec<Cue>) -> Result<(), SceneError> {
+        let at = Duration::from_secs_f32(now);
+        if self.instance.is_none() && !self.stopped && now >= self.start {
+            let instance = self
+                .description
+                .create_instance()
+                .map_err(SceneError::fmod(format!("{}: create_instance", self.path)))?;
+            self.instance = Some(instance);
+            if let Some(volume) = self.volume {
+                instance.set_volume(volume).map_err(SceneError::fmod(format!("{}: set_volume", self.path)))?;
+            }
+            if let Some(position) = &self.position {
+                self.emitter.position = position.clone();
+            }
+            if let Some(motion) = &self.motion {
+                self.emitter.position = motion.position_at(Duration::ZERO);
+            }
+            self.tracker.update(&self.emitter.position, Duration::ZERO);
+            self.apply(now, Duration::ZERO)?;
+            instance.start().map_err(SceneError::fmod(format!("{}: start", self.path)))?;
+            cues.push(Cue {
+                at,
+                message: format!("start {}", self.path),
+            });
+            return Ok(());
+        }
+
+        let Some(instance) = self.instance else {
+            return Ok(());
+        };
+        if self.stopped {
+            return Ok(());
+        }
+        if let Some(stop) = self.stop
+            && now >= stop
+        {
+            instance
+                .stop(StopMode::AllowFadeout)
+                .map_err(SceneError::fmod(format!("{}: stop", self.path)))?;
+            self.stopped = true;
+            cues.push(Cue {
+                at,
+                message: format!("stop {}", self.path),
+            });
+            return Ok(());
+        }
+        self.apply(now, delta)
+    }
+
+    fn apply(&mut self, now: f32, delta: Duration) -> Result<(), SceneError> {
+        let Some(instance) = self.instance else {
+            return Ok(());
+        };
+        // Curves run on scene time, not time since the event started
+        let at = Duration::from_secs_f32(now);
+        for (name, automation) in &self.parameters {
+            instance
+                .set_parameter_by_name(name, automation.value_at(at), false)
+                .map_err(SceneError::fmod(format!("{}: set_parameter_by_name(\"{}\")", self.path, name)))?;
+        }
+        if self.is_spatial() {
+            if let Some(motion) = &self.motion {
+                let travelled = Duration::from_secs_f32((now - self.start).max(0.0));
+                self.emitter.position = motion.position_at(travelled);
+            }
+            self.emitter.velocity = self.tracker.update(&self.emitter.position, delta);
+            instance
+                .set_3d_attributes(self.emitter.attributes())
+                .map_err(SceneError::fmod(format!("{}: set_3d_attributes", self.path)))?;
+        }
+        Ok(())
+    }
+}
+
+/// Run a scene; bank paths are resolved relative to `base_dir`
+///
+/// The output options decide how: a realtime output plays the scene in real
+/// time, the non-realtime ones (`nosound-nrt`, `wavwriter-nrt`) run it
+/// offline as fast as the mixer allows. `on_cue` is called as events and
+/// snapshots start and stop.
+pub fn run_scene(
+    scene: &Scene,
+    base_dir: &Path,
+    output: &OutputOptions,
+    mut on_cue: impl FnMut(&Cue),
+) -> Result<SceneReport, SceneError> {
+    let started = Instant::now();
+
+    let example_banks: Vec<&str> = scene.example_banks.iter().map(String::as_str).collect();
+    let mut builder = StudioSession::builder()
+        .output_options(output)
+        .example_banks(&example_banks)
+        .banks(scene.banks.iter().map(|bank| base_dir.join(bank)));
+    if let Some(sample_rate) = scene.sample_rate {
+        builder = builder.sample_rate(sample_rate);
+    }
+    if let Some(seed) = scene.random_seed {
+        builder = builder.random_seed(seed);
+    }
+    let session = builder.build()?;
+    let studio = session.studio();
+
+    let listeners: Vec<Listener> = scene
+        .listeners
+        .iter()
+        .map(|spec| Listener {
+            yaw: spec.yaw,
+            weight: spec.weight,
+            ..Listener::new(vector(&spec.position))
+        })
+        .collect();
+    if !listeners.is_empty() {
+        spatial::apply_listeners(studio, &listeners).map_err(SceneError::fmod("set_listener_attributes"))?;
+    }
+
+    // Resolve every path up front so a typo fails before anything plays
+    let mut slots = Vec::new();
+    for snapshot in &scene.snapshots {
+        let mut slot = Slot::new(studio, &snapshot.path, snapshot.start, snapshot.stop)?;
+        if let Some(intensity) = &snapshot.intensity {
+            slot.parameters.push((SNAPSHOT_INTENSITY, intensity));
+        }
+        slots.push(slot);
+    }
+    for event in &scene.events {
+        let mut slot = Slot::new(studio, &event.path, event.start, event.stop)?;
+        let known = Parameters::of_event(&slot.description)?;
+        for name in event.parameters.keys() {
+            known.find_settable(name)?;
+        }
+        slot.volume = event.volume;
+        slot.parameters = event.parameters.iter().map(|(name, value)| (name.as_str(), value)).collect();
+        slot.position = event.position.as_ref().map(vector);
+        slot.motion = event.motion.as_ref().map(PathSpec::to_path);
+        slots.push(slot);
+    }
+    if !scene.global_parameters.is_empty() {
+        let known = Parameters::global(studio)?;
+        for name in scene.global_parameters.keys() {
+            known.find_settable(name)?;
+        }
+    }
+
+    let mut cues = Vec::new();
+    let mut advance = |now: f32, delta: Duration, cues: &mut Vec<Cue>| -> Result<(), SceneError> {
+        let at = Duration::from_secs_f32(now);
+        for (name, automation) in &scene.global_parameters {
+            studio
+                .set_parameter_by_name(name, automation.value_at(at), false)
+                .map_err(SceneError::fmod(format!("global parameter \"{}\"", name)))?;
+        }
+        for slot in &mut slots {
+            slot.advance(now, delta, cues)?;
+        }
+        Ok(())
+    };
+
+    let mut clock = UpdateLoop::for_output(studio, output).map_err(SceneError::fmod("UpdateLoop::for_output"))?;
+    let run_result = (|| -> Result<(), SceneError> {
+        // Cues at time 0 go out before the first update
+        advance(0.0, Duration::ZERO, &mut cues)?;
+        cues.iter().for_each(&mut on_cue);
+        clock.run_for_with(scene.duration(), |tick| {
+            let reported = cues.len();
+            advance(tick.time.as_secs_f32(), tick.delta, &mut cues)?;
+            cues[reported..].iter().for_each(&mut on_cue);
+            Ok::<_, SceneError>(())
+        })
+    })();
+
+    // Release instances even when the run failed part way through
+    for slot in &slots {
+        if let Some(instance) = slot.instance {
+            instance.stop(StopMode::Immediate).ok();
+            instance.release().ok();
+        }
+    }
+    run_result?;
+
+    let report = SceneReport {
+        mode: clock.mode(),
+        length: clock.time(),
+        ticks: clock.ticks(),
+        elapsed: started.elapsed(),
+        cues,
+    };
+    drop(clock);
+    session.close().map_err(SceneError::fmod("Studio::release"))?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINIMAL: &str = "length: 10\nexample_banks: [Master]\n";
+
+    fn invalid(text: &str) -> String {
+        match Scene::parse(text, SceneFormat::Yaml) {
+            Err(SceneError::Invalid(message)) => message,
+            other => panic!("expected an invalid scene, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    fn with_event(event: &str) -> String {
+        format!("{}events:\n  - path: event:/Test\n{}", MINIMAL, event)
+    }
+
+    #[test]
+    fn parses_all_three_formats() {
+        let yaml = Scene::parse(MINIMAL, SceneFormat::Yaml).unwrap();
+        let toml = Scene::parse("length = 10\nexample_banks = [\"Master\"]\n", SceneFormat::Toml).unwrap();
+        let json = Scene::parse(r#"{"length": 10, "example_banks": ["Master"]}"#, SceneFormat::Json).unwrap();
+        assert_eq!(yaml, toml);
+        assert_eq!(yaml, json);
+        assert_eq!(yaml.duration(), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn parses_events_with_automation_and_motion() {
+        let scene = Scene::parse(
+            &with_event(
+                "    start: 1\n    stop: 8\n    parameters:\n      RPM: [[0, 1000], [5, 4000, ease]]\n    motion: { shape: orbit, radius: 8, speed: 4 }\n",
+            ),
+            SceneFormat::Yaml,
+        )
+        .unwrap();
+        let event = &scene.events[0];
+        assert_eq!((event.start, event.stop), (1.0, Some(8.0)));
+        assert_eq!(event.parameters["RPM"].value_at(Duration::from_secs(5)), 4000.0);
+        let path = event.motion.as_ref().unwrap().to_path();
+        assert_eq!(path.shape().name(), "orbit");
+        assert_eq!(path.units_per_second(), 4.0);
+    }
+
+    #[test]
+    fn format_follows_the_extension() {
+        assert_eq!(SceneFormat::from_path(Path::new("a.YML")), Some(SceneFormat::Yaml));
+        assert_eq!(SceneFormat::from_path(Path::new("a.toml")), Some(SceneFormat::Toml));
+        assert_eq!(SceneFormat::from_path(Path::new("a.json")), Some(SceneFormat::Json));
+        assert_eq!(SceneFormat::from_path(Path::new("a.txt")), None);
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        let result = Scene::parse(&format!("{}lenght: 5\n", MINIMAL), SceneFormat::Yaml);
+        assert!(matches!(result, Err(SceneError::Parse { .. })));
+    }
+
+    #[test]
+    fn rejects_bad_lengths() {
+        for length in ["0", "-1", ".nan", ".inf", "1e30"] {
+            let message = invalid(&format!("length: {}\nexample_banks: [Master]\n", length));
+            assert!(message.contains("length"), "{}: {}", length, message);
+        }
+        assert!(invalid("length: 5\n").contains("no banks"));
+    }
+
+    #[test]
+    fn rejects_bad_event_times() {
+        assert!(invalid(&with_event("    start: -1\n")).contains("negative"));
+        assert!(invalid(&with_event("    start: .nan\n")).contains("finite"));
+        assert!(invalid(&with_event("    start: 1\n    stop: .inf\n")).contains("finite"));
+        assert!(invalid(&with_event("    start: 10\n")).contains("end of the scene"));
+        assert!(invalid(&with_event("    start: 5\n    stop: 2\n")).contains("before start"));
+    }
+
+    #[test]
+    fn rejects_position_with_motion() {
+        let message = invalid(&with_event("    position: [0, 0, 0]\n    motion: { shape: orbit, radius: 2 }\n"));
+        assert!(message.contains("not both"));
+    }
+
+    #[test]
+    fn rejects_non_finite_motion_and_positions() {
+        for motion in [
+            "{ shape: orbit, radius: .nan }",
+            "{ shape: figure-eight, center: [0, .inf, 0], radius: 2 }",
+            "{ shape: fly-over, height: 5, distance: .inf }",
+            "{ shape: spline, waypoints: [[0, 0, 0], [1, .nan, 0]] }",
+            "{ shape: linear, from: [0, 0, 0], to: [-.inf, 0, 0] }",
+            "{ shape: orbit, radius: 2, speed: .inf }",
+            "{ shape: orbit, radius: 2, speed: -1 }",
+        ] {
+            let message = invalid(&with_event(&format!("    motion: {}\n", motion)));
+            assert!(message.contains("motion"), "{}: {}", motion, message);
+        }
+        assert!(invalid(&with_event("    position: [0, .nan, 0]\n")).contains("position"));
+        assert!(invalid(&with_event("    volume: .inf\n")).contains("volume"));
+    }
+
+    #[test]
+    fn rejects_non_finite_listeners() {
+        for listener in ["{ position: [0, .nan, 0] }", "{ position: [0, 0, 0], yaw: .inf }", "{ position: [0, 0, 0], weight: .nan }"] {
+            let message = invalid(&format!("{}listeners:\n  - {{ position: [1, 0, 0] }}\n  - {}\n", MINIMAL, listener));
+            assert!(message.contains("listener 1"), "{}: {}", listener, message);
+        }
+    }
+
+    #[test]
+    fn duration_of_an_unchecked_length_is_zero() {
+        let mut scene = Scene::parse(MINIMAL, SceneFormat::Yaml).unwrap();
+        scene.length = 1e30;
+        assert_eq!(scene.duration(), Duration::ZERO);
+    }
+
+    #[test]
+    fn rejects_bad_curves() {
+        assert!(invalid(&with_event("    parameters:\n      RPM: [[2, 0], [1, 1]]\n")).contains("RPM"));
+        let snapshot = format!("{}snapshots:\n  - path: snapshot:/Test\n    start: 20\n", MINIMAL);
+        assert!(invalid(&snapshot).contains("snapshot:/Test"));
+    }
+}
@@ -0,0 +1,514 @@
+use std::{
+    env, fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// Environment variable that points at the FMOD SDK root
+pub const SDK_DIR_ENV: &str = "FMOD_SDK_DIR";
+
+/// Name of the config file holding the SDK path (first non-comment line)
+pub const SDK_CONFIG_FILE: &str = "fmod_sdk.conf";
+
+/// Library sub-directory used by the SDK on this platform
+#[cfg(target_os = "macos")]
+pub const LIB_ARCH: &str = ".";
+#[cfg(all(not(target_os = "macos"), target_arch = "x86_64", target_os = "windows"))]
+pub const LIB_ARCH: &str = "x64";
+#[cfg(all(not(target_os = "macos"), target_arch = "x86_64", not(target_os = "windows")))]
+pub const LIB_ARCH: &str = "x86_64";
+#[cfg(all(not(target_os = "macos"), target_arch = "x86"))]
+pub const LIB_ARCH: &str = "x86";
+#[cfg(all(not(target_os = "macos"), target_arch = "aarch64"))]
+pub const LIB_ARCH: &str = "arm64";
+#[cfg(all(not(target_os = "macos"), target_arch = "arm"))]
+pub const LIB_ARCH: &str = "arm";
+// No SDK build is known for other targets; guess the directory from the arch name
+#[cfg(not(any(
+    target_os = "macos",
+    target_arch = "x86_64",
+    target_arch = "x86",
+    target_arch = "aarch64",
+    target_arch = "arm"
+)))]
+pub const LIB_ARCH: &str = std::env::consts::ARCH;
+
+/// Parts of the SDK layout the demos depend on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SdkComponent {
+    Root,
+    CoreLibraries,
+    StudioLibraries,
+    ExampleMedia,
+}
+
+impl SdkComponent {
+    /// Path of the component relative to the SDK root
+    pub fn relative_path(&self, arch: &str) -> PathBuf {
+        match self {
+            SdkComponent::Root => PathBuf::new(),
+            SdkComponent::CoreLibraries => Path::new("api/core/lib").join(arch),
+            SdkComponent::StudioLibraries => Path::new("api/studio/lib").join(arch),
+            SdkComponent::ExampleMedia => PathBuf::from("api/studio/examples/media"),
+        }
+    }
+}
+
+impl fmt::Display for SdkComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SdkComponent::Root => write!(f, "SDK root directory"),
+            SdkComponent::CoreLibraries => write!(f, "core libraries"),
+            SdkComponent::StudioLibraries => write!(f, "studio libraries"),
+            SdkComponent::ExampleMedia => write!(f, "studio example banks"),
+        }
+    }
+}
+
+/// Where the SDK location came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SdkSource {
+    Env,
+    Config(PathBuf),
+    Prefix,
+    Explicit,
+}
+
+impl fmt::Display for SdkSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SdkSource::Env => write!(f, "${}", SDK_DIR_ENV),
+            SdkSource::Config(path) => write!(f, "config file {}", path.display()),
+            SdkSource::Prefix => write!(f, "install prefix search"),
+            SdkSource::Explicit => write!(f, "explicit path"),
+        }
+    }
+}
+
+/// Errors raised while locating or validating the SDK
+#[derive(Debug)]
+pub enum SdkError {
+    /// No candidate directory was configured or found
+    NotFound { searched: Vec<PathBuf> },
+    /// The SDK directory exists but a required piece is missing
+    MissingComponent {
+        component: SdkComponent,
+        path: PathBuf,
+        source: SdkSource,
+    },
+    /// The config file exists but could not be used
+    Config { path: PathBuf, message: String },
+}
+
+impl fmt::Display for SdkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SdkError::NotFound { searched } => {
+                write!(
+                    f,
+                    "FMOD SDK not found. Set {} to your FMOD SDK path or list it in {}",
+                    SDK_DIR_ENV, SDK_CONFIG_FILE
+                )?;
+                if !searched.is_empty() {
+                    write!(f, " (searched: ")?;
+                    for (i, path) in searched.iter().enumerate() {
+                        if i > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{}", path.display())?;
+                    }
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
+            SdkError::MissingComponent { component, path, source } => write!(
+                f,
+                "FMOD SDK from {} is missing its {}: {} does not exist",
+                source,
+                component,
+                path.display()
+            ),
+            SdkError::Config { path, message } => {
+                write!(f, "invalid SDK config file {}: {}", path.display(), message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SdkError {}
+
+/// FMOD version as encoded in `FMOD_VERSION` (0xaaaabbcc)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SdkVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl SdkVersion {
+    /// Decode the packed version used by the FMOD headers and `System::get_version`
+    pub fn from_packed(version: u32) -> Self {
+        SdkVersion {
+            major: (version >> 16) & 0xFFFF,
+            minor: (version >> 8) & 0xFF,
+            patch: version & 0xFF,
+        }
+    }
+}
+
+impl fmt::Display for SdkVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:02x}.{:02x}", self.major, self.minor, self.patch)
+    }
+}
+
+/// A validated FMOD SDK installation
+#[derive(Debug, Clone)]
+pub struct FmodSdk {
+    root: PathBuf,
+    arch: String,
+    version: Option<SdkVersion>,
+    source: SdkSource,
+}
+
+impl FmodSdk {
+    /// Find the SDK from `FMOD_SDK_DIR`, the config file or common install prefixes
+    ///
+    /// An explicitly configured location (env var or config file) is never
+    /// silently skipped: if it is broken the error names the missing piece.
+    pub fn locate() -> Result<Self, SdkError> {
+        if let Ok(dir) = env::var(SDK_DIR_ENV)
+            && !dir.is_empty()
+        {
+            return Self::validate(PathBuf::from(dir), LIB_ARCH, SdkSource::Env);
+        }
+
+        for config in config_file_candidates() {
+            if config.is_file() {
+                let root = read_config_file(&config)?;
+                return Self::validate(root, LIB_ARCH, SdkSource::Config(config));
+            }
+        }
+
+        Self::search(&install_prefixes(), LIB_ARCH)
+    }
+
+    /// First valid SDK in `prefixes` or their versioned sub-directories
+    fn search(prefixes: &[PathBuf], arch: &str) -> Result<Self, SdkError> {
+        let mut searched = Vec::new();
+        for prefix in prefixes {
+            for candidate in prefix_candidates(prefix) {
+                if let Ok(sdk) = Self::validate(candidate.clone(), arch, SdkSource::Prefix) {
+                    return Ok(sdk);
+                }
+                searched.push(candidate);
+            }
+        }
+
+        Err(SdkError::NotFound { searched })
+    }
+
+    /// Validate an SDK at a known location
+    pub fn from_dir(root: impl Into<PathBuf>) -> Result<Self, SdkError> {
+        Self::validate(root.into(), LIB_ARCH, SdkSource::Explicit)
+    }
+
+    fn validate(root: PathBuf, arch: &str, source: SdkSource) -> Result<Self, SdkError> {
+        for component in [
+            SdkComponent::Root,
+            SdkComponent::CoreLibraries,
+            SdkComponent::StudioLibraries,
+            SdkComponent::ExampleMedia,
+        ] {
+            let path = root.join(component.relative_path(arch));
+            if !path.is_dir() {
+                return Err(SdkError::MissingComponent { component, path, source });
+            }
+        }
+
+        let version = read_header_version(&root);
+        Ok(FmodSdk {
+            root,
+            arch: arch.to_string(),
+            version,
+            source,
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn arch(&self) -> &str {
+        &self.arch
+    }
+
+    /// SDK version read from `api/core/inc/fmod_common.h`, if the header is present
+    pub fn version(&self) -> Option<SdkVersion> {
+        self.version
+    }
+
+    pub fn source(&self) -> &SdkSource {
+        &self.source
+    }
+
+    pub fn core_lib_dir(&self) -> PathBuf {
+        self.root.join(SdkComponent::CoreLibraries.relative_path(&self.arch))
+    }
+
+    pub fn studio_lib_dir(&self) -> PathBuf {
+        self.root.join(SdkComponent::StudioLibraries.relative_path(&self.arch))
+    }
+
+    pub fn example_banks_dir(&self) -> PathBuf {
+        self.root.join(SdkComponent::ExampleMedia.relative_path(&self.arch))
+    }
+
+    pub fn example_bank_path(&self, bank_name: &str) -> PathBuf {
+        self.example_banks_dir().join(bank_name)
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+fn config_file_candidates() -> Vec<PathBuf> {
+    let mut candidates = vec![PathBuf::from(SDK_CONFIG_FILE)];
+    if let Some(config_home) = env::var_os("XDG_CONFIG_HOME") {
+        candidates.push(PathBuf::from(config_home).join("libfmod-demos").join(SDK_CONFIG_FILE));
+    } else if let Some(home) = home_dir() {
+        candidates.push(home.join(".config").join("libfmod-demos").join(SDK_CONFIG_FILE));
+    }
+    candidates
+}
+
+fn read_config_file(path: &Path) -> Result<PathBuf, SdkError> {
+    let contents = fs::read_to_string(path).map_err(|e| SdkError::Config {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+
+    let line = contents
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .ok_or_else(|| SdkError::Config {
+            path: path.to_path_buf(),
+            message: "no SDK path found".to_string(),
+        })?;
+
+    let expanded = match (line.strip_prefix("~/"), home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(line),
+    };
+
+    // Relative paths are relative to the config file itself
+    if expanded.is_relative()
+        && let Some(parent) = path.parent()
+    {
+        return Ok(parent.join(expanded));
+    }
+    Ok(expanded)
+}
+
+fn install_prefixes() -> Vec<PathBuf> {
+    let mut prefixes = Vec::new();
+    if let Some(home) = home_dir() {
+        prefixes.push(home.join("fmod"));
+        prefixes.push(home.join("FMOD Programmers API"));
+    }
+    prefixes.push(PathBuf::from("/opt/fmod"));
+    prefixes.push(PathBuf::from("/usr/local/fmod"));
+    prefixes.push(PathBuf::from("/Applications/FMOD Programmers API"));
+    prefixes.push(PathBuf::from("C:\\Program Files (x86)\\FMOD SoundSystem\\FMOD Studio API Windows"));
+    prefixes
+}
+
+/// The prefix itself plus its versioned sub-directories (e.g. `~/fmod/20309`), newest first
+fn prefix_candidates(prefix: &Path) -> Vec<PathBuf> {
+    let mut candidates = vec![prefix.to_path_buf()];
+    if let Ok(entries) = fs::read_dir(prefix) {
+        let mut versioned: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        versioned.sort();
+        versioned.reverse();
+        candidates.extend(versioned);
+    }
+    candidates
+}
+
+fn read_header_version(root: &Path) -> Option<SdkVersion> {
+    let header = fs::read_to_string(root.join("api/core/inc/fmod_common.h")).ok()?;
+    header.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix("#define")?.trim_start();
+        let value = rest.strip_prefix("FMOD_VERSION")?.trim();
+        let hex = value.split_whitespace().next()?;
+        let hex = hex.strip_prefix("0x").or_else(|| hex.strip_prefix("0X"))?;
+        u32::from_str_radix(hex, 16).ok().map(SdkVersion::from_packed)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    const ARCH: &str = "x86_64";
+
+    /// An empty directory under the system temp dir, unique to this test
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("libfmod-demos-sdk-{}-{}", process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Lay out an SDK under `root` with every component except `skip`
+    fn fake_sdk(root: &Path, skip: Option<SdkComponent>) {
+        for component in [SdkComponent::CoreLibraries, SdkComponent::StudioLibraries, SdkComponent::ExampleMedia] {
+            if Some(component) != skip {
+                fs::create_dir_all(root.join(component.relative_path(ARCH))).unwrap();
+            }
+        }
+    }
+
+    fn write_config(dir: &Path, contents: &str) -> PathBuf {
+        let path = dir.join(SDK_CONFIG_FILE);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn packed_version_decodes() {
+        let version = SdkVersion::from_packed(0x0002_0309);
+        assert_eq!(version, SdkVersion { major: 2, minor: 3, patch: 9 });
+        assert_eq!(version.to_string(), "2.03.09");
+        assert!(SdkVersion::from_packed(0x0002_0222) < version);
+        assert_eq!(SdkVersion::from_packed(0x0001_1a0f).to_string(), "1.1a.0f");
+    }
+
+    #[test]
+    fn valid_layout_reads_the_header_version() {
+        let root = scratch_dir("valid");
+        fake_sdk(&root, None);
+        fs::create_dir_all(root.join("api/core/inc")).unwrap();
+        fs::write(
+            root.join("api/core/inc/fmod_common.h"),
+            "#define FMOD_VERSION_MAJOR 2\n#define FMOD_VERSION    0x00020309    /* 0xaaaabbcc */\n",
+        )
+        .unwrap();
+
+        let sdk = FmodSdk::validate(root.clone(), ARCH, SdkSource::Explicit).unwrap();
+        assert_eq!(sdk.version(), Some(SdkVersion { major: 2, minor: 3, patch: 9 }));
+        assert_eq!(sdk.core_lib_dir(), root.join("api/core/lib/x86_64"));
+        assert_eq!(sdk.example_bank_path("SFX.bank"), root.join("api/studio/examples/media/SFX.bank"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn missing_or_malformed_header_has_no_version() {
+        let root = scratch_dir("no-header");
+        fake_sdk(&root, None);
+        assert_eq!(read_header_version(&root), None);
+
+        fs::create_dir_all(root.join("api/core/inc")).unwrap();
+        fs::write(root.join("api/core/inc/fmod_common.h"), "#define FMOD_VERSION 20309\n").unwrap();
+        assert_eq!(read_header_version(&root), None);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn each_missing_component_is_named() {
+        for component in [SdkComponent::CoreLibraries, SdkComponent::StudioLibraries, SdkComponent::ExampleMedia] {
+            let root = scratch_dir("missing");
+            fake_sdk(&root, Some(component));
+            match FmodSdk::validate(root.clone(), ARCH, SdkSource::Env) {
+                Err(SdkError::MissingComponent { component: missing, path, source }) => {
+                    assert_eq!(missing, component);
+                    assert_eq!(path, root.join(component.relative_path(ARCH)));
+                    assert_eq!(source, SdkSource::Env);
+                }
+                other => panic!("{}: expected a missing component, got {:?}", component, other),
+            }
+            fs::remove_dir_all(&root).unwrap();
+        }
+
+        let dir = scratch_dir("missing-root");
+        let error = FmodSdk::validate(dir.join("nope"), ARCH, SdkSource::Explicit).unwrap_err();
+        assert!(matches!(error, SdkError::MissingComponent { component: SdkComponent::Root, .. }));
+        assert!(error.to_string().contains("SDK root directory"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn config_skips_comments_and_resolves_relative_paths() {
+        let dir = scratch_dir("config-relative");
+        let config = write_config(&dir, "# FMOD SDK location\n\n   # indented comment\n  sdk/fmod  \nignored\n");
+        assert_eq!(read_config_file(&config).unwrap(), dir.join("sdk/fmod"));
+
+        let config = write_config(&dir, "/opt/fmod/20309\n");
+        assert_eq!(read_config_file(&config).unwrap(), PathBuf::from("/opt/fmod/20309"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn config_expands_home() {
+        let Some(home) = home_dir() else { return };
+        let dir = scratch_dir("config-home");
+        let config = write_config(&dir, "~/fmod/20309\n");
+        assert_eq!(read_config_file(&config).unwrap(), home.join("fmod/20309"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn config_without_a_path_is_an_error() {
+        let dir = scratch_dir("config-empty");
+        let config = write_config(&dir, "# only a comment\n\n");
+        match read_config_file(&config) {
+            Err(SdkError::Config { path, message }) => {
+                assert_eq!(path, config);
+                assert_eq!(message, "no SDK path found");
+            }
+            other => panic!("expected a config error, got {:?}", other),
+        }
+
+        // A directory where the file should be cannot be read
+        let unreadable = dir.join("as-dir");
+        fs::create_dir_all(&unreadable).unwrap();
+        assert!(matches!(read_config_file(&unreadable), Err(SdkError::Config { .. })));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn search_prefers_the_newest_versioned_directory() {
+        let prefix = scratch_dir("search");
+        fake_sdk(&prefix.join("20222"), None);
+        fake_sdk(&prefix.join("20309"), None);
+        fs::create_dir_all(prefix.join("20400")).unwrap();
+
+        let sdk = FmodSdk::search(std::slice::from_ref(&prefix), ARCH).unwrap();
+        assert_eq!(sdk.root(), prefix.join("20309"));
+        assert_eq!(sdk.source(), &SdkSource::Prefix);
+        fs::remove_dir_all(&prefix).unwrap();
+    }
+
+    #[test]
+    fn search_lists_every_candidate_when_nothing_is_found() {
+        let prefix = scratch_dir("not-found");
+        fs::create_dir_all(prefix.join("20309")).unwrap();
+        let absent = prefix.join("absent");
+
+        match FmodSdk::search(&[prefix.clone(), absent.clone()], ARCH) {
+            Err(SdkError::NotFound { searched }) => {
+                assert_eq!(searched, [prefix.clone(), prefix.join("20309"), absent]);
+            }
+            other => panic!("expected not found, got {:?}", other),
+        }
+        fs::remove_dir_all(&prefix).unwrap();
+    }
+}
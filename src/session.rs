@@ -0,0 +1,333 @@
+use libfmod::{Bank, Init, LoadBank, OutputType, SpeakerMode, Studio, StudioInit, System};
+use std::{
+    ffi::c_void,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use crate::error::FmodError;
+use crate::output::{OutputMode, OutputOptions};
+use crate::sdk::{FmodSdk, SdkError};
+
+/// Errors raised while setting up a Studio session
+#[derive(Debug)]
+pub enum SessionError {
+    /// The example banks were requested but the SDK could not be located
+    Sdk(SdkError),
+    /// An FMOD call failed during setup
+    Fmod { context: String, error: libfmod::Error },
+    /// A bank file failed to load
+    Bank { path: PathBuf, error: libfmod::Error },
+    /// The wavwriter output path cannot be passed to FMOD
+    OutputFile(String),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionError::Sdk(e) => write!(f, "{}", e),
+            SessionError::Fmod { context, error } => write!(f, "{} failed: {:?}", context, error),
+            SessionError::Bank { path, error } => {
+                write!(f, "failed to load bank {}: {:?}", path.display(), error)
+            }
+            SessionError::OutputFile(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+impl FmodError for SessionError {
+    fn from_fmod(context: String, error: libfmod::Error) -> Self {
+        SessionError::Fmod { context, error }
+    }
+}
+
+impl From<SdkError> for SessionError {
+    fn from(e: SdkError) -> Self {
+        SessionError::Sdk(e)
+    }
+}
+
+enum BankSource {
+    Path(PathBuf),
+    Example(String),
+}
+
+/// Builder for [`StudioSession`]
+pub struct StudioSessionBuilder {
+    max_channels: i32,
+    sample_rate: Option<i32>,
+    speaker_mode: Option<SpeakerMode>,
+    output: Option<OutputType>,
+    // Resolved mode and wavwriter file, for the driver data and NRT update flag
+    output_options: OutputOptions,
+    random_seed: Option<u32>,
+    studio_flags: StudioInit,
+    core_flags: Init,
+    banks: Vec<BankSource>,
+}
+
+impl Default for StudioSessionBuilder {
+    fn default() -> Self {
+        StudioSessionBuilder {
+            max_channels: 1024,
+            sample_rate: None,
+            speaker_mode: None,
+            output: None,
+            output_options: OutputOptions::default(),
+            random_seed: None,
+            studio_flags: StudioInit::NORMAL,
+            core_flags: Init::NORMAL,
+            banks: Vec::new(),
+        }
+    }
+}
+
+impl StudioSessionBuilder {
+    pub fn max_channels(mut self, max_channels: i32) -> Self {
+        self.max_channels = max_channels;
+        self
+    }
+
+    pub fn sample_rate(mut self, sample_rate: i32) -> Self {
+        self.sample_rate = Some(sample_rate);
+        self
+    }
+
+    pub fn speaker_mode(mut self, speaker_mode: SpeakerMode) -> Self {
+        self.speaker_mode = Some(speaker_mode);
+        self
+    }
+
+    /// Output driver selected with `set_output` before initialization
+    pub fn output(mut self, output: OutputType) -> Self {
+        self.output = Some(output);
+        self.output_options.mode = OutputMode::from_output_type(output);
+        self
+    }
+
+    /// File written by a wavwriter output
+    pub fn output_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.output_options.file = Some(path.into());
+        self
+    }
+
+    /// Apply the shared `--output` / `FMOD_OUTPUT` settings
+    ///
+    /// A file set earlier with [`output_file`](Self::output_file) is kept when
+    /// `options` names none. Non-realtime modes also switch Studio to synchronous
+    /// updates, so each `studio.update()` mixes exactly one block.
+    pub fn output_options(mut self, options: &OutputOptions) -> Self {
+        self.output = options.mode.output_type();
+        self.output_options = OutputOptions {
+            mode: options.mode,
+            file: options.file.clone().or(self.output_options.file.take()),
+        };
+        self
+    }
+
+    /// Seed FMOD's random number generator so variations are repeatable
+    pub fn random_seed(mut self, seed: u32) -> Self {
+        self.random_seed = Some(seed);
+        self
+    }
+
+    /// Studio init flags; `SYNCHRONOUS_UPDATE` is added for non-realtime outputs regardless
+    pub fn studio_flags(mut self, flags: StudioInit) -> Self {
+        self.studio_flags = flags;
+        self
+    }
+
+    pub fn core_flags(mut self, flags: Init) -> Self {
+        self.core_flags = flags;
+        self
+    }
+
+    /// Load a bank file by path
+    pub fn bank(mut self, path: impl Into<PathBuf>) -> Self {
+        self.banks.push(BankSource::Path(path.into()));
+        self
+    }
+
+    /// Load banks by path, in order
+    pub fn banks<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.banks.extend(paths.into_iter().map(|p| BankSource::Path(p.into())));
+        self
+    }
+
+    /// Load banks by name from the SDK's example media directory
+    pub fn example_banks(mut self, names: &[&str]) -> Self {
+        self.banks.extend(names.iter().map(|n| BankSource::Example(n.to_string())));
+        self
+    }
+
+    /// Create and initialize the Studio system and load all banks
+    ///
+    /// If any step fails, everything set up so far is torn down again.
+    pub fn build(self) -> Result<StudioSession, SessionError> {
+        // Resolve bank paths first so a bad SDK path fails before FMOD starts
+        let sdk = if self.banks.iter().any(|b| matches!(b, BankSource::Example(_))) {
+            Some(FmodSdk::locate()?)
+        } else {
+            None
+        };
+        let bank_paths: Vec<PathBuf> = self
+            .banks
+            .into_iter()
+            .map(|bank| match (bank, &sdk) {
+                (BankSource::Path(path), _) => path,
+                (BankSource::Example(name), Some(sdk)) => sdk.example_bank_path(&name),
+                (BankSource::Example(name), None) => PathBuf::from(name),
+            })
+            .collect();
+
+        let studio = Studio::create().map_err(SessionError::fmod("Studio::create"))?;
+
+        // From here on the guard owns the studio and cleans up on early return
+        let mut session = StudioSession {
+            studio,
+            banks: Vec::new(),
+            closed: false,
+        };
+
+        let core = session.core().map_err(SessionError::fmod("get_core_system"))?;
+
+        if let Some(output) = self.output {
+            core.set_output(output).map_err(SessionError::fmod("set_output"))?;
+        }
+
+        if let Some(seed) = self.random_seed {
+            let mut settings = core.get_advanced_settings().map_err(SessionError::fmod("get_advanced_settings"))?;
+            settings.random_seed = seed;
+            core.set_advanced_settings(settings).map_err(SessionError::fmod("set_advanced_settings"))?;
+        }
+
+        if self.sample_rate.is_some() || self.speaker_mode.is_some() {
+            core.set_software_format(self.sample_rate, self.speaker_mode, Some(0))
+                .map_err(SessionError::fmod("set_software_format"))?;
+        }
+
+        // FMOD only reads the wavwriter file name during initialize
+        let driver_data = self.output_options.driver_data().map_err(SessionError::OutputFile)?;
+
+        // Applied here so a later `studio_flags` call cannot drop it
+        let mut studio_flags = self.studio_flags;
+        if !self.output_options.is_realtime() {
+            studio_flags |= StudioInit::SYNCHRONOUS_UPDATE;
+        }
+
+        session
+            .studio
+            .initialize(
+                self.max_channels,
+                studio_flags,
+                self.core_flags,
+                driver_data.as_ref().map(|s| s.as_ptr() as *mut c_void),
+            )
+            .map_err(SessionError::fmod("Studio::initialize"))?;
+
+        for path in bank_paths {
+            session.load_bank(&path)?;
+        }
+
+        Ok(session)
+    }
+}
+
+/// An initialized Studio system plus the banks it loaded
+///
+/// Banks are unloaded in reverse order and the system is released when the
+/// session is dropped, so an early `?` return never leaks them. Call
+/// [`StudioSession::close`] to see teardown errors instead of ignoring them.
+pub struct StudioSession {
+    studio: Studio,
+    banks: Vec<Bank>,
+    closed: bool,
+}
+
+impl StudioSession {
+    pub fn builder() -> StudioSessionBuilder {
+        StudioSessionBuilder::default()
+    }
+
+    pub fn studio(&self) -> &Studio {
+        &self.studio
+    }
+
+    pub fn core(&self) -> Result<System, libfmod::Error> {
+        self.studio.get_core_system()
+    }
+
+    pub fn banks(&self) -> &[Bank] {
+        &self.banks
+    }
+
+    /// Load another bank that will be unloaded with the session
+    pub fn load_bank(&mut self, path: &Path) -> Result<Bank, SessionError> {
+        let bank = self
+            .studio
+            .load_bank_file(&path.to_string_lossy(), LoadBank::NORMAL)
+            .map_err(|error| SessionError::Bank {
+                path: path.to_path_buf(),
+                error,
+            })?;
+        self.banks.push(bank);
+        Ok(bank)
+    }
+
+    /// Load every bank that can be loaded, collecting failures instead of stopping
+    ///
+    /// Returns the paths that loaded, in order, and an error for each that did
+    /// not. Localized banks (`Dialogue_EN.bank`, `Dialogue_JP.bank`, ...) share
+    /// one GUID, so only the first of a set loads alongside the others.
+    pub fn load_banks<I, P>(&mut self, paths: I) -> (Vec<PathBuf>, Vec<SessionError>)
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        let mut loaded = Vec::new();
+        let mut failed = Vec::new();
+        for path in paths {
+            let path = path.into();
+            match self.load_bank(&path) {
+                Ok(_) => loaded.push(path),
+                Err(error) => failed.push(error),
+            }
+        }
+        (loaded, failed)
+    }
+
+    /// Unload all banks and release the system, reporting the first error
+    pub fn close(mut self) -> Result<(), libfmod::Error> {
+        self.teardown()
+    }
+
+    fn teardown(&mut self) -> Result<(), libfmod::Error> {
+        if self.closed {
+            return Ok(());
+        }
+        self.closed = true;
+
+        let mut result = Ok(());
+        while let Some(bank) = self.banks.pop() {
+            if let Err(e) = bank.unload() {
+                result = result.and(Err(e));
+            }
+        }
+        if let Err(e) = self.studio.release() {
+            result = result.and(Err(e));
+        }
+        result
+    }
+}
+
+impl Drop for StudioSession {
+    fn drop(&mut self) {
+        let _ = self.teardown();
+    }
+}
@@ -0,0 +1,111 @@
+use libfmod::{EventDescription, EventInstance, PlaybackState, StopMode, Studio};
+
+use crate::inspect::{self, EventInfo};
+
+/// Built-in parameter controlling a snapshot's intensity (0-100)
+pub const SNAPSHOT_INTENSITY: &str = "Intensity";
+
+/// Intensity of a newly started snapshot: fully applied
+pub const FULL_INTENSITY: f32 = 100.0;
+
+/// A `snapshot:/` description from the loaded banks, and its instance while started
+///
+/// Snapshots are started and stopped like events; while one plays, its
+/// intensity blends its mixer changes in (0 has no effect, 100 applies them
+/// fully).
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub description: EventDescription,
+    pub info: EventInfo,
+    /// Intensity applied when started, and the current one while active
+    pub intensity: f32,
+    /// Playback state at the last refresh; `None` while not started
+    pub state: Option<PlaybackState>,
+    instance: Option<EventInstance>,
+}
+
+impl Snapshot {
+    pub fn new(description: EventDescription) -> Result<Self, libfmod::Error> {
+        Ok(Snapshot {
+            description,
+            info: inspect::inspect_event(&description)?,
+            intensity: FULL_INTENSITY,
+            state: None,
+            instance: None,
+        })
+    }
+
+    /// Look up a snapshot by `snapshot:/` path
+    pub fn find(studio: &Studio, path: &str) -> Result<Self, libfmod::Error> {
+        Self::new(studio.get_event(path)?)
+    }
+
+    /// Every snapshot in the loaded banks, sorted by path
+    pub fn list(studio: &Studio) -> Result<Vec<Self>, libfmod::Error> {
+        inspect::list_snapshots(studio)?
+            .into_iter()
+            .map(|(description, _)| Self::new(description))
+            .collect()
+    }
+
+    pub fn instance(&self) -> Option<EventInstance> {
+        self.instance
+    }
+
+    /// Started and not yet stopped
+    pub fn is_active(&self) -> bool {
+        self.instance.is_some()
+    }
+
+    /// Start a new instance at the current intensity; does nothing if already active
+    pub fn start(&mut self) -> Result<(), libfmod::Error> {
+        if self.instance.is_some() {
+            return Ok(());
+        }
+        let instance = self.description.create_instance()?;
+        instance.set_parameter_by_name(SNAPSHOT_INTENSITY, self.intensity, true)?;
+        instance.start()?;
+        self.instance = Some(instance);
+        self.state = Some(PlaybackState::Starting);
+        Ok(())
+    }
+
+    /// Stop and release the instance; with `AllowFadeout` the mix eases back
+    pub fn stop(&mut self, mode: StopMode) -> Result<(), libfmod::Error> {
+        if let Some(instance) = self.instance.take() {
+            instance.stop(mode)?;
+            instance.release()?;
+        }
+        self.state = None;
+        Ok(())
+    }
+
+    pub fn toggle(&mut self) -> Result<(), libfmod::Error> {
+        if self.is_active() {
+            self.stop(StopMode::AllowFadeout)
+        } else {
+            self.start()
+        }
+    }
+
+    /// Set the intensity (clamped to 0-100), applying it at once if active
+    pub fn set_intensity(&mut self, intensity: f32) -> Result<(), libfmod::Error> {
+        self.intensity = intensity.clamp(0.0, FULL_INTENSITY);
+        if let Some(instance) = self.instance {
+            instance.set_parameter_by_name(SNAPSHOT_INTENSITY, self.intensity, false)?;
+        }
+        Ok(())
+    }
+
+    /// Read the playback state, forgetting an instance that has gone away
+    pub fn refresh(&mut self) -> Result<(), libfmod::Error> {
+        self.state = match self.instance {
+            Some(instance) if instance.is_valid() => Some(instance.get_playback_state()?),
+            _ => None,
+        };
+        if self.state.is_none() {
+            self.instance = None;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+use libfmod::{Attributes3d, Studio, Vector};
+use std::time::Duration;
+
+/// World up; FMOD's default coordinate system is left-handed with +Y up
+pub const UP: Vector = Vector { x: 0.0, y: 1.0, z: 0.0 };
+
+/// Zero vector, for sources and listeners that don't move
+pub const ZERO: Vector = Vector { x: 0.0, y: 0.0, z: 0.0 };
+
+/// Direction faced at yaw 0 (towards -Z, as in the demos)
+pub const FORWARD: Vector = Vector { x: 0.0, y: 0.0, z: -1.0 };
+
+pub fn vector(x: f32, y: f32, z: f32) -> Vector {
+    Vector { x, y, z }
+}
+
+/// Forward vector for a heading in degrees, turning clockwise seen from above
+///
+/// Yaw 0 faces -Z and yaw 90 faces -X, which FMOD pans to the right.
+pub fn yaw_forward(yaw_degrees: f32) -> Vector {
+    let (sin, cos) = yaw_degrees.to_radians().sin_cos();
+    Vector { x: -sin, y: 0.0, z: -cos }
+}
+
+pub fn distance(a: &Vector, b: &Vector) -> f32 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt()
+}
+
+/// 3D attributes with +Y up
+pub fn attributes(position: Vector, velocity: Vector, forward: Vector) -> Attributes3d {
+    Attributes3d {
+        position,
+        velocity,
+        forward,
+        up: UP,
+    }
+}
+
+/// A Studio listener: where it is, which way it faces and how much it counts
+#[derive(Debug, Clone, PartialEq)]
+pub struct Listener {
+    pub position: Vector,
+    /// Units per second, for doppler; see [`VelocityTracker`]
+    pub velocity: Vector,
+    /// Heading in degrees, see [`yaw_forward`]
+    pub yaw: f32,
+    /// Mixing weight, 0.0 to 1.0; FMOD spatializes for the weighted listeners
+    pub weight: f32,
+}
+
+impl Listener {
+    pub fn new(position: Vector) -> Self {
+        Listener {
+            position,
+            velocity: ZERO,
+            yaw: 0.0,
+            weight: 1.0,
+        }
+    }
+
+    pub fn forward(&self) -> Vector {
+        yaw_forward(self.yaw)
+    }
+
+    /// Turn by `degrees`, keeping the heading within 0-360
+    pub fn turn(&mut self, degrees: f32) {
+        self.yaw = (self.yaw + degrees).rem_euclid(360.0);
+    }
+
+    pub fn attributes(&self) -> Attributes3d {
+        attributes(self.position.clone(), self.velocity.clone(), self.forward())
+    }
+}
+
+/// A 3D event instance's placement: position, velocity and heading
+#[derive(Debug, Clone, PartialEq)]
+pub struct Emitter {
+    pub position: Vector,
+    /// Units per second, for doppler; see [`VelocityTracker`]
+    pub velocity: Vector,
+    /// Heading in degrees, see [`yaw_forward`]
+    pub yaw: f32,
+}
+
+impl Emitter {
+    pub fn new(position: Vector) -> Self {
+        Emitter {
+            position,
+            velocity: ZERO,
+            yaw: 0.0,
+        }
+    }
+
+    pub fn forward(&self) -> Vector {
+        yaw_forward(self.yaw)
+    }
+
+    /// Turn by `degrees`, keeping the heading within 0-360
+    pub fn turn(&mut self, degrees: f32) {
+        self.yaw = (self.yaw + degrees).rem_euclid(360.0);
+    }
+
+    pub fn attributes(&self) -> Attributes3d {
+        attributes(self.position.clone(), self.velocity.clone(), self.forward())
+    }
+}
+
+/// Derives velocity from successive positions, for doppler
+///
+/// FMOD does not work out velocity itself: it uses whatever is passed in
+/// `Attributes3d`, so a source that moves with zero velocity has no doppler
+/// shift and one with a made-up velocity has the wrong one. Feed the tracker
+/// every frame with the current position and the frame time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VelocityTracker {
+    last: Option<Vector>,
+    velocity: Vector,
+    smoothing: Duration,
+}
+
+impl Default for VelocityTracker {
+    fn default() -> Self {
+        VelocityTracker {
+            last: None,
+            velocity: ZERO,
+            smoothing: Duration::ZERO,
+        }
+    }
+}
+
+impl VelocityTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Average the velocity over roughly `time_constant` (default: none)
+    ///
+    /// Useful when positions jump, e.g. a keypress moving a source a metre
+    /// in one frame, which would otherwise be a one-frame burst of speed.
+    pub fn smoothing(mut self, time_constant: Duration) -> Self {
+        self.smoothing = time_constant;
+        self
+    }
+
+    /// Record the position after `elapsed` and return the new velocity
+    ///
+    /// The first call only records the position. Calls with no elapsed time
+    /// keep the previous velocity.
+    pub fn update(&mut self, position: &Vector, elapsed: Duration) -> Vector {
+        let seconds = elapsed.as_secs_f32();
+        if let Some(last) = &self.last
+            && seconds > 0.0
+        {
+            let measured = Vector {
+                x: (position.x - last.x) / seconds,
+                y: (position.y - last.y) / seconds,
+                z: (position.z - last.z) / seconds,
+            };
+            let weight = if self.smoothing.is_zero() {
+                1.0
+            } else {
+                1.0 - (-seconds / self.smoothing.as_secs_f32()).exp()
+            };
+            self.velocity = Vector {
+                x: self.velocity.x + (measured.x - self.velocity.x) * weight,
+                y: self.velocity.y + (measured.y - self.velocity.y) * weight,
+                z: self.velocity.z + (measured.z - self.velocity.z) * weight,
+            };
+        }
+        if self.last.is_none() || seconds > 0.0 {
+            self.last = Some(position.clone());
+        }
+        self.velocity()
+    }
+
+    pub fn velocity(&self) -> Vector {
+        self.velocity.clone()
+    }
+
+    /// Forget the last position, e.g. after teleporting, so no speed is reported
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.velocity = ZERO;
+    }
+}
+
+/// Set the listener count, attributes and weights on the Studio system
+///
+/// FMOD supports up to eight listeners; with several, each event is
+/// spatialized for the closest one and weights fade listeners in or out.
+pub fn apply_listeners(studio: &Studio, listeners: &[Listener]) -> Result<(), libfmod::Error> {
+    studio.set_num_listeners(listeners.len().max(1) as i32)?;
+    for (index, listener) in listeners.iter().enumerate() {
+        let index = index as i32;
+        studio.set_listener_attributes(index, listener.attributes(), None)?;
+        studio.set_listener_weight(index, listener.weight.clamp(0.0, 1.0))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: &Vector, expected: &Vector) {
+        assert!(distance(actual, expected) < 1e-3, "{:?} is not near {:?}", actual, expected);
+    }
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn first_sample_only_records_the_position() {
+        let mut tracker = VelocityTracker::new();
+        assert_eq!(tracker.update(&vector(100.0, 0.0, 0.0), ms(50)), ZERO);
+        assert_near(&tracker.update(&vector(101.0, 0.0, -2.0), ms(100)), &vector(10.0, 0.0, -20.0));
+    }
+
+    #[test]
+    fn velocity_is_the_position_delta_over_time() {
+        let mut tracker = VelocityTracker::new();
+        tracker.update(&ZERO, Duration::ZERO);
+        assert_near(&tracker.update(&vector(0.0, 1.0, 0.0), ms(500)), &vector(0.0, 2.0, 0.0));
+        // Unsmoothed, each frame replaces the velocity outright
+        assert_near(&tracker.update(&vector(0.0, 1.0, 0.0), ms(500)), &ZERO);
+        assert_near(&tracker.velocity(), &ZERO);
+    }
+
+    #[test]
+    fn zero_elapsed_keeps_the_previous_velocity_and_position() {
+        let mut tracker = VelocityTracker::new();
+        tracker.update(&ZERO, Duration::ZERO);
+        tracker.update(&vector(1.0, 0.0, 0.0), ms(100));
+
+        // A repeated frame must not divide by zero or swallow the movement
+        assert_near(&tracker.update(&vector(5.0, 0.0, 0.0), Duration::ZERO), &vector(10.0, 0.0, 0.0));
+        assert_near(&tracker.update(&vector(2.0, 0.0, 0.0), ms(100)), &vector(10.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn reset_after_a_teleport_reports_no_speed() {
+        let mut tracker = VelocityTracker::new();
+        tracker.update(&ZERO, Duration::ZERO);
+        tracker.update(&vector(1.0, 0.0, 0.0), ms(100));
+
+        tracker.reset();
+        assert_eq!(tracker.velocity(), ZERO);
+        assert_eq!(tracker.update(&vector(500.0, 0.0, 0.0), ms(16)), ZERO);
+        assert_near(&tracker.update(&vector(501.0, 0.0, 0.0), ms(100)), &vector(10.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn smoothing_damps_a_sudden_jump() {
+        let mut tracker = VelocityTracker::new().smoothing(ms(250));
+        tracker.update(&ZERO, Duration::ZERO);
+        let jump = tracker.update(&vector(1.0, 0.0, 0.0), ms(10));
+        // Unsmoothed this would be 100 units/s
+        assert!(jump.x > 0.0 && jump.x < 10.0, "{:?}", jump);
+        assert_eq!((jump.y, jump.z), (0.0, 0.0));
+
+        // Holding still decays it towards zero
+        let mut velocity = jump.x;
+        for _ in 0..100 {
+            velocity = tracker.update(&vector(1.0, 0.0, 0.0), ms(10)).x;
+        }
+        assert!(velocity < 0.1, "{}", velocity);
+    }
+}
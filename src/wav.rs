@@ -0,0 +1,287 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+/// Sample encoding of a WAV file's `data` chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleFormat {
+    Pcm16,
+    Pcm24,
+    Pcm32,
+    Float32,
+}
+
+impl SampleFormat {
+    pub fn bytes_per_sample(&self) -> usize {
+        match self {
+            SampleFormat::Pcm16 => 2,
+            SampleFormat::Pcm24 => 3,
+            SampleFormat::Pcm32 | SampleFormat::Float32 => 4,
+        }
+    }
+
+    fn format_tag(&self) -> u16 {
+        match self {
+            SampleFormat::Float32 => WAVE_FORMAT_IEEE_FLOAT,
+            _ => WAVE_FORMAT_PCM,
+        }
+    }
+}
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// An in-memory WAV file with interleaved samples normalized to -1.0..1.0
+#[derive(Debug, Clone, PartialEq)]
+pub struct WavFile {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub format: SampleFormat,
+    pub samples: Vec<f32>,
+}
+
+impl WavFile {
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse(&fs::read(path)?)
+    }
+
+    /// Parse a RIFF/WAVE byte buffer (PCM 16/24/32 bit or 32 bit float)
+    pub fn parse(bytes: &[u8]) -> io::Result<Self> {
+        if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+            return Err(invalid("not a RIFF/WAVE file"));
+        }
+
+        let mut format = None;
+        let mut data = None;
+        let mut pos = 12;
+        while pos + 8 <= bytes.len() {
+            let id = &bytes[pos..pos + 4];
+            let size = u32::from_le_bytes([bytes[pos + 4], bytes[pos + 5], bytes[pos + 6], bytes[pos + 7]]) as usize;
+            let body_start = pos + 8;
+            // Writers that never finalized the header leave the size at 0 or too large
+            let body_end = (body_start + size).min(bytes.len());
+            let body = &bytes[body_start..body_end];
+
+            match id {
+                b"fmt " => format = Some(parse_format(body)?),
+                b"data" if size == 0 => {
+                    data = Some(&bytes[body_start..]);
+                    break;
+                }
+                b"data" => data = Some(body),
+                _ => {}
+            }
+
+            // Chunks are padded to an even size
+            pos = body_start + size + (size & 1);
+        }
+
+        let (sample_rate, channels, format) = format.ok_or_else(|| invalid("missing fmt chunk"))?;
+        let data = data.ok_or_else(|| invalid("missing data chunk"))?;
+        let samples = decode(data, format);
+
+        Ok(WavFile {
+            sample_rate,
+            channels,
+            format,
+            samples,
+        })
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut file = fs::File::create(path)?;
+        file.write_all(&self.to_bytes())?;
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let bytes_per_sample = self.format.bytes_per_sample();
+        let data_len = self.samples.len() * bytes_per_sample;
+        let block_align = self.channels as usize * bytes_per_sample;
+
+        let mut out = Vec::with_capacity(44 + data_len);
+        out.extend_from_slice(b"RIFF");
+        out.extend_from_slice(&((36 + data_len) as u32).to_le_bytes());
+        out.extend_from_slice(b"WAVE");
+        out.extend_from_slice(b"fmt ");
+        out.extend_from_slice(&16u32.to_le_bytes());
+        out.extend_from_slice(&self.format.format_tag().to_le_bytes());
+        out.extend_from_slice(&self.channels.to_le_bytes());
+        out.extend_from_slice(&self.sample_rate.to_le_bytes());
+        out.extend_from_slice(&((self.sample_rate as usize * block_align) as u32).to_le_bytes());
+        out.extend_from_slice(&(block_align as u16).to_le_bytes());
+        out.extend_from_slice(&((bytes_per_sample * 8) as u16).to_le_bytes());
+        out.extend_from_slice(b"data");
+        out.extend_from_slice(&(data_len as u32).to_le_bytes());
+
+        for &sample in &self.samples {
+            let s = sample.clamp(-1.0, 1.0);
+            match self.format {
+                SampleFormat::Pcm16 => out.extend_from_slice(&((s * i16::MAX as f32) as i16).to_le_bytes()),
+                SampleFormat::Pcm24 => {
+                    let v = (s * 8_388_607.0) as i32;
+                    out.extend_from_slice(&v.to_le_bytes()[0..3]);
+                }
+                SampleFormat::Pcm32 => out.extend_from_slice(&((s as f64 * i32::MAX as f64) as i32).to_le_bytes()),
+                SampleFormat::Float32 => out.extend_from_slice(&sample.to_le_bytes()),
+            }
+        }
+        out
+    }
+
+    /// Number of sample frames (samples per channel)
+    pub fn frames(&self) -> usize {
+        if self.channels == 0 {
+            0
+        } else {
+            self.samples.len() / self.channels as usize
+        }
+    }
+
+    pub fn duration_secs(&self) -> f64 {
+        if self.sample_rate == 0 {
+            0.0
+        } else {
+            self.frames() as f64 / self.sample_rate as f64
+        }
+    }
+
+    /// Cut or zero-pad the file to exactly `frames` sample frames
+    pub fn set_frames(&mut self, frames: usize) {
+        self.samples.resize(frames * self.channels as usize, 0.0);
+    }
+
+    /// Samples of one channel, de-interleaved
+    pub fn channel(&self, index: u16) -> Vec<f32> {
+        self.samples
+            .iter()
+            .skip(index as usize)
+            .step_by(self.channels.max(1) as usize)
+            .copied()
+            .collect()
+    }
+
+    /// Average of all channels per frame
+    pub fn mono_mix(&self) -> Vec<f32> {
+        let channels = self.channels.max(1) as usize;
+        self.samples
+            .chunks(channels)
+            .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+            .collect()
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn parse_format(body: &[u8]) -> io::Result<(u32, u16, SampleFormat)> {
+    if body.len() < 16 {
+        return Err(invalid("fmt chunk too short"));
+    }
+    let mut tag = u16::from_le_bytes([body[0], body[1]]);
+    let channels = u16::from_le_bytes([body[2], body[3]]);
+    let sample_rate = u32::from_le_bytes([body[4], body[5], body[6], body[7]]);
+    let bits = u16::from_le_bytes([body[14], body[15]]);
+
+    // WAVE_FORMAT_EXTENSIBLE keeps the real format tag at the start of the sub-format GUID
+    if tag == WAVE_FORMAT_EXTENSIBLE && body.len() >= 26 {
+        tag = u16::from_le_bytes([body[24], body[25]]);
+    }
+
+    let format = match (tag, bits) {
+        (WAVE_FORMAT_PCM, 16) => SampleFormat::Pcm16,
+        (WAVE_FORMAT_PCM, 24) => SampleFormat::Pcm24,
+        (WAVE_FORMAT_PCM, 32) => SampleFormat::Pcm32,
+        (WAVE_FORMAT_IEEE_FLOAT, 32) => SampleFormat::Float32,
+        _ => {
+            return Err(invalid(&format!(
+                "unsupported sample format (tag {}, {} bits)",
+                tag, bits
+            )));
+        }
+    };
+    Ok((sample_rate, channels, format))
+}
+
+fn decode(data: &[u8], format: SampleFormat) -> Vec<f32> {
+    let width = format.bytes_per_sample();
+    data.chunks_exact(width)
+        .map(|b| match format {
+            SampleFormat::Pcm16 => i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0,
+            SampleFormat::Pcm24 => {
+                // Sign-extend by placing the 24 bits in the top of an i32
+                (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f32 / 8_388_608.0
+            }
+            SampleFormat::Pcm32 => (i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64 / 2_147_483_648.0) as f32,
+            SampleFormat::Float32 => f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(format: SampleFormat) -> WavFile {
+        WavFile {
+            sample_rate: 48000,
+            channels: 2,
+            format,
+            samples: (0..200).map(|i| (i as f32 * 0.1).sin() * 0.9).collect(),
+        }
+    }
+
+    #[test]
+    fn round_trips_every_format() {
+        for (format, tolerance) in [
+            (SampleFormat::Pcm16, 1e-4),
+            (SampleFormat::Pcm24, 1e-6),
+            (SampleFormat::Pcm32, 1e-6),
+            (SampleFormat::Float32, 0.0),
+        ] {
+            let original = sine(format);
+            let parsed = WavFile::parse(&original.to_bytes()).unwrap();
+            assert_eq!(parsed.sample_rate, 48000);
+            assert_eq!(parsed.channels, 2);
+            assert_eq!(parsed.format, format);
+            assert_eq!(parsed.frames(), 100);
+            for (a, b) in original.samples.iter().zip(&parsed.samples) {
+                assert!((a - b).abs() <= tolerance, "{:?}: {} vs {}", format, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn reads_data_chunk_with_unfinalized_size() {
+        let mut bytes = sine(SampleFormat::Float32).to_bytes();
+        // A writer that was killed before patching the data size
+        bytes[40..44].copy_from_slice(&0u32.to_le_bytes());
+        assert_eq!(WavFile::parse(&bytes).unwrap().samples.len(), 200);
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(WavFile::parse(b"RIFF\0\0\0\0AVI ").is_err());
+        let mut bytes = sine(SampleFormat::Pcm16).to_bytes();
+        bytes[34..36].copy_from_slice(&8u16.to_le_bytes());
+        assert!(WavFile::parse(&bytes).is_err());
+    }
+
+    #[test]
+    fn splits_and_mixes_channels() {
+        let wav = WavFile {
+            sample_rate: 10,
+            channels: 2,
+            format: SampleFormat::Float32,
+            samples: vec![1.0, 0.0, 0.5, -0.5],
+        };
+        assert_eq!(wav.channel(0), vec![1.0, 0.5]);
+        assert_eq!(wav.channel(1), vec![0.0, -0.5]);
+        assert_eq!(wav.mono_mix(), vec![0.5, 0.0]);
+        assert_eq!(wav.duration_secs(), 0.2);
+    }
+}